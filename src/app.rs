@@ -4,30 +4,86 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::thread;
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tracing::{info, error, warn};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use chrono::{DateTime, Local};
+use tracing::{debug, info, error, warn};
 
 use crate::core::AppConfig;
 use crate::core::daemon::BackupDaemon;
+use crate::core::backup::{BackupResult, SkipReason};
+use crate::core::config_exchange::{self, ImportPreview};
+use crate::core::config_watch::ConfigWatcher;
+use crate::core::paths;
+use crate::core::preferences::{AppTheme, UiPreferences};
+use crate::core::profiles::ProfileStore;
+use crate::core::retry::AttemptRecord;
+use crate::core::events::{AppEvent, EventBus, PairProgress};
+use crate::core::history::{HistoryStore, RunOutcome, RunRecord};
+use crate::core::progress::{RunProgress, TransferProgress};
+use crate::core::runner::{throughput_mb_s, BackupRunner, RunTrigger};
 use crate::system::tray::SystemTray;
 use crate::ui::main_window::{MainWindow, UIAction};
-use crate::ui::settings_window::{SettingsWindow, SettingsAction};
+use crate::ui::settings_window::{apply_draft, SettingsWindow, SettingsAction};
+use crate::ui::statistics_window::StatisticsWindow;
+use crate::ui::import_dialog::ImportDialog;
+use crate::ui::config_conflict;
 
 /// Estado de ejecución de un backup pair individual
 #[derive(Debug, Clone)]
 pub enum BackupStatus {
     Pending,    // No ejecutado aún
     Running,    // En ejecución 
+    Retrying { attempt: u32, max_attempts: u32, next_retry_at: u64 }, // Esperando reintento tras un fallo
+    Skipped(SkipReason),       // Omitido (ej. origen sin cambios)
     Success(BackupMetrics),    // Completado exitosamente con métricas
     Warning(String), // Completado con advertencias
     Error(String),   // Falló con error
 }
 
+impl BackupStatus {
+    /// Estado final de un pair a partir del resultado del runner
+    pub fn from_result(result: BackupResult, error: Option<String>, duration: Duration) -> Self {
+        match result {
+            BackupResult::Success { files_copied, bytes_transferred } => {
+                BackupStatus::Success(BackupMetrics { files_copied, bytes_transferred, duration })
+            }
+            BackupResult::Warning { message, .. } => BackupStatus::Warning(message),
+            BackupResult::Skipped(reason) => BackupStatus::Skipped(reason),
+            BackupResult::Failed => BackupStatus::Error(error.unwrap_or_else(|| "Backup falló".to_string())),
+        }
+    }
+    
+    /// Estado final de una ejecución guardada en el historial
+    pub fn from_record(record: &RunRecord) -> Self {
+        let result = match record.outcome {
+            RunOutcome::Success => BackupResult::Success {
+                files_copied: record.files_copied,
+                bytes_transferred: record.bytes_transferred,
+            },
+            RunOutcome::Warning => BackupResult::Warning {
+                message: record.message.clone().unwrap_or_default(),
+                files_copied: record.files_copied,
+                bytes_transferred: record.bytes_transferred,
+            },
+            RunOutcome::Skipped => BackupResult::Skipped(SkipReason::NoChanges),
+            RunOutcome::Failed => BackupResult::Failed,
+        };
+        Self::from_result(result, record.message.clone(), record.duration())
+    }
+}
+
 /// Métricas de una ejecución de backup
 #[derive(Debug, Clone)]
 pub struct BackupMetrics {
     pub files_copied: u32,
     pub bytes_transferred: u64,
+    pub duration: Duration,
+}
+
+impl BackupMetrics {
+    pub fn throughput_mb_s(&self) -> Option<f64> {
+        throughput_mb_s(self.bytes_transferred, self.duration)
+    }
 }
 
 /// Estado y metadata de un backup pair
@@ -41,6 +97,8 @@ pub struct BackupPairStatus {
     pub success_count: u32,           // Contador de ejecuciones exitosas
     pub files_copied_last: Option<u32>, // Archivos copiados en última ejecución
     pub total_size_transferred: Option<u64>, // Bytes transferidos en última ejecución
+    pub attempts: Vec<AttemptRecord>, // Intentos de la última ejecución
+    pub last_duration: Option<Duration>, // Duración de la última ejecución (cualquier resultado)
 }
 
 impl BackupPairStatus {
@@ -53,10 +111,39 @@ impl BackupPairStatus {
             success_count: 0,
             files_copied_last: None,
             total_size_transferred: None,
+            attempts: Vec::new(),
+            last_duration: None,
         }
     }
     
+    /// Reconstruir contadores y último estado a partir del historial persistido
+    pub fn from_history(backup_pair_id: String, records: &[RunRecord]) -> Self {
+        let mut status = Self::new(backup_pair_id);
+        for record in records {
+            status.update_execution(BackupStatus::from_record(record));
+            if record.counts_as_execution() {
+                status.last_execution = Some(record.finished_at.max(0) as u64);
+                status.last_duration = Some(record.duration());
+            }
+        }
+        status
+    }
+    
     pub fn update_execution(&mut self, status: BackupStatus) {
+        // Estados intermedios y pairs omitidos no cuentan como ejecución
+        match status {
+            BackupStatus::Running => {
+                self.attempts.clear();
+                self.status = status;
+                return;
+            }
+            BackupStatus::Retrying { .. } | BackupStatus::Skipped(_) => {
+                self.status = status;
+                return;
+            }
+            _ => {}
+        }
+        
         self.status = status.clone();
         self.execution_count += 1;
         
@@ -129,7 +216,17 @@ pub enum BackgroundCommand {
     StartDaemon,
     StopDaemon,
     RunBackupNow,
-    UpdateConfig(AppConfig),
+    /// Adelantar la ejecución programada del daemon (o backup manual si está detenido)
+    RunScheduledNow,
+    UpdateConfig(Box<AppConfig>),
+    /// config.json cambió en disco (aviso del watcher, incluye los guardados propios)
+    ConfigFileChanged,
+    /// Resolver el conflicto entre config.json editado y los cambios sin guardar de la UI
+    ResolveConfigConflict { use_disk: bool },
+    /// Activar otro perfil (el daemon se detiene y se reanuda con la nueva configuración)
+    SwitchProfile(String),
+    /// Crear un perfil copiando el activo y pasar a usarlo
+    CreateProfile(String),
     
     // === BACKUP PAIR MANAGEMENT ===
     AddBackupPair { source: String, destination: String },
@@ -138,13 +235,19 @@ pub enum BackgroundCommand {
     MoveBackupPairUp(usize),
     MoveBackupPairDown(usize),
     ToggleBackupPairEnabled(usize, bool),
-    
-    // === BACKUP STATUS TRACKING ===
-    UpdateBackupStatus { backup_pair_id: String, status: BackupStatus },
+    ToggleBackupPairContinuous(usize, bool),
     
     Exit,
 }
 
+/// Ejecución en curso y su avance acumulado
+#[derive(Debug, Clone)]
+pub struct ActiveRun {
+    pub run_id: u64,
+    pub trigger: RunTrigger,
+    pub progress: RunProgress,
+}
+
 /// Estado global de la aplicación (independiente de egui)
 #[derive(Debug, Clone)]
 pub struct AppState {
@@ -154,6 +257,162 @@ pub struct AppState {
     
     /// Estado de cada backup pair (key = backup_pair_id)
     pub backup_statuses: HashMap<String, BackupPairStatus>,
+    
+    /// Próxima ejecución programada por el daemon (key = backup_pair_id)
+    pub next_run_at: HashMap<String, DateTime<Local>>,
+    
+    /// Ejecuciones en curso, en orden de inicio (manual, daemon y modo continuo pueden coincidir)
+    pub active_runs: Vec<ActiveRun>,
+    
+    /// Avance de cada pair que está copiando (key = backup_pair_id), sea de la ejecución que sea
+    pub transfers: HashMap<String, TransferProgress>,
+    
+    /// Cambia cada vez que la configuración se reemplaza entera (importación o config.json editado
+    /// fuera de la app): la UI resincroniza sus buffers
+    pub config_generation: u64,
+    
+    /// config.json cambió en disco mientras la UI tenía cambios sin guardar: el usuario decide
+    pub config_conflict: Option<Arc<AppConfig>>,
+    
+    /// Perfiles disponibles y el activo (selector de la ventana y menú del tray)
+    pub profiles: Vec<String>,
+    pub active_profile: String,
+}
+
+impl AppState {
+    /// La próxima ejecución programada de cualquier pair
+    pub fn next_scheduled_run(&self) -> Option<DateTime<Local>> {
+        self.next_run_at.values().min().copied()
+    }
+    
+    /// Avance conjunto de las ejecuciones en curso (pairs terminados y en cola incluidos)
+    pub fn run_progress(&self) -> Option<TransferProgress> {
+        let snapshots: Vec<_> = self.active_runs.iter().filter_map(|run| run.progress.snapshot()).collect();
+        TransferProgress::combined(&snapshots)
+    }
+    
+    fn run_of_pair(&mut self, pair_id: &str) -> Option<&mut RunProgress> {
+        self.active_runs.iter_mut()
+            .map(|run| &mut run.progress)
+            .find(|progress| progress.contains(pair_id))
+    }
+    
+    /// Reflejar un evento del bus en el estado que muestra la UI
+    pub fn apply_event(&mut self, event: &AppEvent) {
+        match event {
+            AppEvent::RunStarted { run_id, trigger, pair_ids } => {
+                debug!("▶️ {}: {} pair(s) en cola", trigger.label(), pair_ids.len());
+                self.active_runs.push(ActiveRun {
+                    run_id: *run_id,
+                    trigger: *trigger,
+                    progress: RunProgress::new(pair_ids.iter().cloned()),
+                });
+            }
+            AppEvent::Progress { pair_id, progress: PairProgress::Started } => {
+                self.update_backup_status(pair_id, BackupStatus::Running);
+            }
+            AppEvent::Progress { pair_id, progress: PairProgress::Attempt(attempt) } => {
+                self.record_backup_attempt(pair_id, attempt);
+            }
+            AppEvent::Progress { pair_id, progress: PairProgress::Transfer(transfer) } => {
+                self.transfers.insert(pair_id.clone(), *transfer);
+                if let Some(run) = self.run_of_pair(pair_id) {
+                    run.update(pair_id, *transfer);
+                }
+            }
+            AppEvent::PairFinished { pair_id, result, error, duration, .. } => {
+                // Solo sale este pair: los de otras ejecuciones siguen mostrando su avance
+                self.transfers.remove(pair_id);
+                if let Some(run) = self.run_of_pair(pair_id) {
+                    run.finish(pair_id);
+                }
+                self.update_backup_status(pair_id, BackupStatus::from_result(result.clone(), error.clone(), *duration));
+                if !matches!(result, BackupResult::Skipped(_)) {
+                    if let Some(status) = self.backup_statuses.get_mut(pair_id) {
+                        status.last_duration = Some(*duration);
+                    }
+                }
+            }
+            AppEvent::RunFinished { run_id, trigger, summary } => {
+                debug!("⏹️ {} terminado: {:?}", trigger.label(), summary);
+                self.active_runs.retain(|run| run.run_id != *run_id);
+            }
+            AppEvent::DaemonStateChanged { running } => {
+                self.daemon_running = *running;
+            }
+            AppEvent::NextRunsChanged(next_runs) => {
+                self.next_run_at = next_runs.clone();
+            }
+            AppEvent::ConfigChanged | AppEvent::Shutdown => {}
+        }
+    }
+    
+    /// Actualizar estado de un backup pair específico
+    fn update_backup_status(&mut self, backup_pair_id: &str, status: BackupStatus) {
+        // Obtener o crear entrada para este backup pair
+        let backup_status = self.backup_statuses
+            .entry(backup_pair_id.to_string())
+            .or_insert_with(|| BackupPairStatus::new(backup_pair_id.to_string()));
+            
+        // Actualizar estado y timestamp
+        backup_status.update_execution(status.clone());
+        
+        info!("📊 Estado actualizado para backup pair {}: {:?}", backup_pair_id, status);
+    }
+    
+    /// Registrar un intento de backup (y el estado de reintento si corresponde)
+    fn record_backup_attempt(&mut self, backup_pair_id: &str, attempt: &AttemptRecord) {
+        let backup_status = self.backup_statuses
+            .entry(backup_pair_id.to_string())
+            .or_insert_with(|| BackupPairStatus::new(backup_pair_id.to_string()));
+        
+        if let Some(delay) = attempt.next_retry_in {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            backup_status.update_execution(BackupStatus::Retrying {
+                attempt: attempt.attempt + 1,
+                max_attempts: attempt.max_attempts,
+                next_retry_at: now + delay.as_secs(),
+            });
+        }
+        
+        info!("🔁 Intento {}/{} de backup pair {}: {}", 
+             attempt.attempt, attempt.max_attempts, backup_pair_id, attempt.outcome);
+        backup_status.attempts.push(attempt.clone());
+    }
+}
+
+/// Tiempo restante hasta `next` para UI y tray ("en 5 min", "en 2 h 10 min")
+pub fn format_countdown(next: DateTime<Local>) -> String {
+    let seconds = (next - Local::now()).num_seconds();
+    if seconds <= 0 {
+        "ahora".to_string()
+    } else if seconds < 60 {
+        format!("en {}s", seconds)
+    } else if seconds < 3600 {
+        format!("en {} min", (seconds + 59) / 60)
+    } else if seconds < 86400 {
+        format!("en {} h {} min", seconds / 3600, (seconds % 3600) / 60)
+    } else {
+        format!("el {}", next.format("%d/%m %H:%M"))
+    }
+}
+
+/// Tiempo restante de una copia para UI y tray ("quedan 3 min")
+pub fn format_remaining(eta: Option<Duration>) -> String {
+    let Some(eta) = eta else {
+        return "calculando...".to_string();
+    };
+    let seconds = eta.as_secs();
+    if seconds < 60 {
+        format!("quedan {}s", seconds)
+    } else if seconds < 3600 {
+        format!("quedan {} min", seconds.div_ceil(60))
+    } else {
+        format!("quedan {} h {} min", seconds / 3600, (seconds % 3600) / 60)
+    }
 }
 
 impl Default for AppState {
@@ -163,6 +422,13 @@ impl Default for AppState {
             daemon_running: false,
             should_exit: false,
             backup_statuses: HashMap::new(),
+            next_run_at: HashMap::new(),
+            active_runs: Vec::new(),
+            transfers: HashMap::new(),
+            config_generation: 0,
+            config_conflict: None,
+            profiles: Vec::new(),
+            active_profile: String::new(),
         }
     }
 }
@@ -174,23 +440,54 @@ pub struct BackgroundManager {
     daemon: BackupDaemon,
     daemon_running: Arc<AtomicBool>,
     config: Arc<Mutex<AppConfig>>, // Config compartido con la UI
+    events: EventBus,
+    /// La UI tiene cambios sin guardar (formulario de pair o settings abiertos con ediciones)
+    ui_editing: Arc<AtomicBool>,
+    /// Recarga en caliente de config.json (None si no se pudo vigilar la carpeta)
+    _config_watcher: Option<ConfigWatcher>,
 }
 
 impl BackgroundManager {
-    fn new(command_receiver: Receiver<BackgroundCommand>, config: Arc<Mutex<AppConfig>>) -> Self {
-        let daemon = BackupDaemon::new(Arc::clone(&config));
+    fn new(
+        command_receiver: Receiver<BackgroundCommand>,
+        config: Arc<Mutex<AppConfig>>,
+        events: EventBus,
+        ui_editing: Arc<AtomicBool>,
+    ) -> Self {
+        let state = Arc::new(Mutex::new(AppState::default()));
+        
+        // Ejecuciones (manuales o del daemon), estado del daemon y próximas ejecuciones → cards
+        let event_state = Arc::clone(&state);
+        events.subscribe(move |event| {
+            match event_state.lock() {
+                Ok(mut state) => state.apply_event(event),
+                Err(e) => error!("❌ Error actualizando estado desde evento: {}", e),
+            }
+        });
+        
+        let daemon = BackupDaemon::new(Arc::clone(&config), events.clone());
         let daemon_running = daemon.get_running_flag();
         
+        let config_watcher = ConfigWatcher::start(&paths::current().config_file, || {
+            send_background_command(BackgroundCommand::ConfigFileChanged);
+        })
+        .map_err(|e| warn!("⚠️ Recarga en caliente de config.json deshabilitada: {:#}", e))
+        .ok();
+        
         let mut manager = Self {
-            state: Arc::new(Mutex::new(AppState::default())),
+            state,
             command_receiver,
             daemon,
             daemon_running,
             config, // Guardar referencia al config compartido
+            events,
+            ui_editing,
+            _config_watcher: config_watcher,
         };
         
-        // Inicializar estados de backup pairs
+        // Inicializar estados de backup pairs (el historial se restaura en el hilo de fondo)
         manager.initialize_backup_statuses();
+        manager.refresh_profiles();
         
         manager
     }
@@ -198,6 +495,11 @@ impl BackgroundManager {
     fn run(mut self, egui_ctx: egui::Context) {
         info!("Background manager iniciado");
         
+        // Recuperar contadores y último resultado de sesiones anteriores sin frenar el arranque de la UI
+        self.restore_history();
+        self.initialize_backup_statuses();
+        egui_ctx.request_repaint();
+        
         while let Ok(command) = self.command_receiver.recv() {
             match command {
                 BackgroundCommand::ShowWindow => {
@@ -237,23 +539,49 @@ impl BackgroundManager {
                 }
                 BackgroundCommand::StartDaemon => {
                     self.start_daemon();
-                    if let Ok(mut state) = self.state.lock() {
-                        state.daemon_running = true;
-                    }
                 }
                 BackgroundCommand::StopDaemon => {
                     self.stop_daemon();
-                    if let Ok(mut state) = self.state.lock() {
-                        state.daemon_running = false;
-                    }
                 }
                 BackgroundCommand::RunBackupNow => {
                     info!("🔄 Ejecutando backup manual desde UI");
                     self.run_manual_backup();
                 }
+                BackgroundCommand::RunScheduledNow => {
+                    if self.daemon_running.load(Ordering::Relaxed) {
+                        info!("⚡ Adelantando ejecución programada del daemon");
+                        self.daemon.run_now();
+                    } else {
+                        info!("🔄 Daemon detenido - ejecutando backup manual");
+                        self.run_manual_backup();
+                    }
+                }
                 BackgroundCommand::UpdateConfig(new_config) => {
                     info!("⚙️ Actualizando configuración desde UI");
-                    self.update_config(new_config);
+                    self.update_config(*new_config);
+                }
+                BackgroundCommand::ConfigFileChanged => {
+                    self.reload_config_from_disk();
+                    egui_ctx.request_repaint();
+                }
+                BackgroundCommand::ResolveConfigConflict { use_disk } => {
+                    let disk_config = self.state.lock().ok().and_then(|mut state| state.config_conflict.take());
+                    match disk_config {
+                        Some(disk_config) if use_disk => {
+                            info!("📝 Conflicto resuelto: se usa config.json de disco");
+                            self.apply_external_config(AppConfig::clone(&disk_config));
+                        }
+                        Some(_) => info!("📝 Conflicto resuelto: se mantienen los cambios de la UI (el próximo guardado reemplaza config.json)"),
+                        None => {}
+                    }
+                }
+                BackgroundCommand::SwitchProfile(name) => {
+                    info!("👤 Cambiando al perfil \"{}\"", name);
+                    self.switch_profile(&name);
+                }
+                BackgroundCommand::CreateProfile(name) => {
+                    info!("👤 Creando perfil \"{}\"", name);
+                    self.create_profile(&name);
                 }
                 
                 // === BACKUP PAIR MANAGEMENT ===
@@ -281,9 +609,9 @@ impl BackgroundManager {
                     info!("🔄 Toggling backup pair #{} to {}", index + 1, if enabled { "enabled" } else { "disabled" });
                     self.toggle_backup_pair_enabled(index, enabled);
                 }
-                
-                BackgroundCommand::UpdateBackupStatus { backup_pair_id, status } => {
-                    self.update_backup_status(backup_pair_id, status);
+                BackgroundCommand::ToggleBackupPairContinuous(index, continuous) => {
+                    info!("👁️ Modo continuo de backup pair #{} → {}", index + 1, continuous);
+                    self.toggle_backup_pair_continuous(index, continuous);
                 }
                 
                 BackgroundCommand::Exit => {
@@ -296,10 +624,11 @@ impl BackgroundManager {
                     if self.daemon_running.load(Ordering::Relaxed) {
                         self.stop_daemon();
                     }
+                    self.events.publish(AppEvent::Shutdown);
                     
                     // Limpiar sender global para evitar más comandos
-                    unsafe {
-                        BACKGROUND_SENDER = None;
+                    if let Ok(mut sender) = BACKGROUND_SENDER.lock() {
+                        *sender = None;
                     }
                     
                     info!("🚪 Cerrando aplicación completamente");
@@ -359,23 +688,10 @@ impl BackgroundManager {
             }
         };
         
-        // Clonar sender para usar en el thread de backup
-        let sender = unsafe {
-            let ptr: *const Option<Sender<BackgroundCommand>> = &raw const BACKGROUND_SENDER;
-            if let Some(opt_ref) = ptr.as_ref() {
-                opt_ref.clone()
-            } else {
-                None
-            }
-        };
-        
         // Ejecutar backup en thread separado para no bloquear background manager
+        let events = self.events.clone();
         std::thread::spawn(move || {
-            use crate::core::backup::execute_backup;
-            
-            let backup_pairs = &config.backup_pairs;
-            
-            if backup_pairs.is_empty() {
+            if config.backup_pairs.is_empty() {
                 warn!("⚠️ No hay backup pairs configurados");
                 if let Err(e) = crate::system::notifications::show_backup_warning("No hay directorios configurados para backup") {
                     warn!("⚠️ Error mostrando notificación: {}", e);
@@ -383,120 +699,15 @@ impl BackgroundManager {
                 return;
             }
             
-            info!("🚀 Backup manual iniciado - {} pair(s) a procesar", backup_pairs.len());
-            
-            let mut total_success = 0;
-            let mut total_warnings = 0;
-            let mut total_failures = 0;
-            
-            // Ejecutar backups secuencialmente (daisy-chain)
-            for (i, pair) in backup_pairs.iter().enumerate() {
-                if !pair.enabled {
-                    info!("⏭️ Backup pair #{} deshabilitado - omitiendo", i + 1);
-                    continue;
-                }
-                
-                info!("🔄 Procesando backup pair #{}: {} → {}", 
-                     i + 1, pair.source.display(), pair.destination.display());
-                
-                // Marcar como "Running" antes de comenzar
-                if let Some(ref sender) = sender {
-                    if let Err(e) = sender.send(BackgroundCommand::UpdateBackupStatus {
-                        backup_pair_id: pair.id.clone(),
-                        status: BackupStatus::Running,
-                    }) {
-                        warn!("⚠️ Error enviando estado Running: {}", e);
-                    }
-                }
-                
-                match execute_backup(&pair.source, &pair.destination, &config.robocopy) {
-                    Ok(result) => {
-                        match result {
-                            crate::core::backup::BackupResult::Success { files_copied, bytes_transferred } => {
-                                info!("✅ Backup pair #{} completado exitosamente - {} archivos, {} bytes", i + 1, files_copied, bytes_transferred);
-                                total_success += 1;
-                                
-                                // Actualizar estado a Success con métricas reales
-                                if let Some(ref sender) = sender {
-                                    if let Err(e) = sender.send(BackgroundCommand::UpdateBackupStatus {
-                                        backup_pair_id: pair.id.clone(),
-                                        status: BackupStatus::Success(BackupMetrics {
-                                            files_copied,
-                                            bytes_transferred,
-                                        }),
-                                    }) {
-                                        warn!("⚠️ Error enviando estado Success: {}", e);
-                                    }
-                                }
-                            }
-                            crate::core::backup::BackupResult::Warning(msg) => {
-                                warn!("⚠️ Backup pair #{} completado con advertencias: {}", i + 1, msg);
-                                total_warnings += 1;
-                                
-                                // Actualizar estado a Warning
-                                if let Some(ref sender) = sender {
-                                    if let Err(e) = sender.send(BackgroundCommand::UpdateBackupStatus {
-                                        backup_pair_id: pair.id.clone(),
-                                        status: BackupStatus::Warning(msg.clone()),
-                                    }) {
-                                        warn!("⚠️ Error enviando estado Warning: {}", e);
-                                    }
-                                }
-                            }
-                            crate::core::backup::BackupResult::Failed => {
-                                error!("❌ Backup pair #{} falló", i + 1);
-                                total_failures += 1;
-                                
-                                // Actualizar estado a Error
-                                if let Some(ref sender) = sender {
-                                    if let Err(e) = sender.send(BackgroundCommand::UpdateBackupStatus {
-                                        backup_pair_id: pair.id.clone(),
-                                        status: BackupStatus::Error("Backup falló".to_string()),
-                                    }) {
-                                        warn!("⚠️ Error enviando estado Error: {}", e);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("❌ Error crítico en backup pair #{}: {}", i + 1, e);
-                        total_failures += 1;
-                        
-                        // Actualizar estado a Error con mensaje específico
-                        if let Some(ref sender) = sender {
-                            if let Err(send_err) = sender.send(BackgroundCommand::UpdateBackupStatus {
-                                backup_pair_id: pair.id.clone(),
-                                status: BackupStatus::Error(format!("Error crítico: {}", e)),
-                            }) {
-                                warn!("⚠️ Error enviando estado Error: {}", send_err);
-                            }
-                        }
-                    }
-                }
+            let enabled_pairs = config.enabled_pairs();
+            let skipped = config.backup_pairs.len() - enabled_pairs.len();
+            if skipped > 0 {
+                info!("⏭️ {} backup pair(s) deshabilitado(s) - omitiendo", skipped);
             }
             
-            // Notificación final consolidada
-            if total_failures > 0 {
-                let msg = format!("{} exitosos, {} con advertencias, {} fallidos", 
-                                 total_success, total_warnings, total_failures);
-                if let Err(e) = crate::system::notifications::show_backup_failed(&msg) {
-                    warn!("⚠️ Error mostrando notificación: {}", e);
-                }
-            } else if total_warnings > 0 {
-                let msg = format!("{} exitosos, {} con advertencias", total_success, total_warnings);
-                if let Err(e) = crate::system::notifications::show_backup_warning(&msg) {
-                    warn!("⚠️ Error mostrando notificación: {}", e);
-                }
-                         } else {
-                 info!("🎉 Todos los {} backups completados exitosamente", total_success);
-                 if let Err(e) = crate::system::notifications::show_backup_success(Some(total_success as u32), None) {
-                     warn!("⚠️ Error mostrando notificación: {}", e);
-                 }
-             }
-            
-            info!("🏁 Backup manual finalizado: {} éxito, {} advertencias, {} fallos", 
-                 total_success, total_warnings, total_failures);
+            BackupRunner::new(&config, RunTrigger::Manual)
+                .with_events(&events)
+                .run(&enabled_pairs);
         });
     }
     
@@ -514,30 +725,172 @@ impl BackgroundManager {
         
         info!("💾 Configuración guardada exitosamente");
         
+        if let Ok(mut state) = self.state.lock() {
+            state.config_generation += 1;
+        }
+        
         // Reinicializar estados de backup pairs
         self.initialize_backup_statuses();
         
-        // Si el daemon está corriendo, reiniciarlo con la nueva configuración
+        // El daemon comparte la config: basta con despertarlo para que reprograme
+        self.events.publish(AppEvent::ConfigChanged);
+        info!("✅ Configuración del daemon actualizada");
+    }
+    
+    /// Activar un perfil guardado
+    fn switch_profile(&mut self, name: &str) {
+        let Ok(current) = self.config.lock().map(|config| config.clone()) else {
+            return;
+        };
+        match ProfileStore::open().switch(&current, name) {
+            Ok(next) if next.profile != current.profile => self.hand_over_profile(next),
+            Ok(_) => debug!("👤 El perfil \"{}\" ya está activo", name),
+            Err(e) => self.report_profile_error(&e),
+        }
+    }
+    
+    /// Crear un perfil copiando el activo y activarlo
+    fn create_profile(&mut self, name: &str) {
+        let Ok(current) = self.config.lock().map(|config| config.clone()) else {
+            return;
+        };
+        match ProfileStore::open().create(&current, name) {
+            Ok(created) => self.hand_over_profile(created),
+            Err(e) => self.report_profile_error(&e),
+        }
+    }
+    
+    /// Reemplazar la configuración por la de otro perfil sin cortar una copia a la mitad:
+    /// el daemon se detiene (espera a que termine la ejecución en curso), se cambia la
+    /// configuración y vuelve a arrancar si estaba corriendo
+    fn hand_over_profile(&mut self, next: AppConfig) {
+        let profile = next.profile.clone();
         let was_running = self.daemon_running.load(Ordering::Relaxed);
-        
         if was_running {
-            info!("🔄 Reiniciando daemon con nueva configuración");
+            info!("👤 Deteniendo daemon para cambiar de perfil (espera la ejecución en curso)");
             self.stop_daemon();
-            
-            // Actualizar la configuración del daemon con config compartido
-            self.daemon = BackupDaemon::new(Arc::clone(&self.config));
-            
-            // Reiniciar el daemon
+        }
+        
+        self.update_config(next);
+        // Los contadores de los pairs de este perfil vuelven del historial
+        self.restore_history();
+        self.initialize_backup_statuses();
+        self.refresh_profiles();
+        
+        if was_running {
             self.start_daemon();
-            
-            info!("✅ Daemon reiniciado con nueva configuración");
-        } else {
-            // Solo actualizar la configuración del daemon
-            self.daemon = BackupDaemon::new(Arc::clone(&self.config));
-            info!("✅ Configuración del daemon actualizada");
+        }
+        info!("✅ Perfil \"{}\" activo", profile);
+        if let Err(e) = crate::system::notifications::show_profile_switched(&profile) {
+            warn!("⚠️ Error mostrando notificación: {}", e);
         }
     }
     
+    fn report_profile_error(&self, error: &anyhow::Error) {
+        error!("❌ {:#}", error);
+        if let Err(e) = crate::system::notifications::show_backup_warning(&format!("{:#}", error)) {
+            warn!("⚠️ Error mostrando notificación: {}", e);
+        }
+    }
+    
+    /// Releer la lista de perfiles para el selector y el tray
+    fn refresh_profiles(&mut self) {
+        let Ok(config) = self.config.lock().map(|config| config.clone()) else {
+            return;
+        };
+        let profiles = ProfileStore::open().names(&config);
+        if let Ok(mut state) = self.state.lock() {
+            state.profiles = profiles;
+            state.active_profile = config.profile;
+        }
+    }
+    
+    /// config.json cambió en disco: aplicar ediciones externas válidas sin pisar cambios de la UI
+    fn reload_config_from_disk(&mut self) {
+        let config_path = &paths::current().config_file;
+        let content = match std::fs::read_to_string(config_path) {
+            Ok(content) => content,
+            // Durante el rename de un guardado atómico el archivo puede faltar un instante
+            Err(e) => {
+                debug!("config.json no legible tras el cambio: {}", e);
+                return;
+            }
+        };
+        
+        let current = match self.config.lock() {
+            Ok(config) => serde_json::to_string_pretty(&*config).unwrap_or_default(),
+            Err(_) => return,
+        };
+        // Guardado propio (auto-save, UI o settings): nada que recargar
+        if content == current {
+            return;
+        }
+        
+        let (disk_config, migrated_from) = match AppConfig::from_json(&content) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.reject_config_reload(&format!("{:#}", e));
+                return;
+            }
+        };
+        if serde_json::to_string_pretty(&disk_config).unwrap_or_default() == current {
+            debug!("config.json reformateado sin cambios de contenido");
+            return;
+        }
+        
+        let report = disk_config.validate();
+        let blocking: Vec<String> = report
+            .blocking()
+            .map(|issue| format!("{}: {}", issue.subject, issue.message))
+            .collect();
+        if !blocking.is_empty() {
+            self.reject_config_reload(&blocking.join("; "));
+            return;
+        }
+        report.log("config.json recargado");
+        
+        if let Some(version) = migrated_from {
+            info!("🔄 config.json editado en formato v{} - se guarda migrado", version);
+            if let Err(e) = disk_config.save() {
+                error!("❌ Error guardando configuración migrada: {}", e);
+            }
+        }
+        
+        if self.ui_editing.load(Ordering::Relaxed) {
+            warn!("📝 config.json cambió en disco mientras la UI tiene cambios sin guardar - esperando decisión");
+            if let Ok(mut state) = self.state.lock() {
+                state.config_conflict = Some(Arc::new(disk_config));
+            }
+            return;
+        }
+        
+        info!("📝 config.json editado fuera de la app - recargando");
+        self.apply_external_config(disk_config);
+    }
+    
+    /// Edición externa inválida: se mantiene la configuración en memoria (y no se sobrescribe el archivo)
+    fn reject_config_reload(&self, reason: &str) {
+        warn!("⚠️ config.json editado no es válido - se mantiene la configuración anterior: {}", reason);
+        if let Err(e) = crate::system::notifications::show_config_reload_failed(reason) {
+            warn!("⚠️ Error mostrando notificación: {}", e);
+        }
+    }
+    
+    /// Aplicar una configuración leída de disco (sin volver a guardarla)
+    fn apply_external_config(&mut self, new_config: AppConfig) {
+        if let Ok(mut config) = self.config.lock() {
+            *config = new_config;
+        }
+        if let Ok(mut state) = self.state.lock() {
+            state.config_generation += 1;
+            state.config_conflict = None;
+        }
+        self.initialize_backup_statuses();
+        self.refresh_profiles();
+        self.events.publish(AppEvent::ConfigChanged);
+        info!("✅ Configuración recargada desde disco");
+    }
+    
     // === BACKUP PAIR MANAGEMENT METHODS ===
     
     fn add_backup_pair(&mut self, source: String, destination: String) {
@@ -549,6 +902,7 @@ impl BackgroundManager {
         // Actualizar config compartido
         if let Ok(mut config) = self.config.lock() {
             config.backup_pairs.push(new_pair);
+            config.renumber_priorities();
             
             // Guardar a disco
             if let Err(e) = config.save() {
@@ -556,8 +910,8 @@ impl BackgroundManager {
                 return;
             }
             
-            // Actualizar daemon con config actualizado
-            self.daemon = BackupDaemon::new(Arc::clone(&self.config));
+            // Avisar al daemon para que reprograme con la config actualizada
+            self.events.publish(AppEvent::ConfigChanged);
             info!("✅ Backup pair agregado exitosamente");
         } else {
             error!("❌ Error accediendo configuración compartida para agregar backup pair");
@@ -569,14 +923,13 @@ impl BackgroundManager {
     }
     
     fn update_backup_pair(&mut self, index: usize, source: String, destination: String) {
-        use crate::core::config::BackupPair;
-        
         // Actualizar config compartido
         if let Ok(mut config) = self.config.lock() {
             if index < config.backup_pairs.len() {
-                // Crear nuevo backup pair actualizado
-                let updated_pair = BackupPair::new(source, destination);
-                config.backup_pairs[index] = updated_pair;
+                // Actualizar solo las rutas: id, límites y hooks se conservan
+                let pair = &mut config.backup_pairs[index];
+                pair.source = source.into();
+                pair.destination = destination.into();
                 
                 // Guardar a disco
                 if let Err(e) = config.save() {
@@ -584,8 +937,8 @@ impl BackgroundManager {
                     return;
                 }
                 
-                // Actualizar daemon con config actualizado
-                self.daemon = BackupDaemon::new(Arc::clone(&self.config));
+                // Avisar al daemon para que reprograme con la config actualizada
+                self.events.publish(AppEvent::ConfigChanged);
                 info!("✅ Backup pair #{} actualizado exitosamente", index + 1);
             } else {
                 error!("❌ Índice de backup pair inválido para actualizar: {}", index);
@@ -600,6 +953,7 @@ impl BackgroundManager {
         let removed_pair = if let Ok(mut config) = self.config.lock() {
             if index < config.backup_pairs.len() {
                 let removed_pair = config.backup_pairs.remove(index);
+                config.renumber_priorities();
                 
                 // Guardar a disco
                 if let Err(e) = config.save() {
@@ -607,8 +961,8 @@ impl BackgroundManager {
                     return;
                 }
                 
-                // Actualizar daemon con config actualizado
-                self.daemon = BackupDaemon::new(Arc::clone(&self.config));
+                // Avisar al daemon para que reprograme con la config actualizada
+                self.events.publish(AppEvent::ConfigChanged);
                 
                 Some(removed_pair)
             } else {
@@ -636,6 +990,7 @@ impl BackgroundManager {
             if index > 0 && index < config.backup_pairs.len() {
                 // Intercambiar posiciones
                 config.backup_pairs.swap(index, index - 1);
+                config.renumber_priorities();
                 
                 // Guardar a disco
                 if let Err(e) = config.save() {
@@ -643,8 +998,8 @@ impl BackgroundManager {
                     return;
                 }
                 
-                // Actualizar daemon con config actualizado
-                self.daemon = BackupDaemon::new(Arc::clone(&self.config));
+                // Avisar al daemon para que reprograme con la config actualizada
+                self.events.publish(AppEvent::ConfigChanged);
                 info!("✅ Backup pair movido hacia arriba: #{} → #{}", index + 1, index);
             } else {
                 warn!("⚠️ No se puede mover backup pair hacia arriba: índice {}", index);
@@ -660,6 +1015,7 @@ impl BackgroundManager {
             if index < config.backup_pairs.len().saturating_sub(1) {
                 // Intercambiar posiciones
                 config.backup_pairs.swap(index, index + 1);
+                config.renumber_priorities();
                 
                 // Guardar a disco
                 if let Err(e) = config.save() {
@@ -667,8 +1023,8 @@ impl BackgroundManager {
                     return;
                 }
                 
-                // Actualizar daemon con config actualizado
-                self.daemon = BackupDaemon::new(Arc::clone(&self.config));
+                // Avisar al daemon para que reprograme con la config actualizada
+                self.events.publish(AppEvent::ConfigChanged);
                 info!("✅ Backup pair movido hacia abajo: #{} → #{}", index + 1, index + 2);
             } else {
                 warn!("⚠️ No se puede mover backup pair hacia abajo: índice {}", index);
@@ -691,8 +1047,8 @@ impl BackgroundManager {
                     return;
                 }
 
-                // Actualizar daemon con config actualizado
-                self.daemon = BackupDaemon::new(Arc::clone(&self.config));
+                // Avisar al daemon para que reprograme con la config actualizada
+                self.events.publish(AppEvent::ConfigChanged);
 
                 let action = if enabled { "habilitado" } else { "deshabilitado" };
                 info!("✅ Backup pair #{} {} exitosamente", index + 1, action);
@@ -704,20 +1060,43 @@ impl BackgroundManager {
         }
     }
 
-    /// Actualizar estado de un backup pair específico
-    fn update_backup_status(&mut self, backup_pair_id: String, status: BackupStatus) {
-        if let Ok(mut state) = self.state.lock() {
-            // Obtener o crear entrada para este backup pair
-            let backup_status = state.backup_statuses
-                .entry(backup_pair_id.clone())
-                .or_insert_with(|| BackupPairStatus::new(backup_pair_id.clone()));
+    /// Activar/desactivar el modo continuo de un backup pair
+    /// El daemon sincroniza sus watches al recargar la config compartida
+    fn toggle_backup_pair_continuous(&mut self, index: usize, continuous: bool) {
+        use crate::core::config::ContinuousMode;
+        
+        if let Ok(mut config) = self.config.lock() {
+            if index < config.backup_pairs.len() {
+                config.backup_pairs[index].continuous = continuous.then(ContinuousMode::default);
                 
-            // Actualizar estado y timestamp
-            backup_status.update_execution(status.clone());
-            
-            info!("📊 Estado actualizado para backup pair {}: {:?}", backup_pair_id, status);
+                if let Err(e) = config.save() {
+                    error!("❌ Error guardando tras cambiar modo continuo: {}", e);
+                    return;
+                }
+                
+                self.events.publish(AppEvent::ConfigChanged);
+                
+                let action = if continuous { "activado" } else { "desactivado" };
+                info!("✅ Modo continuo {} para backup pair #{}", action, index + 1);
+            } else {
+                error!("❌ Índice de backup pair inválido para modo continuo: {}", index);
+            }
         } else {
-            error!("❌ Error actualizando estado de backup pair");
+            error!("❌ Error accediendo configuración compartida para modo continuo");
+        }
+    }
+
+    /// Restaurar estados de los backup pairs desde el historial en disco
+    fn restore_history(&mut self) {
+        let history = HistoryStore::open().load_by_pair();
+        if let Ok(mut state) = self.state.lock() {
+            for (pair_id, records) in &history {
+                state.backup_statuses.insert(
+                    pair_id.clone(),
+                    BackupPairStatus::from_history(pair_id.clone(), records),
+                );
+            }
+            info!("📜 Historial restaurado para {} pair(s)", history.len());
         }
     }
     
@@ -747,26 +1126,66 @@ impl BackgroundManager {
     }
 }
 
-/// Canal global para comandos al hilo de fondo
-static mut BACKGROUND_SENDER: Option<Sender<BackgroundCommand>> = None;
+/// Canal global para comandos al hilo de fondo (UI y tray → background manager)
+static BACKGROUND_SENDER: Mutex<Option<Sender<BackgroundCommand>>> = Mutex::new(None);
 
 /// Enviar comando al hilo de fondo
 pub fn send_background_command(command: BackgroundCommand) {
-    unsafe {
-        let ptr: *const Option<Sender<BackgroundCommand>> = &raw const BACKGROUND_SENDER;
-        if let Some(opt_ref) = ptr.as_ref() {
-            if let Some(sender) = opt_ref.as_ref() {
-                if let Err(e) = sender.send(command.clone()) {
-                    if !matches!(command, BackgroundCommand::Exit) {
-                        error!("❌ Error sending background command {:?}: {}", command, e);
-                    }
+    let sender = BACKGROUND_SENDER.lock().ok().and_then(|sender| sender.clone());
+    
+    match sender {
+        Some(sender) => {
+            if let Err(e) = sender.send(command.clone()) {
+                if !matches!(command, BackgroundCommand::Exit) {
+                    error!("❌ Error sending background command {:?}: {}", command, e);
                 }
-                return;
             }
         }
+        None => {
+            if !matches!(command, BackgroundCommand::Exit) {
+                error!("❌ Background sender not available");
+            }
+        }
+    }
+}
 
-        if !matches!(command, BackgroundCommand::Exit) {
-            error!("❌ Background sender not available");
+/// Lo que muestra el tooltip del tray, reconstruido a partir de los eventos del bus
+#[derive(Default)]
+struct TrayStatus {
+    state: AppState,
+    /// Llegó un evento que cambia el tooltip
+    changed: bool,
+}
+
+impl TrayStatus {
+    /// Suscribir el tray al bus: ejecuciones, avance y estado del daemon
+    fn subscribe(events: &EventBus) -> Arc<Mutex<Self>> {
+        let status = Arc::new(Mutex::new(Self::default()));
+        let subscriber = Arc::clone(&status);
+        events.subscribe(move |event| {
+            if matches!(event, AppEvent::ConfigChanged) {
+                return;
+            }
+            if let Ok(mut status) = subscriber.lock() {
+                status.state.apply_event(event);
+                status.changed = true;
+            }
+        });
+        status
+    }
+    
+    /// Estado del daemon, avance de la ejecución en curso o cuánto falta para el próximo backup
+    fn tooltip(&self) -> String {
+        let state = &self.state;
+        match (state.active_runs.first().map(|run| run.trigger), state.daemon_running, state.next_scheduled_run()) {
+            (Some(trigger), _, _) => match state.run_progress() {
+                Some(progress) => format!("RustyVault - {} en curso - {}% - {}",
+                    trigger.label(), progress.percent(), format_remaining(progress.eta())),
+                None => format!("RustyVault - {} en curso", trigger.label()),
+            },
+            (None, true, Some(next)) => format!("RustyVault - próximo backup {}", format_countdown(next)),
+            (None, true, None) => "RustyVault - daemon activo".to_string(),
+            (None, false, _) => "RustyVault - daemon detenido".to_string(),
         }
     }
 }
@@ -786,31 +1205,174 @@ pub struct BackupApp {
     /// Settings window
     settings_window: Option<SettingsWindow>,
     
+    /// Statistics window
+    statistics_window: Option<StatisticsWindow>,
+    
+    /// Vista previa de una configuración importada (pendiente de confirmar)
+    import_dialog: Option<ImportDialog>,
+    
+    /// Una ejecución terminó: la ventana de estadísticas debe releer el historial
+    history_changed: Arc<AtomicBool>,
+    
     /// Auto-start daemon flag (desde CLI)
     auto_start_daemon: bool,
     
     /// Referencia al estado del background thread
     background_state: Arc<Mutex<AppState>>,
+    
+    /// Estado del tray alimentado por el bus de eventos (independiente del de la ventana)
+    tray_status: Arc<Mutex<TrayStatus>>,
+    
+    /// Último tooltip mostrado en el tray
+    tray_tooltip: String,
+    
+    /// Cuándo volver a calcular la cuenta regresiva del tooltip (solo sin eventos nuevos)
+    tray_countdown_at: Option<Instant>,
+    
+    /// La UI tiene cambios sin guardar (compartido con el background para la recarga en caliente)
+    ui_editing: Arc<AtomicBool>,
+    
+    /// Última `config_generation` ya reflejada en los buffers de la UI
+    config_generation_seen: u64,
+    
+    /// Perfiles mostrados en el menú del tray (perfiles, activo)
+    tray_profiles: (Vec<String>, String),
+}
+
+/// config.json no se pudo leer: ofrecer restaurar la última copia válida antes de arrancar
+fn offer_config_restore(problem: &str, fallback: &str) -> Option<AppConfig> {
+    let backup = AppConfig::latest_valid_backup()?;
+    let answer = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("RustyVault - config.json")
+        .set_description(format!(
+            "{}\n\n¿Restaurar la última copia válida?\n{}\n\nSi eliges No {}.",
+            problem,
+            backup.display(),
+            fallback
+        ))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+    if answer != rfd::MessageDialogResult::Yes {
+        info!("⏭️ Restauración de config.json rechazada");
+        return None;
+    }
+    
+    AppConfig::restore_backup(&backup)
+        .map_err(|e| error!("❌ Error restaurando configuración: {:#}", e))
+        .ok()
+}
+
+/// Errores bloqueantes al arrancar (los mismos que rechazan una recarga o una importación)
+/// No hay configuración anterior a la que volver: se ofrece la última copia sin errores y, si no
+/// hay o se rechaza, se sigue con el archivo avisando (un schedule inválido solo frena a ese pair)
+fn check_startup_config(config: AppConfig) -> AppConfig {
+    let blocking: Vec<String> = config
+        .validate()
+        .blocking()
+        .map(|issue| format!("{}: {}", issue.subject, issue.message))
+        .collect();
+    if blocking.is_empty() {
+        return config;
+    }
+    
+    let problem = format!("config.json tiene errores:\n{}", blocking.join("\n"));
+    error!("❌ {}", problem.replace('\n', " "));
+    if let Some(restored) = offer_config_restore(&problem, "se usará config.json tal como está") {
+        return restored;
+    }
+    show_error_dialog(
+        "RustyVault - config.json",
+        &format!("{}\n\nSe usa config.json tal como está. Corrígelo: los cambios se cargan automáticamente.", problem),
+    );
+    config
+}
+
+/// Perfil pedido con `--profile`: pasa a ser el activo (si no existe se sigue con el actual)
+fn activate_profile(config: AppConfig, name: &str) -> AppConfig {
+    let next = match ProfileStore::open().switch(&config, name) {
+        Ok(next) => next,
+        Err(e) => {
+            error!("❌ {:#}", e);
+            show_error_dialog("RustyVault - Profile", &format!("{:#}\n\nSe usa el perfil \"{}\".", e, config.profile));
+            return config;
+        }
+    };
+    if next.profile != config.profile {
+        if let Err(e) = next.save() {
+            error!("❌ Error guardando configuración: {}", e);
+        }
+    }
+    next
+}
+
+/// Aplicar tema, nivel del log y notificaciones (al arrancar, al cambiarlas y al recargar config.json)
+fn apply_ui_preferences(ctx: &egui::Context, prefs: &UiPreferences) {
+    ctx.set_theme(match prefs.theme {
+        AppTheme::Auto => egui::ThemePreference::System,
+        AppTheme::Light => egui::ThemePreference::Light,
+        AppTheme::Dark => egui::ThemePreference::Dark,
+    });
+    crate::logging::set_file_level(prefs.log_level);
+    crate::system::notifications::configure(prefs);
+}
+
+/// Crear o quitar el acceso directo en la carpeta Startup de Windows (noop en otras plataformas)
+fn update_startup_shortcut(enabled: bool) {
+    match crate::system::registry::get_current_exe_path() {
+        Ok(exe_path) => {
+            if let Err(e) = crate::system::startup::set_startup_shortcut(enabled, &exe_path) {
+                error!("❌ Error configuring Startup shortcut: {}", e);
+            } else {
+                info!("✅ Startup shortcut updated: {}", enabled);
+            }
+        }
+        Err(e) => {
+            error!("❌ Could not determine current exe path for startup registration: {}", e);
+        }
+    }
+}
+
+/// Error que el usuario debe ver aunque no tenga abiertos los logs
+fn show_error_dialog(title: &str, description: &str) {
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Error)
+        .set_title(title)
+        .set_description(description)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
 }
 
 impl BackupApp {
     /// Constructor principal - llamado desde main.rs
-    pub fn new(_cc: &eframe::CreationContext<'_>, auto_start_daemon: bool) -> Self {
+    pub fn new(_cc: &eframe::CreationContext<'_>, auto_start_daemon: bool, profile: Option<String>) -> Self {
         info!("🏗️ Inicializando BackupApp con arquitectura de background thread...");
         
         // Cargar configuración
         let config = match AppConfig::load() {
             Ok(cfg) => {
                 info!("✅ Configuración cargada exitosamente");
-                cfg
+                check_startup_config(cfg)
             }
             Err(e) => {
-                error!("❌ Error cargando configuración: {}", e);
-                warn!("🔄 Usando configuración por defecto");
-                AppConfig::default()
+                error!("❌ Error cargando configuración: {:#}", e);
+                let problem = format!("No se pudo leer config.json:\n{:#}", e);
+                offer_config_restore(&problem, "se usará la configuración por defecto").unwrap_or_else(|| {
+                    warn!("🔄 Usando configuración por defecto");
+                    AppConfig::default()
+                })
             }
         };
         
+        // `--profile <nombre>`: activar ese perfil antes de arrancar el daemon
+        let config = match profile {
+            Some(name) => activate_profile(config, &name),
+            None => config,
+        };
+        
+        apply_ui_preferences(&_cc.egui_ctx, &config.ui_preferences);
+        let start_minimized = config.ui_preferences.window.start_minimized;
+        
         // Estado compartido thread-safe
         let config_shared = Arc::new(Mutex::new(config));
         
@@ -818,12 +1380,34 @@ impl BackupApp {
         let (command_sender, command_receiver) = mpsc::channel::<BackgroundCommand>();
         
         // Guardar sender globalmente para que tray pueda usarlo
-        unsafe {
-            BACKGROUND_SENDER = Some(command_sender.clone());
+        if let Ok(mut sender) = BACKGROUND_SENDER.lock() {
+            *sender = Some(command_sender);
         }
         
+        // Suscriptores independientes: notificaciones, historial, tray y repintado de la ventana
+        let events = EventBus::new();
+        crate::system::notifications::subscribe(&events);
+        HistoryStore::subscribe(&events);
+        // El tray se actualiza antes de pedir el repintado que lo muestra
+        let tray_status = TrayStatus::subscribe(&events);
+        let repaint_ctx = _cc.egui_ctx.clone();
+        events.subscribe(move |_| repaint_ctx.request_repaint());
+        let history_changed = Arc::new(AtomicBool::new(false));
+        let history_flag = Arc::clone(&history_changed);
+        events.subscribe(move |event| {
+            if matches!(event, AppEvent::RunFinished { .. }) {
+                history_flag.store(true, Ordering::Relaxed);
+            }
+        });
+        
         // Crear background manager
-        let background_manager = BackgroundManager::new(command_receiver, Arc::clone(&config_shared));
+        let ui_editing = Arc::new(AtomicBool::new(false));
+        let background_manager = BackgroundManager::new(
+            command_receiver,
+            Arc::clone(&config_shared),
+            events,
+            Arc::clone(&ui_editing),
+        );
         let background_state = Arc::clone(&background_manager.state);
         
         // Iniciar background thread
@@ -845,6 +1429,12 @@ impl BackupApp {
             }
         };
         
+        // Arrancar oculto en el tray (solo si hay tray desde donde volver a abrir la ventana)
+        if start_minimized && system_tray.is_some() {
+            info!("📦 Iniciando minimizado en el tray");
+            send_background_command(BackgroundCommand::HideWindow);
+        }
+        
         // Inicializar UI state
         let ui_state = MainWindow::new();
         
@@ -855,8 +1445,71 @@ impl BackupApp {
             system_tray,
             ui_state,
             settings_window: None,
+            statistics_window: None,
+            import_dialog: None,
+            history_changed,
             auto_start_daemon,
             background_state,
+            tray_status,
+            tray_tooltip: String::new(),
+            tray_countdown_at: None,
+            ui_editing,
+            config_generation_seen: 0,
+            tray_profiles: (Vec::new(), String::new()),
+        }
+    }
+    
+    /// Reconstruir el menú de perfiles del tray solo cuando cambia la lista o el activo
+    fn update_tray_profiles(&mut self, state: &AppState) {
+        if self.tray_profiles.0 == state.profiles && self.tray_profiles.1 == state.active_profile {
+            return;
+        }
+        self.tray_profiles = (state.profiles.clone(), state.active_profile.clone());
+        if let Some(ref tray) = self.system_tray {
+            tray.set_profiles(&state.profiles, &state.active_profile);
+        }
+    }
+    
+    /// Minimizar/cerrar al tray según las preferencias (sin tray la ventana se comporta como siempre)
+    fn handle_window_behavior(&mut self, ctx: &egui::Context, state: &AppState) {
+        if self.system_tray.is_none() {
+            return;
+        }
+        let window = self.config.lock().map(|config| config.ui_preferences.window.clone()).unwrap_or_default();
+        let (close_requested, minimized) = ctx.input(|i| (i.viewport().close_requested(), i.viewport().minimized == Some(true)));
+        
+        if window.close_to_tray && close_requested {
+            info!("📦 Cerrar → tray");
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.handle_ui_action(UIAction::MinimizeToTray, ctx);
+        } else if window.minimize_to_tray && minimized && state.window_visible {
+            info!("📦 Minimizar → tray");
+            self.handle_ui_action(UIAction::MinimizeToTray, ctx);
+        }
+    }
+    
+    /// Mostrar en el tray el estado del daemon y cuánto falta para el próximo backup
+    /// Solo se recalcula cuando llega un evento del bus o vence la cuenta regresiva mostrada
+    fn update_tray_tooltip(&mut self) {
+        let countdown_due = self.tray_countdown_at.is_some_and(|at| Instant::now() >= at);
+        let tooltip = {
+            let Ok(mut status) = self.tray_status.lock() else { return };
+            if !status.changed && !countdown_due {
+                return;
+            }
+            status.changed = false;
+            let counting_down = status.state.active_runs.is_empty()
+                && status.state.daemon_running
+                && status.state.next_scheduled_run().is_some();
+            self.tray_countdown_at = counting_down.then(|| Instant::now() + Duration::from_secs(1));
+            status.tooltip()
+        };
+        
+        if tooltip != self.tray_tooltip {
+            if let Some(tray) = &self.system_tray {
+                tray.set_tooltip(&tooltip);
+            }
+            self.tray_tooltip = tooltip;
         }
     }
     
@@ -879,76 +1532,63 @@ impl BackupApp {
                 send_background_command(BackgroundCommand::StopDaemon);
                 info!("⏹ Daemon stop requested from settings");
             }
-            SettingsAction::UpdateInterval(interval) => {
-                // Update the configuration
-                if let Ok(mut config) = self.config.lock() {
-                    config.check_interval_seconds = interval;
-                    if let Err(e) = config.save() {
-                        error!("❌ Error saving config: {}", e);
-                    }
-                }
-                send_background_command(BackgroundCommand::UpdateConfig(self.extract_config_from_ui().unwrap_or_default()));
-                info!("⏰ Interval updated to {} seconds", interval);
+            SettingsAction::PreviewPreferences(prefs) => {
+                // Solo se muestran; se guardan con Apply (Cancel vuelve a las guardadas)
+                apply_ui_preferences(_ctx, &prefs);
             }
-            SettingsAction::UpdateRobocopyConfig(robocopy_config) => {
-                if let Ok(mut config) = self.config.lock() {
-                    config.robocopy = robocopy_config;
-                    if let Err(e) = config.save() {
-                        error!("❌ Error saving robocopy config: {}", e);
-                    }
-                }
-                info!("🔧 Robocopy configuration updated");
-            }
-            SettingsAction::UpdateAutoStart(enabled) => {
-                info!("🚀 Auto-start setting: {}", enabled);
-                // Persist in config
-                if let Ok(mut config) = self.config.lock() {
-                    config.start_with_windows = enabled;
-                    if let Err(e) = config.save() {
-                        error!("❌ Error saving config: {}", e);
+            SettingsAction::ApplyAndSave(draft) => {
+                let live = match self.config.lock() {
+                    Ok(config) => config.clone(),
+                    Err(_) => {
+                        error!("❌ Could not acquire config lock to apply settings");
+                        return;
                     }
-                } else {
-                    error!("❌ Could not acquire config lock to update start_with_windows");
+                };
+                let updated = apply_draft(&draft, &live);
+                if updated.start_with_windows != live.start_with_windows {
+                    update_startup_shortcut(updated.start_with_windows);
                 }
-
-                // Try to update Windows startup using a Startup-folder shortcut (noop on non-windows builds)
-                match crate::system::registry::get_current_exe_path() {
-                    Ok(exe_path) => {
-                        if let Err(e) = crate::system::startup::set_startup_shortcut(enabled, &exe_path) {
-                            error!("❌ Error configuring Startup shortcut: {}", e);
-                        } else {
-                            info!("✅ Startup shortcut updated: {}", enabled);
-                        }
-                    }
-                    Err(e) => {
-                        error!("❌ Could not determine current exe path for startup registration: {}", e);
-                    }
-                }
-            }
-            SettingsAction::UpdateNotificationEnabled(enabled) => {
-                info!("🔔 Notifications enabled: {}", enabled);
-                // TODO: Store in config
-            }
-            SettingsAction::UpdateTheme(theme) => {
-                info!("🎨 Theme updated: {:?}", theme);
-                // TODO: Implement theme switching
+                apply_ui_preferences(_ctx, &updated.ui_preferences);
+                send_background_command(BackgroundCommand::UpdateConfig(Box::new(updated)));
+                info!("💾 Settings applied and saved");
             }
             SettingsAction::ExportConfig => {
                 info!("📤 Export config requested");
-                // TODO: Implement file dialog for export
+                let Some(path) = rfd::FileDialog::new()
+                    .set_title("Export configuration")
+                    .set_file_name(config_exchange::EXPORT_FILE_NAME)
+                    .add_filter("JSON", &["json"])
+                    .save_file()
+                else {
+                    return;
+                };
+                let config = match self.config.lock() {
+                    Ok(config) => config.clone(),
+                    Err(_) => return,
+                };
+                if let Err(e) = config_exchange::export_config(&config, &path) {
+                    error!("❌ {:#}", e);
+                    show_error_dialog("RustyVault - Export", &format!("{:#}", e));
+                }
             }
             SettingsAction::ImportConfig(config_path) => {
                 info!("📥 Import config from: {}", config_path);
-                // TODO: Implement config import
+                match ImportPreview::load(std::path::Path::new(&config_path)) {
+                    Ok(preview) => {
+                        info!("📥 {} pairs en el archivo, validación: {}",
+                              preview.incoming.backup_pairs.len(), preview.report.summary());
+                        self.import_dialog = Some(ImportDialog::new(preview));
+                    }
+                    Err(e) => {
+                        error!("❌ Error importando configuración: {:#}", e);
+                        show_error_dialog("RustyVault - Import", &format!("{:#}", e));
+                    }
+                }
             }
             SettingsAction::CloseSettings => {
                 self.settings_window = None;
                 info!("⚙️ Settings window closed");
             }
-            SettingsAction::ApplyAndSave => {
-                info!("💾 Apply and save settings");
-                // This is handled by individual setting actions
-            }
         }
     }
 
@@ -983,6 +1623,18 @@ impl BackupApp {
                     info!("⚙️ Settings window opened");
                 }
             }
+            UIAction::SwitchProfile(name) => {
+                send_background_command(BackgroundCommand::SwitchProfile(name));
+            }
+            UIAction::CreateProfile(name) => {
+                send_background_command(BackgroundCommand::CreateProfile(name));
+            }
+            UIAction::OpenStatistics => {
+                if self.statistics_window.is_none() {
+                    self.statistics_window = Some(StatisticsWindow::new());
+                    info!("📊 Statistics window opened");
+                }
+            }
             UIAction::UpdateAutoStart(enabled) => {
                 // Guardar inmediatamente en config
                 if let Ok(mut config) = self.config.lock() {
@@ -991,19 +1643,7 @@ impl BackupApp {
                         error!("❌ Error saving config: {}", e);
                     }
                 }
-                // Actualizar shortcut
-                match crate::system::registry::get_current_exe_path() {
-                    Ok(exe_path) => {
-                        if let Err(e) = crate::system::startup::set_startup_shortcut(enabled, &exe_path) {
-                            error!("❌ Error configuring Startup shortcut: {}", e);
-                        } else {
-                            info!("✅ Startup shortcut updated: {}", enabled);
-                        }
-                    }
-                    Err(e) => {
-                        error!("❌ Could not determine current exe path for startup registration: {}", e);
-                    }
-                }
+                update_startup_shortcut(enabled);
                 // Mantener la ventana enfocada después de procesar
                 let ctx_clone = _ctx.clone();
                 std::thread::spawn(move || {
@@ -1018,7 +1658,7 @@ impl BackupApp {
             UIAction::ConfigChanged => {
                 // Extraer configuración actual de la UI y enviar al background
                 if let Ok(updated_config) = self.extract_config_from_ui() {
-                    send_background_command(BackgroundCommand::UpdateConfig(updated_config));
+                    send_background_command(BackgroundCommand::UpdateConfig(Box::new(updated_config)));
                 } else {
                     error!("❌ Error extrayendo configuración de la UI");
                 }
@@ -1064,6 +1704,9 @@ impl BackupApp {
             UIAction::ToggleBackupPairEnabled(index, enabled) => {
                 send_background_command(BackgroundCommand::ToggleBackupPairEnabled(index, enabled));
             }
+            UIAction::ToggleBackupPairContinuous(index, continuous) => {
+                send_background_command(BackgroundCommand::ToggleBackupPairContinuous(index, continuous));
+            }
         }
     }
     
@@ -1112,6 +1755,43 @@ impl eframe::App for BackupApp {
             return;
         }
         
+        // Configuración reemplazada (importación o recarga desde disco): resincronizar buffers
+        if current_state.config_generation != self.config_generation_seen {
+            self.config_generation_seen = current_state.config_generation;
+            self.ui_state.reload_from_config();
+            if let Ok(config) = self.config.lock() {
+                apply_ui_preferences(ctx, &config.ui_preferences);
+                if let Some(settings_window) = self.settings_window.as_mut() {
+                    if !settings_window.has_unsaved_changes() {
+                        settings_window.initialize_from_config(&config);
+                    }
+                }
+            }
+        }
+        
+        self.handle_window_behavior(ctx, &current_state);
+        
+        // config.json editado mientras la UI tenía cambios sin guardar
+        if let Some(disk_config) = &current_state.config_conflict {
+            let current = self.config.lock().map(|config| config.clone()).unwrap_or_default();
+            if let Some(use_disk) = config_conflict::render(ctx, &current, disk_config) {
+                if use_disk {
+                    // Descartar ediciones: formulario de pair y settings se reabren desde la config recargada
+                    self.ui_state.discard_edits();
+                    self.settings_window = None;
+                }
+                send_background_command(BackgroundCommand::ResolveConfigConflict { use_disk });
+            }
+        }
+        
+        // Cuenta regresiva del próximo backup en tray y cards
+        let next_run = current_state.next_scheduled_run();
+        if next_run.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+        self.update_tray_tooltip();
+        self.update_tray_profiles(&current_state);
+        
         // Recolectar acciones de UI
         let mut ui_actions = Vec::new();
         
@@ -1128,11 +1808,7 @@ impl eframe::App for BackupApp {
         if let Some(ref mut settings_window) = self.settings_window {
             let daemon_running = Arc::new(AtomicBool::new(current_state.daemon_running));
             
-            let (keep_open, settings_actions) = settings_window.render(
-                ctx,
-                &self.config,
-                &daemon_running,
-            );
+            let (keep_open, settings_actions) = settings_window.render(ctx, &daemon_running);
             
             if !keep_open {
                 self.settings_window = None;
@@ -1144,10 +1820,36 @@ impl eframe::App for BackupApp {
             }
         }
         
+        // Ventana de estadísticas (se relee el historial al terminar cada ejecución)
+        if let Some(ref mut statistics_window) = self.statistics_window {
+            if self.history_changed.swap(false, Ordering::Relaxed) {
+                statistics_window.reload();
+            }
+            if !statistics_window.render(ctx, &self.config) {
+                self.statistics_window = None;
+            }
+        }
+        
+        // Vista previa de importación: se aplica solo al confirmar
+        if let Some(ref mut import_dialog) = self.import_dialog {
+            let (keep_open, imported) = import_dialog.render(ctx, &self.config);
+            if !keep_open {
+                self.import_dialog = None;
+            }
+            if let Some(imported) = imported {
+                info!("📥 Configuración importada: {} pairs", imported.backup_pairs.len());
+                send_background_command(BackgroundCommand::UpdateConfig(Box::new(imported)));
+            }
+        }
+        
         // Procesar acciones después del render
         for action in ui_actions {
             self.handle_ui_action(action, ctx);
         }
+        
+        let editing = self.ui_state.has_unsaved_edits()
+            || self.settings_window.as_ref().is_some_and(SettingsWindow::has_unsaved_changes);
+        self.ui_editing.store(editing, Ordering::Relaxed);
     }
     
     /// Manejo de cierre de ventana
@@ -1158,4 +1860,27 @@ impl eframe::App for BackupApp {
         // No guardamos config aquí porque el auto-save en tiempo real ya lo hace
         // y self.config puede tener valores desactualizados
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::runner::RunSummary;
+
+    #[test]
+    fn test_overlapping_runs_with_same_trigger_finish_separately() {
+        let mut state = AppState::default();
+        let started = |run_id: u64, pair_id: &str| AppEvent::RunStarted {
+            run_id,
+            trigger: RunTrigger::Manual,
+            pair_ids: vec![pair_id.to_string()],
+        };
+        state.apply_event(&started(1, "long"));
+        state.apply_event(&started(2, "new"));
+
+        // Termina la ejecución corta (solo el pair nuevo): la larga sigue en curso
+        state.apply_event(&AppEvent::RunFinished { run_id: 2, trigger: RunTrigger::Manual, summary: RunSummary::default() });
+        assert_eq!(state.active_runs.iter().map(|run| run.run_id).collect::<Vec<_>>(), [1]);
+        assert!(state.active_runs[0].progress.contains("long"));
+    }
+}
@@ -1,26 +1,173 @@
 /// Módulo de backup - ejecución de robocopy y manejo de procesos
 /// TODO: Implementar ejecución real de robocopy según PRD
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::{info, debug};
 
-use crate::core::RobocopyConfig;
+use crate::core::{AppConfig, BackupEngine, BackupPair, BandwidthLimit, RobocopyConfig};
+use crate::core::native::execute_native_backup;
+use crate::core::progress::ProgressTracker;
+use crate::core::throttle::{self, Throttle};
 
 /// Resultado de una operación de backup
 #[derive(Debug, Clone)]
 pub enum BackupResult {
     Success { files_copied: u32, bytes_transferred: u64 },
-    Warning(String),
+    /// Copia terminada con advertencias; los totales reflejan lo que sí se copió
+    Warning { message: String, files_copied: u32, bytes_transferred: u64 },
     Failed,
+    /// El pair no se ejecutó (ej. origen sin cambios)
+    Skipped(SkipReason),
 }
 
-/// Ejecutar backup usando robocopy con configuración especificada
+impl BackupResult {
+    /// El destino quedó al día: copia (con o sin advertencias) u origen sin cambios
+    /// Solo estos resultados cuentan como última ejecución para el scheduler y la recuperación
+    pub fn leaves_destination_current(&self) -> bool {
+        match self {
+            BackupResult::Success { .. } | BackupResult::Warning { .. } => true,
+            BackupResult::Skipped(reason) => *reason == SkipReason::NoChanges,
+            BackupResult::Failed => false,
+        }
+    }
+}
+
+/// Motivo por el que un pair no se ejecutó
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// El fingerprint del origen coincide con el del último backup exitoso
+    NoChanges,
+    /// Otra ejecución (manual, daemon o modo continuo) ya está copiando este pair
+    AlreadyRunning,
+}
+
+impl SkipReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            SkipReason::NoChanges => "sin cambios",
+            SkipReason::AlreadyRunning => "ya en ejecución",
+        }
+    }
+}
+
+/// Opciones de ejecución de un backup individual
+#[derive(Debug, Clone)]
+pub struct BackupOptions {
+    pub engine: BackupEngine,
+    pub robocopy: RobocopyConfig,
+    /// Límite propio del pair y el global, con sus ventanas horarias
+    pair_limit: Option<BandwidthLimit>,
+    global_limit: BandwidthLimit,
+    /// Lanes de la ejecución que copian a la vez (robocopy reparte el límite global entre ellas)
+    lanes: usize,
+    /// Throttle del motor nativo (incluye el bucket global compartido)
+    pub throttle: Throttle,
+    /// Bytes y archivos copiados en vivo (el runner lo lee para el porcentaje y el ETA)
+    pub progress: ProgressTracker,
+    /// Flag del daemon: si pasa a false la copia en curso se interrumpe
+    pub running: Option<Arc<AtomicBool>>,
+}
+
+impl BackupOptions {
+    /// Construir opciones para un pair según la configuración
+    /// `global_bucket` se comparte entre todos los pairs de la misma ejecución, que copian en `lanes` lanes
+    pub fn for_pair(
+        config: &AppConfig,
+        pair: &BackupPair,
+        global_bucket: Option<Arc<Mutex<throttle::TokenBucket>>>,
+        lanes: usize,
+    ) -> Self {
+        Self {
+            engine: config.engine,
+            robocopy: config.robocopy.clone(),
+            pair_limit: pair.bandwidth_limit.clone(),
+            global_limit: config.bandwidth_limit.clone(),
+            lanes: lanes.max(1),
+            throttle: Throttle::unlimited()
+                .with_limit(pair.bandwidth_limit.as_ref())
+                .with_shared(global_bucket),
+            progress: ProgressTracker::new(),
+            running: None,
+        }
+    }
+    
+    /// Interrumpir la copia cuando `running` pase a false (daemon detenido)
+    pub fn with_running_flag(mut self, running: Option<Arc<AtomicBool>>) -> Self {
+        self.running = running;
+        self
+    }
+    
+    /// Límite en MB/s vigente ahora para robocopy (por pair + parte del global)
+    /// `/IPG` queda fijo mientras robocopy corre: se vuelve a evaluar en cada intento
+    pub fn bandwidth_limit_mb(&self) -> Option<f64> {
+        let hour = throttle::current_hour();
+        let pair_limit = self.pair_limit.as_ref().and_then(|limit| limit.limit_at_hour(hour));
+        
+        // Robocopy no puede compartir un bucket: el global se reparte entre las lanes en paralelo
+        let global_share = self.global_limit
+            .limit_at_hour(hour)
+            .map(|mb| mb / self.lanes as f64);
+        match (pair_limit, global_share) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+    
+    /// Motor que realmente copiará (robocopy solo existe en Windows)
+    pub fn effective_engine(&self) -> BackupEngine {
+        match self.engine {
+            BackupEngine::Robocopy if cfg!(windows) => BackupEngine::Robocopy,
+            _ => BackupEngine::Native,
+        }
+    }
+}
+
+/// Ejecutar backup con el motor configurado
+/// Si robocopy no está disponible (ej. fuera de Windows) se usa el motor nativo
 pub fn execute_backup(
+    source: &Path,
+    destination: &Path,
+    options: &BackupOptions,
+) -> Result<BackupResult> {
+    match options.effective_engine() {
+        BackupEngine::Robocopy => {
+            let bandwidth_limit_mb = options.bandwidth_limit_mb();
+            if let Some(limit) = bandwidth_limit_mb {
+                info!("🐢 Límite de ancho de banda: {:.1} MB/s", limit);
+            }
+            execute_robocopy_backup(source, destination, &options.robocopy, bandwidth_limit_mb, &options.progress, options.running.as_deref())
+        }
+        _ => {
+            if !source.exists() {
+                tracing::error!("❌ Carpeta de origen no existe: {}", source.display());
+                return Ok(BackupResult::Failed);
+            }
+            execute_native_backup(
+                source,
+                destination,
+                options.robocopy.mirror_mode,
+                &options.throttle,
+                &options.progress,
+                options.running.as_deref(),
+            )
+        }
+    }
+}
+
+/// Ejecutar backup usando robocopy con configuración especificada
+fn execute_robocopy_backup(
     source: &Path,
     destination: &Path,
     config: &RobocopyConfig,
+    bandwidth_limit_mb: Option<f64>,
+    progress: &ProgressTracker,
+    running: Option<&AtomicBool>,
 ) -> Result<BackupResult> {
+    use std::io::{BufRead, BufReader, Read};
     use std::process::{Command, Stdio};
     
     info!("🚀 Iniciando backup: {} -> {}", source.display(), destination.display());
@@ -38,7 +185,12 @@ pub fn execute_backup(
     }
     
     // Construir argumentos robocopy
-    let args = config.build_args();
+    let mut args = config.build_args();
+    if let Some(limit) = bandwidth_limit_mb {
+        // /IPG no es compatible con /MT: con límite activo se copia en un solo hilo
+        args.retain(|arg| !arg.starts_with("/MT"));
+        args.push(format!("/IPG:{}", throttle::robocopy_inter_packet_gap_ms(limit)));
+    }
     debug!("🔧 Argumentos robocopy: {:?}", args);
     
     // Ejecutar robocopy con CREATE_NO_WINDOW (proceso oculto)
@@ -60,13 +212,57 @@ pub fn execute_backup(
         command.creation_flags(0x08000000); // CREATE_NO_WINDOW
     }
     
-    let output = command.output();
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::error!("❌ Error ejecutando robocopy: {}", e);
+            return Ok(BackupResult::Failed);
+        }
+    };
+    
+    // stdout y stderr en sus propios hilos: este espera a robocopy y lo corta si el daemon se detiene
+    let stdout_pipe = child.stdout.take();
+    let stderr_pipe = child.stderr.take();
+    let (status, stdout, stderr) = std::thread::scope(|scope| {
+        // stdout línea a línea: cada archivo listado suma su tamaño al progreso
+        let stdout_reader = stdout_pipe.map(|pipe| {
+            scope.spawn(move || {
+                let mut reader = BufReader::new(pipe);
+                let mut stdout = String::new();
+                let mut line = Vec::new();
+                while reader.read_until(b'\n', &mut line).unwrap_or(0) > 0 {
+                    let text = String::from_utf8_lossy(&line);
+                    if let Some(size) = parse_robocopy_file_size(&text) {
+                        progress.add_file();
+                        progress.add_bytes(size);
+                    }
+                    stdout.push_str(&text);
+                    line.clear();
+                }
+                stdout
+            })
+        });
+        let stderr_reader = stderr_pipe.map(|mut pipe| {
+            scope.spawn(move || {
+                let mut buffer = Vec::new();
+                let _ = pipe.read_to_end(&mut buffer);
+                String::from_utf8_lossy(&buffer).into_owned()
+            })
+        });
+        
+        let status = wait_unless_stopped(&mut child, running);
+        let stdout = stdout_reader.and_then(|reader| reader.join().ok()).unwrap_or_default();
+        let stderr = stderr_reader.and_then(|reader| reader.join().ok()).unwrap_or_default();
+        (status, stdout, stderr)
+    });
     
-    match output {
-        Ok(result) => {
-            let exit_code = result.status.code().unwrap_or(-1);
-            let stdout = String::from_utf8_lossy(&result.stdout);
-            let stderr = String::from_utf8_lossy(&result.stderr);
+    match status {
+        Ok(None) => {
+            tracing::warn!("🛑 Robocopy interrumpido: daemon detenido");
+            bail!("Copia cancelada: daemon detenido");
+        }
+        Ok(Some(status)) => {
+            let exit_code = status.code().unwrap_or(-1);
             
             info!("✅ Robocopy terminado con código: {}", exit_code);
             
@@ -81,12 +277,45 @@ pub fn execute_backup(
             Ok(parse_robocopy_output(exit_code, &stdout))
         }
         Err(e) => {
-            tracing::error!("❌ Error ejecutando robocopy: {}", e);
+            tracing::error!("❌ Error esperando a robocopy: {}", e);
             Ok(BackupResult::Failed)
         }
     }
 }
 
+/// Esperar a robocopy revisando `running`; si el daemon se detiene se mata el proceso y se devuelve None
+fn wait_unless_stopped(child: &mut std::process::Child, running: Option<&AtomicBool>) -> std::io::Result<Option<std::process::ExitStatus>> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if running.is_some_and(|running| !running.load(Ordering::Relaxed)) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Tamaño de una línea de archivo de robocopy (se imprime al empezar a copiarlo)
+/// Formato: "\t    New File  \t\t   1.2 m\tC:\ruta\archivo"; las líneas de extras en destino no cuentan
+fn parse_robocopy_file_size(line: &str) -> Option<u64> {
+    if line.contains("EXTRA") {
+        return None;
+    }
+    let fields: Vec<&str> = line
+        .split('\t')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .collect();
+    if fields.len() < 3 {
+        return None;
+    }
+    let size: String = fields[fields.len() - 2].split_whitespace().collect();
+    parse_robocopy_size_combined(&size).ok()
+}
+
 /// Parsear output completo de robocopy para extraer estadísticas reales
 fn parse_robocopy_output(exit_code: i32, stdout: &str) -> BackupResult {
     // Parsear estadísticas del output de robocopy
@@ -95,12 +324,12 @@ fn parse_robocopy_output(exit_code: i32, stdout: &str) -> BackupResult {
     match exit_code {
         0 => BackupResult::Success { files_copied, bytes_transferred }, // No files copied (no changes)
         1 => BackupResult::Success { files_copied, bytes_transferred }, // Files copied successfully
-        2 => BackupResult::Warning("Extra files/dirs in destination".to_string()),
-        3 => BackupResult::Warning("Files copied + extra files in dest".to_string()),
-        4 => BackupResult::Warning("Some mismatched files/dirs".to_string()),
-        5 => BackupResult::Warning("Files copied + some mismatched".to_string()),
-        6 => BackupResult::Warning("Extra + mismatched files".to_string()),
-        7 => BackupResult::Warning("Files copied + extra + mismatched".to_string()),
+        2 => BackupResult::Warning { message: "Extra files/dirs in destination".to_string(), files_copied, bytes_transferred },
+        3 => BackupResult::Warning { message: "Files copied + extra files in dest".to_string(), files_copied, bytes_transferred },
+        4 => BackupResult::Warning { message: "Some mismatched files/dirs".to_string(), files_copied, bytes_transferred },
+        5 => BackupResult::Warning { message: "Files copied + some mismatched".to_string(), files_copied, bytes_transferred },
+        6 => BackupResult::Warning { message: "Extra + mismatched files".to_string(), files_copied, bytes_transferred },
+        7 => BackupResult::Warning { message: "Files copied + extra + mismatched".to_string(), files_copied, bytes_transferred },
         _ => BackupResult::Failed, // Exit codes 8+ indicate errors
     }
 }
@@ -231,12 +460,12 @@ fn parse_robocopy_exit_code(exit_code: i32) -> BackupResult {
     match exit_code {
         0 => BackupResult::Success { files_copied: 0, bytes_transferred: 0 }, // No files copied (no changes)
         1 => BackupResult::Success { files_copied: 0, bytes_transferred: 0 }, // Files copied successfully
-        2 => BackupResult::Warning("Extra files/dirs in destination".to_string()),
-        3 => BackupResult::Warning("Files copied + extra files in dest".to_string()),
-        4 => BackupResult::Warning("Some mismatched files/dirs".to_string()),
-        5 => BackupResult::Warning("Files copied + some mismatched".to_string()),
-        6 => BackupResult::Warning("Extra + mismatched files".to_string()),
-        7 => BackupResult::Warning("Files copied + extra + mismatched".to_string()),
+        2 => BackupResult::Warning { message: "Extra files/dirs in destination".to_string(), files_copied: 0, bytes_transferred: 0 },
+        3 => BackupResult::Warning { message: "Files copied + extra files in dest".to_string(), files_copied: 0, bytes_transferred: 0 },
+        4 => BackupResult::Warning { message: "Some mismatched files/dirs".to_string(), files_copied: 0, bytes_transferred: 0 },
+        5 => BackupResult::Warning { message: "Files copied + some mismatched".to_string(), files_copied: 0, bytes_transferred: 0 },
+        6 => BackupResult::Warning { message: "Extra + mismatched files".to_string(), files_copied: 0, bytes_transferred: 0 },
+        7 => BackupResult::Warning { message: "Files copied + extra + mismatched".to_string(), files_copied: 0, bytes_transferred: 0 },
         _ => BackupResult::Failed, // Exit codes 8+ indicate errors
     }
 } 
\ No newline at end of file
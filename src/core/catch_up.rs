@@ -0,0 +1,121 @@
+//! Módulo de recuperación - ejecuta los backups perdidos mientras el equipo estuvo apagado o suspendido
+//! Persiste la última ejecución exitosa por pair y detecta saltos del reloj de pared
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::core::backup::BackupResult;
+use crate::core::config::BackupPair;
+use crate::core::pair_state::PairStateStore;
+
+/// Archivo donde se persisten las últimas ejecuciones exitosas (junto a config.json)
+const LAST_RUNS_FILE: &str = "last_runs.json";
+
+/// Diferencia mínima entre reloj de pared y monotónico para considerar que hubo un salto
+const CLOCK_JUMP_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Última ejecución exitosa por backup pair (key = pair id, valor = Unix timestamp)
+#[derive(Debug)]
+pub struct LastRunStore {
+    entries: PairStateStore<i64>,
+}
+
+impl LastRunStore {
+    /// Cargar últimas ejecuciones guardadas (vacío si no existen o están corruptas)
+    pub fn load() -> Self {
+        Self {
+            entries: PairStateStore::load(LAST_RUNS_FILE),
+        }
+    }
+
+    /// Copia de todas las entradas (para restaurar el scheduler)
+    pub fn snapshot(&self) -> HashMap<String, i64> {
+        self.entries.snapshot()
+    }
+
+    /// Registrar el resultado de un pair; solo cuentan las ejecuciones que dejaron el destino al día
+    /// (los pairs omitidos por no tener cambios también están al día)
+    pub fn record(&self, pair: &BackupPair, result: &BackupResult) {
+        if result.leaves_destination_current() {
+            self.entries.insert(&pair.id, unix_now());
+        }
+    }
+
+    /// Guardar a disco conservando solo los pairs de `keep` (`pair_state::retained_pair_ids`)
+    pub fn save(&self, keep: &HashSet<String>) -> Result<()> {
+        self.entries.save(keep)
+    }
+}
+
+/// Detecta suspensiones y cambios de hora durante una espera
+///
+/// - Reloj de pared y monotónico divergen: cambio de hora o suspensión (Linux/macOS pausan el monotónico)
+/// - La espera duró mucho más de lo pedido: suspensión (en Windows el monotónico sigue corriendo)
+#[derive(Debug)]
+pub struct ClockJumpDetector {
+    wall: SystemTime,
+    mono: Instant,
+}
+
+impl Default for ClockJumpDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockJumpDetector {
+    pub fn new() -> Self {
+        Self {
+            wall: SystemTime::now(),
+            mono: Instant::now(),
+        }
+    }
+
+    /// Marcar el inicio de una espera
+    pub fn start_wait(&mut self) {
+        self.wall = SystemTime::now();
+        self.mono = Instant::now();
+    }
+
+    /// Verificar la espera iniciada con `start_wait` que debía durar `expected`
+    /// Devuelve el salto en segundos (positivo = el reloj avanzó de más)
+    pub fn check(&self, expected: Duration) -> Option<i64> {
+        let wall_elapsed = match SystemTime::now().duration_since(self.wall) {
+            Ok(elapsed) => elapsed.as_secs_f64(),
+            Err(e) => -e.duration().as_secs_f64(),
+        };
+        let mono_elapsed = self.mono.elapsed().as_secs_f64();
+        detect_jump(wall_elapsed, mono_elapsed, expected.as_secs_f64())
+    }
+}
+
+fn detect_jump(wall_elapsed: f64, mono_elapsed: f64, expected: f64) -> Option<i64> {
+    let threshold = CLOCK_JUMP_THRESHOLD.as_secs_f64();
+    let diverged = (wall_elapsed - mono_elapsed).abs() > threshold;
+    let overslept = mono_elapsed - expected > threshold;
+    (diverged || overslept).then(|| (wall_elapsed - expected).round() as i64)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_jump() {
+        // Espera normal de 1s
+        assert_eq!(detect_jump(1.0, 1.0, 1.0), None);
+        // Suspensión con monotónico pausado (Linux) o corriendo (Windows)
+        assert_eq!(detect_jump(3601.0, 1.0, 1.0), Some(3600));
+        assert_eq!(detect_jump(3601.0, 3601.0, 1.0), Some(3600));
+        // Reloj atrasado manualmente
+        assert_eq!(detect_jump(-599.0, 1.0, 1.0), Some(-600));
+    }
+}
@@ -1,10 +1,21 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::env;
 use std::path::{Path, PathBuf};
 use std::fs;
 use tracing::{info, warn, debug};
 
+use crate::core::migration::{self, CURRENT_CONFIG_VERSION};
+use crate::core::path_vars;
+use crate::core::paths;
+use crate::core::persistence;
+use crate::core::validation::{self, ValidationReport};
+pub use crate::core::preferences::UiPreferences;
+pub use crate::core::retry::RetryPolicy;
+pub use crate::core::schedule::{Schedule, ScheduleConfig, TimeWindow};
+
+/// Últimas versiones guardadas de config.json que se conservan (`config.bak.1.json` = la más reciente)
+const CONFIG_BACKUPS_KEPT: usize = 5;
+
 /// Pair de directorio origen → destino para backup
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BackupPair {
@@ -14,6 +25,18 @@ pub struct BackupPair {
     pub enabled: bool,
     #[serde(default)]
     pub priority: usize,  // Para ordenamiento manual
+    /// Límite de ancho de banda propio de este pair (se combina con el global)
+    #[serde(default)]
+    pub bandwidth_limit: Option<BandwidthLimit>,
+    /// Comandos a ejecutar antes/después de este pair
+    #[serde(default)]
+    pub hooks: Option<RunHooks>,
+    /// Modo continuo: backup disparado por cambios en el origen en lugar del intervalo
+    #[serde(default)]
+    pub continuous: Option<ContinuousMode>,
+    /// Schedule propio de este pair (None = usa el schedule global)
+    #[serde(default)]
+    pub schedule: Option<ScheduleConfig>,
 }
 
 impl BackupPair {
@@ -25,6 +48,10 @@ impl BackupPair {
             destination: destination.into(),
             enabled: true,  // Por defecto habilitado
             priority: 0,    // Se asignará automáticamente
+            bandwidth_limit: None,
+            hooks: None,
+            continuous: None,
+            schedule: None,
         }
     }
 
@@ -36,6 +63,10 @@ impl BackupPair {
             destination,
             enabled: true,
             priority: 0,
+            bandwidth_limit: None,
+            hooks: None,
+            continuous: None,
+            schedule: None,
         }
     }
 
@@ -44,6 +75,13 @@ impl BackupPair {
         self.enabled
     }
 
+    /// Copia del pair con las variables de sus rutas expandidas (`%USERPROFILE%`, `~`, `{Documents}`...)
+    pub fn resolved(&self) -> Result<BackupPair> {
+        let source = path_vars::expand(&self.source).map_err(|e| anyhow::anyhow!("Origen: {}", e))?;
+        let destination = path_vars::expand(&self.destination).map_err(|e| anyhow::anyhow!("Destino: {}", e))?;
+        Ok(BackupPair { source, destination, ..self.clone() })
+    }
+
     /// Obtener nombre corto para display
     pub fn display_name(&self) -> String {
         format!("{} → {}",
@@ -56,6 +94,14 @@ impl BackupPair {
 /// Configuración principal de la aplicación - Simple JSON junto al ejecutable
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AppConfig {
+    /// Versión del esquema; los archivos más viejos se migran al cargar
+    #[serde(default)]
+    pub config_version: u32,
+    
+    /// Perfil con nombre al que pertenece esta configuración (los demás viven en `profiles/`)
+    #[serde(default = "default_profile_name")]
+    pub profile: String,
+    
     // NEW: Lista de backup pairs
     pub backup_pairs: Vec<BackupPair>,
     
@@ -69,6 +115,199 @@ pub struct AppConfig {
     pub check_interval_seconds: u64,
     pub start_with_windows: bool,
     pub robocopy: RobocopyConfig,
+    
+    /// Máximo de backup pairs ejecutándose en paralelo
+    /// Pairs que comparten volumen destino siempre se ejecutan en serie
+    #[serde(default = "default_max_parallel_backups")]
+    pub max_parallel_backups: usize,
+    
+    /// Motor de copia: robocopy (Windows) o copia nativa
+    #[serde(default)]
+    pub engine: BackupEngine,
+    
+    /// Límite global de ancho de banda (compartido entre pairs en paralelo)
+    #[serde(default)]
+    pub bandwidth_limit: BandwidthLimit,
+    
+    /// Reintentos del pair completo ante fallos transitorios (NAS apagado, red caída)
+    #[serde(default)]
+    pub pair_retry: RetryPolicy,
+    
+    /// Hooks globales: se ejecutan una vez antes y después de cada ejecución completa
+    #[serde(default)]
+    pub hooks: RunHooks,
+    
+    /// Omitir en el daemon los pairs cuyo origen no cambió desde el último backup exitoso
+    #[serde(default = "default_skip_unchanged")]
+    pub skip_unchanged: bool,
+    
+    /// Schedule global del daemon (diario, semanal, mensual, cron...)
+    /// None = cada `check_interval_seconds` como siempre
+    #[serde(default)]
+    pub schedule: Option<ScheduleConfig>,
+    
+    /// Recuperar ejecuciones perdidas al arrancar o al volver de suspensión
+    #[serde(default)]
+    pub catch_up: CatchUpPolicy,
+    
+    /// Tema, notificaciones, nivel de log, tray e idioma (no cambian con el perfil)
+    #[serde(default)]
+    pub ui_preferences: UiPreferences,
+}
+
+/// Motor utilizado para copiar archivos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupEngine {
+    /// Robocopy de Windows - si no está disponible se usa el motor nativo
+    #[default]
+    Robocopy,
+    /// Copia nativa en Rust, disponible en cualquier plataforma
+    Native,
+}
+
+/// Límite de throughput con ventana horaria opcional
+/// Ej: 10 MB/s de 9 a 18h, sin límite el resto del día
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct BandwidthLimit {
+    /// Máximo en MB/s (None o 0 = sin límite)
+    #[serde(default)]
+    pub max_mb_per_sec: Option<f64>,
+    /// Hora local (0-23) desde la que aplica el límite; None = todo el día
+    #[serde(default)]
+    pub active_from_hour: Option<u8>,
+    /// Hora local (0-23, exclusiva) hasta la que aplica el límite; igual a la de inicio = todo el día
+    #[serde(default)]
+    pub active_until_hour: Option<u8>,
+}
+
+impl BandwidthLimit {
+    /// Límite vigente en MB/s para una hora local dada (None = sin límite)
+    pub fn limit_at_hour(&self, hour: u32) -> Option<f64> {
+        let limit = self.max_mb_per_sec.filter(|mb| *mb > 0.0)?;
+        
+        match (self.active_from_hour, self.active_until_hour) {
+            (Some(from), Some(until)) => {
+                let (from, until) = (from as u32, until as u32);
+                let active = if from == until {
+                    // Misma hora de inicio y fin: todo el día
+                    true
+                } else if from < until {
+                    hour >= from && hour < until
+                } else {
+                    // Ventana que cruza medianoche (ej. 22 → 6)
+                    hour >= from || hour < until
+                };
+                active.then_some(limit)
+            }
+            (Some(from), None) => (hour >= from as u32).then_some(limit),
+            (None, Some(until)) => (hour < until as u32).then_some(limit),
+            (None, None) => Some(limit),
+        }
+    }
+    
+    /// Descripción corta para UI y logs
+    pub fn describe(&self) -> String {
+        match (self.max_mb_per_sec.filter(|mb| *mb > 0.0), self.active_from_hour, self.active_until_hour) {
+            (None, _, _) => "Sin límite".to_string(),
+            (Some(mb), Some(from), Some(until)) if from != until => format!("{} MB/s de {}h a {}h", mb, from, until),
+            (Some(mb), Some(from), None) => format!("{} MB/s desde {}h", mb, from),
+            (Some(mb), None, Some(until)) => format!("{} MB/s hasta {}h", mb, until),
+            (Some(mb), _, _) => format!("{} MB/s", mb),
+        }
+    }
+}
+
+/// Configuración del modo continuo de un pair
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ContinuousMode {
+    /// Segundos sin cambios antes de lanzar el backup (agrupa ráfagas de escrituras)
+    #[serde(default = "default_quiet_period_seconds")]
+    pub quiet_period_seconds: u64,
+}
+
+impl Default for ContinuousMode {
+    fn default() -> Self {
+        Self {
+            quiet_period_seconds: default_quiet_period_seconds(),
+        }
+    }
+}
+
+fn default_quiet_period_seconds() -> u64 {
+    30
+}
+
+/// Comandos de shell a ejecutar antes y después de un backup
+/// Reciben contexto por variables de entorno (`RUSTYVAULT_PAIR_ID`, `RUSTYVAULT_SOURCE`,
+/// `RUSTYVAULT_DESTINATION` y `RUSTYVAULT_RESULT` en el post-hook)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RunHooks {
+    /// Comando previo; si falla, el backup se aborta
+    #[serde(default)]
+    pub pre_run: Option<String>,
+    /// Comando posterior; si falla, el resultado pasa a advertencia
+    #[serde(default)]
+    pub post_run: Option<String>,
+    /// Tiempo máximo por comando antes de terminarlo
+    #[serde(default = "default_hook_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for RunHooks {
+    fn default() -> Self {
+        Self {
+            pre_run: None,
+            post_run: None,
+            timeout_seconds: default_hook_timeout_seconds(),
+        }
+    }
+}
+
+impl RunHooks {
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout_seconds.max(1))
+    }
+}
+
+/// Recuperación de backups perdidos (equipo apagado o suspendido a la hora programada)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CatchUpPolicy {
+    /// Ejecutar al arrancar/reanudar los pairs cuya ejecución programada ya pasó
+    #[serde(default = "default_catch_up_enabled")]
+    pub enabled: bool,
+    /// Espera antes de recuperar (ej. dar tiempo a que conecte la red o la VPN)
+    #[serde(default)]
+    pub grace_delay_seconds: u64,
+}
+
+impl Default for CatchUpPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: default_catch_up_enabled(),
+            grace_delay_seconds: 0,
+        }
+    }
+}
+
+fn default_catch_up_enabled() -> bool {
+    true
+}
+
+fn default_hook_timeout_seconds() -> u64 {
+    300 // 5 minutos: suficiente para un dump de base de datos pequeña
+}
+
+fn default_skip_unchanged() -> bool {
+    true // Evita despertar discos dormidos si no hay nada que copiar
+}
+
+fn default_profile_name() -> String {
+    crate::core::profiles::DEFAULT_PROFILE.to_string()
+}
+
+fn default_max_parallel_backups() -> usize {
+    2
 }
 
 /// Configuración específica de Robocopy con tooltips explicativos
@@ -98,12 +337,23 @@ pub struct RobocopyConfig {
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            config_version: CURRENT_CONFIG_VERSION,
+            profile: default_profile_name(),
             backup_pairs: vec![], // Lista vacía por defecto
             source_folder: String::new(), // Deprecated: solo para migración
             destination_folder: String::new(), // Deprecated: solo para migración  
             check_interval_seconds: 3600, // 1 hora por defecto
             start_with_windows: false,
             robocopy: RobocopyConfig::default(),
+            max_parallel_backups: default_max_parallel_backups(),
+            engine: BackupEngine::default(),
+            bandwidth_limit: BandwidthLimit::default(),
+            pair_retry: RetryPolicy::default(),
+            hooks: RunHooks::default(),
+            skip_unchanged: default_skip_unchanged(),
+            schedule: None,
+            catch_up: CatchUpPolicy::default(),
+            ui_preferences: UiPreferences::default(),
         }
     }
 }
@@ -123,7 +373,7 @@ impl Default for RobocopyConfig {
 impl AppConfig {
     /// Cargar configuración desde config.json en carpeta del ejecutable
     /// Si no existe, crear con valores por defecto
-    /// Auto-migra formato legacy (single backup) a nuevo formato (multiple backups)
+    /// Archivos de versiones anteriores se migran (con copia previa del original)
     pub fn load() -> Result<Self> {
         let config_path = get_config_path()?;
         
@@ -133,28 +383,18 @@ impl AppConfig {
             let config_content = fs::read_to_string(&config_path)
                 .with_context(|| format!("Error leyendo config.json: {}", config_path.display()))?;
             
-            let mut config: AppConfig = serde_json::from_str(&config_content)
-                .with_context(|| "Error parseando config.json - JSON inválido")?;
+            let (config, migrated_from) = Self::from_json(&config_content)?;
             
-            // 🔄 AUTO-MIGRACIÓN: legacy single backup → multiple backups
-            if config.backup_pairs.is_empty() && !config.source_folder.is_empty() && !config.destination_folder.is_empty() {
-                info!("🔄 Migrando configuración legacy a formato múltiple backups");
-                
-                config.backup_pairs.push(BackupPair::new(
-                    config.source_folder.clone(),
-                    config.destination_folder.clone(),
-                ));
-                
-                // Limpiar campos legacy
-                config.source_folder.clear();
-                config.destination_folder.clear();
-                
-                // Auto-guardar formato migrado
+            // 🔄 AUTO-MIGRACIÓN: respaldar el original antes de sobrescribirlo con la versión actual
+            if let Some(version) = migrated_from {
+                migration::backup_before_migration(&config_path, version)?;
                 config.save().context("Error guardando configuración migrada")?;
-                info!("✅ Migración automática completada");
+                info!("✅ Migración automática completada (v{} → v{})", version, CURRENT_CONFIG_VERSION);
             }
             
             info!("✅ Configuración cargada correctamente");
+            // Solo se informa: la GUI ofrece restaurar y `--backup-now` no ejecuta si hay errores bloqueantes
+            config.validate().log("config.json");
             debug!("🔧 Backup pairs: {}", config.backup_pairs.len());
             debug!("🔧 Interval: {}s", config.check_interval_seconds);
             
@@ -175,60 +415,99 @@ impl AppConfig {
         }
     }
     
+    /// Parsear un config.json de cualquier versión y migrarlo a la actual
+    /// Devuelve también la versión original si hubo que migrar
+    pub fn from_json(content: &str) -> Result<(Self, Option<u32>)> {
+        let raw: serde_json::Value = serde_json::from_str(content)
+            .with_context(|| "Error parseando config.json - JSON inválido")?;
+        let (migrated, original) = migration::migrate(raw)?;
+        let config = serde_json::from_value(migrated)
+            .with_context(|| format!("Error parseando config.json (versión {})", original))?;
+        Ok((config, (original < CURRENT_CONFIG_VERSION).then_some(original)))
+    }
+    
+    /// Una configuración de una versión más nueva se cargó sin migrar y es de solo lectura:
+    /// reescribirla descartaría los campos que esta build no conoce
+    pub fn ensure_writable(&self) -> Result<()> {
+        if self.config_version > CURRENT_CONFIG_VERSION {
+            bail!("config.json es de una versión más nueva (v{} > v{}) - no se sobrescribe; actualiza RustyVault",
+                  self.config_version, CURRENT_CONFIG_VERSION);
+        }
+        Ok(())
+    }
+    
     /// Guardar configuración a config.json
-    /// Auto-save en cada cambio según PRD
+    /// Auto-save en cada cambio según PRD; escritura atómica y rotación de las últimas copias
     pub fn save(&self) -> Result<()> {
+        self.ensure_writable()?;
         let config_path = get_config_path()?;
         
         let config_json = serde_json::to_string_pretty(self)
             .context("Error serializando configuración a JSON")?;
         
-        fs::write(&config_path, config_json)
+        // En modo instalado la carpeta del usuario puede no existir todavía
+        if let Some(dir) = config_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Error creando carpeta de configuración: {}", dir.display()))?;
+        }
+        
+        persistence::write_atomic(&config_path, config_json.as_bytes())
             .with_context(|| format!("Error escribiendo config.json: {}", config_path.display()))?;
+        persistence::push_copy(&config_path, CONFIG_BACKUPS_KEPT, &config_json);
         
         debug!("💾 Configuración guardada en: {}", config_path.display());
         Ok(())
     }
     
-    /// Validar que todas las rutas de backup pairs sean válidas
-    pub fn validate_paths(&self) -> Result<()> {
-        if self.backup_pairs.is_empty() {
-            return Err(anyhow::anyhow!("❌ No hay backup pairs configurados"));
-        }
+    /// Copia válida más reciente de config.json (para recuperar un archivo dañado o con errores bloqueantes)
+    pub fn latest_valid_backup() -> Option<PathBuf> {
+        let config_path = get_config_path().ok()?;
+        persistence::latest_valid_copy(&config_path, CONFIG_BACKUPS_KEPT, |content| {
+            Self::from_json(content).is_ok_and(|(config, _)| config.validate().blocking().next().is_none())
+        })
+    }
+    
+    /// Reemplazar config.json por una copia; el archivo dañado queda como `config.corrupt.json`
+    pub fn restore_backup(backup_path: &Path) -> Result<Self> {
+        let config_path = get_config_path()?;
+        let content = fs::read_to_string(backup_path)
+            .with_context(|| format!("Error leyendo copia {}", backup_path.display()))?;
+        let (config, _) = Self::from_json(&content)?;
         
-        for (i, pair) in self.backup_pairs.iter().enumerate() {
-            if !pair.enabled {
-                continue; // Skip disabled pairs
-            }
-            
-            // Validar source folder existe
-            if !pair.source.exists() {
-            return Err(anyhow::anyhow!(
-                    "❌ Backup #{}: Carpeta origen no existe: {}", 
-                    i + 1, pair.source.display()
-            ));
+        if config_path.exists() {
+            let corrupt_path = config_path.with_file_name("config.corrupt.json");
+            fs::rename(&config_path, &corrupt_path)
+                .with_context(|| format!("Error apartando config.json dañado a {}", corrupt_path.display()))?;
+            warn!("⚠️ config.json dañado conservado como {}", corrupt_path.display());
         }
+        config.save()?;
         
-            if !pair.source.is_dir() {
-                return Err(anyhow::anyhow!(
-                    "❌ Backup #{}: Ruta origen no es una carpeta: {}", 
-                    i + 1, pair.source.display()
-                ));
-            }
-            
-            // Destination se auto-crea, solo validar que sea una ruta válida
-            if let Some(parent) = pair.destination.parent() {
-                if !parent.exists() {
-                    return Err(anyhow::anyhow!(
-                        "❌ Backup #{}: Carpeta padre del destino no existe: {}", 
-                        i + 1, parent.display()
-                ));
-                }
-            }
+        info!("♻️ Configuración restaurada desde {}", backup_path.display());
+        Ok(config)
+    }
+    
+    /// Schedule global efectivo (el intervalo clásico si no hay uno configurado)
+    pub fn effective_schedule(&self) -> ScheduleConfig {
+        self.schedule
+            .clone()
+            .unwrap_or_else(|| ScheduleConfig::interval(self.check_interval_seconds))
+    }
+    
+    /// Backup pairs habilitados, en el orden de la configuración
+    pub fn enabled_pairs(&self) -> Vec<BackupPair> {
+        self.backup_pairs.iter().filter(|pair| pair.enabled).cloned().collect()
+    }
+    
+    /// Sincronizar `priority` con el orden de la lista (el runner ejecuta por prioridad)
+    pub fn renumber_priorities(&mut self) {
+        for (index, pair) in self.backup_pairs.iter_mut().enumerate() {
+            pair.priority = index + 1;
         }
-        
-        info!("✅ Validación de {} backup pairs exitosa", self.backup_pairs.len());
-        Ok(())
+    }
+    
+    /// Validar rutas de todos los pairs, schedules, motor y opciones (reporte completo)
+    pub fn validate(&self) -> ValidationReport {
+        validation::validate(self)
     }
 }
 
@@ -267,25 +546,23 @@ impl RobocopyConfig {
     }
 }
 
-/// Obtener ruta del archivo config.json (carpeta del ejecutable)
+/// Obtener ruta del archivo config.json (ver `paths`: --config, variable de entorno, portable o instalado)
 fn get_config_path() -> Result<PathBuf> {
-    // Prioridad: carpeta del ejecutable
-    if let Ok(exe_path) = env::current_exe() {
-        if let Some(exe_dir) = exe_path.parent() {
-            return Ok(exe_dir.join("config.json"));
-        }
-    }
-    
-    // Fallback a directorio actual
-    Ok(PathBuf::from("config.json"))
+    Ok(paths::current().config_file.clone())
+}
+
+/// Ruta de un archivo de datos auxiliar guardado junto a config.json
+pub fn data_file_path(file_name: &str) -> Result<PathBuf> {
+    Ok(paths::current().data_dir().join(file_name))
 }
 
 /// Carpeta por defecto para source (Documents del usuario)
+/// Sin carpeta conocida se guarda la variable, que se expande al ejecutar (ver `path_vars`)
 fn get_default_source_folder() -> String {
     if let Some(docs_dir) = dirs::document_dir() {
         docs_dir.to_string_lossy().to_string()
     } else {
-        "C:\\Users\\%USERNAME%\\Documents".to_string()
+        "%USERPROFILE%\\Documents".to_string()
     }
 }
 
@@ -0,0 +1,276 @@
+//! Módulo de exportación/importación - llevar una configuración estándar a otras máquinas
+//! El archivo exportado es un config.json completo (sirve también con `--config`); al importar se
+//! migra, se valida (`validation`) y se muestra qué cambia antes de reemplazar o combinar
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::core::config::{AppConfig, BackupPair};
+use crate::core::persistence;
+use crate::core::validation::ValidationReport;
+
+/// Nombre sugerido al exportar
+pub const EXPORT_FILE_NAME: &str = "rustyvault-config.json";
+
+/// Cómo aplicar la configuración importada
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// La configuración importada reemplaza a la actual por completo
+    Replace,
+    /// Se agregan los pairs importados; los ajustes globales locales se mantienen
+    Merge,
+}
+
+/// Una diferencia entre la configuración actual y la resultante
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    PairAdded(String),
+    PairRemoved(String),
+    /// Pair con el mismo id y campos distintos
+    PairChanged { pair: String, fields: Vec<&'static str> },
+    Setting { name: &'static str, from: String, to: String },
+}
+
+impl ConfigChange {
+    pub fn describe(&self) -> String {
+        match self {
+            ConfigChange::PairAdded(pair) => format!("➕ {}", pair),
+            ConfigChange::PairRemoved(pair) => format!("➖ {}", pair),
+            ConfigChange::PairChanged { pair, fields } => format!("✏ {} ({})", pair, fields.join(", ")),
+            ConfigChange::Setting { name, from, to } => format!("⚙ {}: {} → {}", name, from, to),
+        }
+    }
+}
+
+/// Exportar la configuración completa (pairs, motor, schedules, preferencias)
+pub fn export_config(config: &AppConfig, path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(config).context("Error serializando configuración")?;
+    persistence::write_atomic(path, json.as_bytes())
+        .with_context(|| format!("Error exportando configuración a {}", path.display()))?;
+    info!("📤 Configuración exportada a {}", path.display());
+    Ok(())
+}
+
+/// Configuración leída de un archivo, validada y lista para aplicar
+#[derive(Debug, Clone)]
+pub struct ImportPreview {
+    pub path: PathBuf,
+    pub incoming: AppConfig,
+    /// Versión del archivo si hubo que migrarlo
+    pub migrated_from: Option<u32>,
+    pub report: ValidationReport,
+}
+
+impl ImportPreview {
+    /// Leer y validar un archivo exportado (de esta o de versiones anteriores)
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Error leyendo {}", path.display()))?;
+        let (incoming, migrated_from) = AppConfig::from_json(&content)
+            .with_context(|| format!("{} no es una configuración de RustyVault válida", path.display()))?;
+        let report = incoming.validate();
+        Ok(Self { path: path.to_path_buf(), incoming, migrated_from, report })
+    }
+
+    /// Pairs que se importarán deshabilitados por errores (rutas que no existen en esta máquina...)
+    pub fn disabled_pairs(&self) -> usize {
+        self.incoming.backup_pairs
+            .iter()
+            .filter(|pair| pair.enabled && self.report.pair_has_errors(&pair.id))
+            .count()
+    }
+
+    /// Hay errores que impiden importar (schedules u opciones inválidas)
+    pub fn is_blocked(&self) -> bool {
+        self.report.blocking().next().is_some()
+    }
+
+    /// Configuración resultante de aplicar la importación sobre `current`
+    pub fn apply(&self, current: &AppConfig, mode: ImportMode) -> AppConfig {
+        let mut incoming = self.incoming.clone();
+        // Pairs con errores en esta máquina se importan deshabilitados
+        for pair in incoming.backup_pairs.iter_mut() {
+            if self.report.pair_has_errors(&pair.id) {
+                pair.enabled = false;
+            }
+        }
+
+        let mut result = match mode {
+            // El contenido reemplaza al perfil activo, que conserva su nombre y los ajustes de esta
+            // máquina (iniciar con Windows, preferencias de interfaz), igual que al cambiar de perfil
+            ImportMode::Replace => AppConfig {
+                profile: current.profile.clone(),
+                start_with_windows: current.start_with_windows,
+                ui_preferences: current.ui_preferences.clone(),
+                ..incoming
+            },
+            ImportMode::Merge => merge(current, incoming.backup_pairs),
+        };
+        result.renumber_priorities();
+        result
+    }
+}
+
+/// Agregar `incoming` a los pairs actuales: los repetidos (mismas rutas) se omiten y los ids
+/// que ya existen se regeneran para no pisar historial ni estado de otro pair
+fn merge(current: &AppConfig, incoming: Vec<BackupPair>) -> AppConfig {
+    let mut result = current.clone();
+    let mut ids: HashSet<String> = result.backup_pairs.iter().map(|pair| pair.id.clone()).collect();
+
+    for mut pair in incoming {
+        let duplicate = result.backup_pairs
+            .iter()
+            .any(|existing| existing.source == pair.source && existing.destination == pair.destination);
+        if duplicate {
+            continue;
+        }
+        if ids.contains(&pair.id) {
+            pair.id = uuid::Uuid::new_v4().to_string();
+        }
+        ids.insert(pair.id.clone());
+        result.backup_pairs.push(pair);
+    }
+    result
+}
+
+fn pair_label(pair: &BackupPair) -> String {
+    format!("{} → {}", pair.source.display(), pair.destination.display())
+}
+
+/// Diferencias entre dos configuraciones (pairs por id y ajustes globales)
+pub fn diff(current: &AppConfig, updated: &AppConfig) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+
+    for pair in &updated.backup_pairs {
+        match current.backup_pairs.iter().find(|existing| existing.id == pair.id) {
+            None => changes.push(ConfigChange::PairAdded(pair_label(pair))),
+            Some(existing) => {
+                let fields = changed_pair_fields(existing, pair);
+                if !fields.is_empty() {
+                    changes.push(ConfigChange::PairChanged { pair: pair_label(pair), fields });
+                }
+            }
+        }
+    }
+    for pair in &current.backup_pairs {
+        if !updated.backup_pairs.iter().any(|candidate| candidate.id == pair.id) {
+            changes.push(ConfigChange::PairRemoved(pair_label(pair)));
+        }
+    }
+
+    let mut setting = |name: &'static str, from: String, to: String| {
+        if from != to {
+            changes.push(ConfigChange::Setting { name, from, to });
+        }
+    };
+    setting("Perfil", current.profile.clone(), updated.profile.clone());
+    setting("Intervalo", format!("{}s", current.check_interval_seconds), format!("{}s", updated.check_interval_seconds));
+    setting("Schedule", current.effective_schedule().describe(), updated.effective_schedule().describe());
+    setting("Motor", format!("{:?}", current.engine), format!("{:?}", updated.engine));
+    setting("Robocopy", current.robocopy.build_args().join(" "), updated.robocopy.build_args().join(" "));
+    setting("Límite de ancho de banda", current.bandwidth_limit.describe(), updated.bandwidth_limit.describe());
+    setting("Pairs en paralelo", current.max_parallel_backups.to_string(), updated.max_parallel_backups.to_string());
+    setting("Reintentos", format!("{:?}", current.pair_retry), format!("{:?}", updated.pair_retry));
+    setting("Hooks globales", format!("{:?}", current.hooks), format!("{:?}", updated.hooks));
+    setting("Omitir sin cambios", current.skip_unchanged.to_string(), updated.skip_unchanged.to_string());
+    setting("Recuperar ejecuciones perdidas", format!("{:?}", current.catch_up), format!("{:?}", updated.catch_up));
+    setting("Iniciar con Windows", current.start_with_windows.to_string(), updated.start_with_windows.to_string());
+    setting("Preferencias de interfaz", format!("{:?}", current.ui_preferences), format!("{:?}", updated.ui_preferences));
+    changes
+}
+
+fn changed_pair_fields(a: &BackupPair, b: &BackupPair) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if a.source != b.source {
+        fields.push("origen");
+    }
+    if a.destination != b.destination {
+        fields.push("destino");
+    }
+    if a.enabled != b.enabled {
+        fields.push("habilitado");
+    }
+    if a.bandwidth_limit != b.bandwidth_limit {
+        fields.push("límite");
+    }
+    if a.hooks != b.hooks {
+        fields.push("hooks");
+    }
+    if a.continuous != b.continuous {
+        fields.push("modo continuo");
+    }
+    if a.schedule != b.schedule {
+        fields.push("schedule");
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_regenerates_conflicting_ids() {
+        let current = AppConfig {
+            backup_pairs: vec![
+                BackupPair::with_id("a".to_string(), "C:\\docs".into(), "D:\\docs".into()),
+                BackupPair::with_id("b".to_string(), "C:\\fotos".into(), "D:\\fotos".into()),
+            ],
+            ..AppConfig::default()
+        };
+        let preview = ImportPreview {
+            path: PathBuf::from(EXPORT_FILE_NAME),
+            incoming: AppConfig {
+                backup_pairs: vec![
+                    // Mismas rutas que un pair local: se omite
+                    BackupPair::with_id("x".to_string(), "C:\\docs".into(), "D:\\docs".into()),
+                    // Id repetido con otras rutas: se agrega con id nuevo
+                    BackupPair::with_id("b".to_string(), "C:\\musica".into(), "D:\\musica".into()),
+                ],
+                check_interval_seconds: 60,
+                ..AppConfig::default()
+            },
+            migrated_from: None,
+            report: ValidationReport::default(),
+        };
+
+        let merged = preview.apply(&current, ImportMode::Merge);
+        assert_eq!(merged.backup_pairs.len(), 3);
+        assert_eq!(merged.check_interval_seconds, current.check_interval_seconds);
+        let added = &merged.backup_pairs[2];
+        assert_ne!(added.id, "b");
+        assert_eq!(added.priority, 3);
+        assert_eq!(diff(&current, &merged), vec![ConfigChange::PairAdded(pair_label(added))]);
+
+        let replaced = preview.apply(&current, ImportMode::Replace);
+        let changes = diff(&current, &replaced);
+        assert!(changes.contains(&ConfigChange::PairRemoved(pair_label(&current.backup_pairs[0]))));
+        assert!(changes.contains(&ConfigChange::PairChanged { pair: pair_label(&replaced.backup_pairs[1]), fields: vec!["origen", "destino"] }));
+        assert!(changes.iter().any(|change| matches!(change, ConfigChange::Setting { name: "Intervalo", .. })));
+    }
+
+    #[test]
+    fn test_replace_keeps_machine_settings() {
+        let mut current = AppConfig { start_with_windows: true, ..AppConfig::default() };
+        current.ui_preferences.theme = crate::core::preferences::AppTheme::Light;
+        let mut incoming = AppConfig { start_with_windows: false, check_interval_seconds: 60, ..AppConfig::default() };
+        incoming.ui_preferences.theme = crate::core::preferences::AppTheme::Dark;
+        let preview = ImportPreview {
+            path: PathBuf::from(EXPORT_FILE_NAME),
+            incoming,
+            migrated_from: None,
+            report: ValidationReport::default(),
+        };
+
+        let replaced = preview.apply(&current, ImportMode::Replace);
+        assert_eq!(replaced.check_interval_seconds, 60);
+        assert!(replaced.start_with_windows);
+        assert_eq!(replaced.ui_preferences, current.ui_preferences);
+        assert!(!diff(&current, &replaced)
+            .iter()
+            .any(|change| matches!(change, ConfigChange::Setting { name: "Iniciar con Windows" | "Preferencias de interfaz", .. })));
+    }
+}
@@ -0,0 +1,160 @@
+//! Módulo de recarga en caliente - detecta ediciones de config.json hechas fuera de la app
+//! Se vigila la carpeta (no el archivo): los editores y `write_atomic` reemplazan el archivo con
+//! un rename, y un watch sobre el archivo original dejaría de recibir eventos tras el primero
+
+use anyhow::{Context, Result};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, info, warn};
+
+/// Espera sin eventos antes de avisar (un guardado genera varios eventos seguidos)
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Vigila config.json; el watch se detiene al descartar el valor
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    /// Llamar a `on_change` (desde un thread propio) cada vez que config.json cambie en disco
+    /// También se avisa de los guardados de la propia app: quien recibe el aviso compara contenidos
+    pub fn start(config_path: &Path, on_change: impl Fn() + Send + 'static) -> Result<Self> {
+        let dir = config_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let file_name = config_path.file_name().map(|name| name.to_os_string()).unwrap_or_default();
+        let (sender, receiver) = mpsc::channel::<()>();
+
+        let handler = move |event: notify::Result<Event>| match event {
+            Ok(event) => {
+                if touches_file(&event, &file_name) {
+                    let _ = sender.send(());
+                }
+            }
+            Err(e) => warn!("⚠️ Error vigilando config.json: {}", e),
+        };
+
+        let mut watcher = notify::recommended_watcher(handler).context("Error creando watcher de config.json")?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Error vigilando {}", dir.display()))?;
+
+        // El thread termina cuando se descarta el watcher (se cierra el canal)
+        thread::spawn(move || debounce(&receiver, DEBOUNCE, on_change));
+
+        info!("👁️ Vigilando cambios externos en {}", config_path.display());
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// El evento modifica el archivo vigilado (las lecturas y los demás archivos de la carpeta no cuentan)
+fn touches_file(event: &Event, file_name: &OsStr) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event.paths.iter().any(|path| path.file_name() == Some(file_name))
+}
+
+/// Un aviso por ráfaga: tras cada evento se espera `quiet` sin eventos nuevos antes de llamar a `on_change`
+/// Termina cuando se cierra el canal
+fn debounce(receiver: &Receiver<()>, quiet: Duration, on_change: impl Fn()) {
+    while receiver.recv().is_ok() {
+        loop {
+            match receiver.recv_timeout(quiet) {
+                Ok(()) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        debug!("👁️ config.json cambió en disco");
+        on_change();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, ModifyKind};
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    /// Esperar hasta que `done` se cumpla (con un límite generoso para CI cargado)
+    fn wait_until(done: impl Fn() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if done() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        done()
+    }
+
+    #[test]
+    fn test_burst_of_events_notifies_once() {
+        let (sender, receiver) = mpsc::channel();
+        let notifications = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&notifications);
+
+        // La ráfaga ya está en el canal antes de que el debounce empiece: se agrupa siempre
+        for _ in 0..3 {
+            sender.send(()).unwrap();
+        }
+        let debouncer = thread::spawn(move || debounce(&receiver, DEBOUNCE, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        assert!(wait_until(|| notifications.load(Ordering::SeqCst) >= 1));
+
+        // Otro evento más tarde es otro aviso
+        sender.send(()).unwrap();
+        assert!(wait_until(|| notifications.load(Ordering::SeqCst) >= 2));
+
+        drop(sender);
+        debouncer.join().unwrap();
+        assert_eq!(notifications.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_only_config_file_changes_count() {
+        let dir = std::env::temp_dir();
+        let file_name = OsStr::new("config.json");
+        let modify = |name: &str| Event::new(EventKind::Modify(ModifyKind::Any)).add_path(dir.join(name));
+
+        assert!(touches_file(&modify("config.json"), file_name));
+        // Otros archivos de la carpeta (historial, copias, temporales) no cuentan
+        assert!(!touches_file(&modify("history.jsonl"), file_name));
+        assert!(!touches_file(&modify("config.tmp.1.0.json"), file_name));
+        let read = Event::new(EventKind::Access(AccessKind::Any)).add_path(dir.join("config.json"));
+        assert!(!touches_file(&read, file_name));
+    }
+
+    #[test]
+    fn test_watcher_reports_external_edits() {
+        let dir = std::env::temp_dir().join(format!("rustyvault_config_watch_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        fs::write(&path, "{}").unwrap();
+
+        let notifications = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&notifications);
+        let watcher = ConfigWatcher::start(&path, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        fs::write(&path, "{\"check_interval_seconds\": 60}").unwrap();
+        let notified = wait_until(|| notifications.load(Ordering::SeqCst) >= 1);
+        drop(watcher);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(notified);
+    }
+}
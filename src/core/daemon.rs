@@ -1,15 +1,39 @@
-/// Módulo de daemon - lógica del backup automático con intervalos
+/// Módulo de daemon - lógica del backup automático con schedules
 /// Implementación real con threads para consistencia con el resto de la app
 
 use anyhow::Result;
+use chrono::Local;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tracing::{info, debug, error, warn};
 
-use crate::core::AppConfig;
-use crate::core::backup::{execute_backup, BackupResult};
+use crate::core::{AppConfig, BackupPair};
+use crate::core::catch_up::{ClockJumpDetector, LastRunStore};
+use crate::core::events::{AppEvent, EventBus};
+use crate::core::runner::{BackupRunner, RunSummary, RunTrigger};
+use crate::core::schedule::{PairScheduler, SystemClock};
+use crate::core::watcher::SourceWatcher;
+
+/// Espera máxima entre revisiones aunque no haya nada programado (red de seguridad)
+const MAX_IDLE_WAIT: Duration = Duration::from_secs(60);
+
+/// Frecuencia de revisión mientras hay pairs en modo continuo (quiet periods)
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Mensajes de control para el thread del daemon (lo despiertan de inmediato)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonControl {
+    /// Ejecutar ya todos los pairs programados
+    RunNow,
+    /// Releer la configuración y reprogramar
+    Reload,
+    /// Terminar sin esperar al próximo tick
+    Stop,
+}
 
 /// Estructura del daemon de backup automático
 pub struct BackupDaemon {
@@ -19,15 +43,30 @@ pub struct BackupDaemon {
     running: Arc<AtomicBool>,
     /// Handle del thread del daemon
     handle: Option<std::thread::JoinHandle<()>>,
+    /// Canal para despertar al thread del daemon (compartido con el suscriptor de ConfigChanged)
+    control: Arc<Mutex<Option<Sender<DaemonControl>>>>,
+    /// Bus donde se publican ejecuciones, estado y próximas ejecuciones
+    events: EventBus,
 }
 
 impl BackupDaemon {
-    /// Crear nueva instancia del daemon
-    pub fn new(config: Arc<Mutex<AppConfig>>) -> Self {
+    /// Crear nueva instancia del daemon; se reprograma solo ante `AppEvent::ConfigChanged`
+    pub fn new(config: Arc<Mutex<AppConfig>>, events: EventBus) -> Self {
+        let control: Arc<Mutex<Option<Sender<DaemonControl>>>> = Arc::new(Mutex::new(None));
+        
+        let reload_control = Arc::clone(&control);
+        events.subscribe(move |event| {
+            if matches!(event, AppEvent::ConfigChanged) {
+                send_control(&reload_control, DaemonControl::Reload);
+            }
+        });
+        
         Self {
             config,
             running: Arc::new(AtomicBool::new(false)),
             handle: None,
+            control,
+            events,
         }
     }
     
@@ -44,13 +83,19 @@ impl BackupDaemon {
         // Clonar datos para el thread
         let config_clone = Arc::clone(&self.config);
         let running_clone = Arc::clone(&self.running);
+        let events = self.events.clone();
+        let (control_sender, control_receiver) = mpsc::channel();
         
         // Spawear daemon task en thread separado
         let handle = std::thread::spawn(move || {
-            daemon_task(config_clone, running_clone);
+            daemon_task(config_clone, running_clone, control_receiver, events);
         });
         
         self.handle = Some(handle);
+        if let Ok(mut control) = self.control.lock() {
+            *control = Some(control_sender);
+        }
+        self.events.publish(AppEvent::DaemonStateChanged { running: true });
         
         // Mostrar notificación de daemon iniciado
         if let Ok(config) = self.config.lock() {
@@ -72,10 +117,14 @@ impl BackupDaemon {
         
         info!("🛑 Deteniendo daemon de backup...");
         
-        // Señalizar al daemon que pare
+        // Señalizar al daemon que pare y despertarlo si está esperando
         self.running.store(false, Ordering::Relaxed);
+        self.send_control(DaemonControl::Stop);
+        if let Ok(mut control) = self.control.lock() {
+            *control = None;
+        }
         
-        // Esperar a que termine el thread
+        // Esperar a que termine el thread (una copia en curso se corta al ver el flag)
         if let Some(handle) = self.handle.take() {
             match handle.join() {
                 Ok(_) => info!("✅ Daemon thread terminado correctamente"),
@@ -83,6 +132,8 @@ impl BackupDaemon {
             }
         }
         
+        self.events.publish(AppEvent::DaemonStateChanged { running: false });
+        
         // Mostrar notificación de daemon detenido
         if let Err(e) = crate::system::notifications::show_daemon_stopped() {
             warn!("⚠️ Error mostrando notificación daemon: {}", e);
@@ -92,6 +143,15 @@ impl BackupDaemon {
         Ok(())
     }
     
+    /// Ejecutar ya los pairs programados sin esperar a su hora
+    pub fn run_now(&self) {
+        self.send_control(DaemonControl::RunNow);
+    }
+    
+    fn send_control(&self, message: DaemonControl) {
+        send_control(&self.control, message);
+    }
+    
     /// Verificar si el daemon está corriendo
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::Relaxed)
@@ -110,126 +170,176 @@ impl BackupDaemon {
     }
 }
 
+/// Despertar al thread del daemon (si está corriendo)
+fn send_control(control: &Mutex<Option<Sender<DaemonControl>>>, message: DaemonControl) {
+    if let Ok(control) = control.lock() {
+        if let Some(sender) = control.as_ref() {
+            if sender.send(message).is_err() {
+                debug!("Daemon thread ya terminado - {:?} ignorado", message);
+            }
+        }
+    }
+}
+
+/// Ejecutar un conjunto de pairs con el runner, publicando su avance en el bus
+fn run_pairs(app_config: &AppConfig, pairs: &[BackupPair], trigger: RunTrigger, running: &Arc<AtomicBool>, events: &EventBus) -> RunSummary {
+    BackupRunner::new(app_config, trigger)
+        .with_events(events)
+        .with_running_flag(running)
+        .run(pairs)
+}
+
 /// Task principal del daemon - se ejecuta en background
-fn daemon_task(config: Arc<Mutex<AppConfig>>, running: Arc<AtomicBool>) {
+/// Espera hasta la próxima ejecución o hasta recibir un mensaje de control
+fn daemon_task(
+    config: Arc<Mutex<AppConfig>>,
+    running: Arc<AtomicBool>,
+    control: Receiver<DaemonControl>,
+    events: EventBus,
+) {
     info!("🤖 Daemon task iniciado - comenzando loop automático");
     
-    let mut iteration = 0;
+    let mut run_number = 0;
+    let mut watcher = SourceWatcher::new();
+    let mut scheduler = PairScheduler::new(SystemClock);
+    let mut clock_jumps = ClockJumpDetector::new();
+    let mut warned_empty = false;
+    let mut published = None;
+    
+    // Recordar cuándo corrió cada pair en sesiones anteriores para recuperar los atrasados
+    scheduler.restore_last_runs(LastRunStore::load().snapshot());
     
     while running.load(Ordering::Relaxed) {
-        iteration += 1;
-        debug!("🔄 Daemon iteration #{}", iteration);
-        
-        // Obtener configuración actual
-        let (backup_pairs, robocopy_config, interval) = match config.lock() {
-            Ok(cfg) => {
-                (
-                    cfg.backup_pairs.clone(),
-                    cfg.robocopy.clone(),
-                    cfg.check_interval_seconds,
-                )
-            }
+        // Obtener configuración actual (puede cambiar mientras el daemon corre)
+        let app_config = match config.lock() {
+            Ok(cfg) => cfg.clone(),
             Err(e) => {
                 error!("❌ Error accediendo configuración en daemon: {}", e);
-                // Sleep un poco y continuar
-                std::thread::sleep(Duration::from_secs(60));
-                continue;
+                // Reintentar más tarde sin dejar de atender Stop
+                match control.recv_timeout(MAX_IDLE_WAIT) {
+                    Ok(DaemonControl::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+                    _ => continue,
+                }
             }
         };
+        let backup_pairs = &app_config.backup_pairs;
         
-        // Validar configuración antes de ejecutar
         if backup_pairs.is_empty() {
-            warn!("⚠️ No hay backup pairs configurados - omitiendo backup automático");
+            if !warned_empty {
+                warn!("⚠️ No hay backup pairs configurados - omitiendo backup automático");
+                warned_empty = true;
+            }
         } else {
-            // Ejecutar backup automático secuencial
-            info!("🚀 Ejecutando backup automático #{} - {} pair(s) a procesar", iteration, backup_pairs.len());
+            warned_empty = false;
+        }
+        
+        watcher.sync(backup_pairs, false);
+        scheduler.set_catch_up(&app_config.catch_up);
+        
+        // Pairs en modo continuo con watch sano no dependen del schedule
+        let scheduled: Vec<_> = backup_pairs
+            .iter()
+            .filter(|pair| pair.enabled && !watcher.is_watched(&pair.id))
+            .cloned()
+            .collect();
+        
+        let due = scheduler.due_pairs(&scheduled, &app_config.effective_schedule());
+        if !due.is_empty() {
+            run_number += 1;
+            debug!("🔄 Ejecución programada #{}", run_number);
             
-            let mut total_success = 0;
-            let mut total_warnings = 0;
-            let mut total_failures = 0;
+            let due_pairs: Vec<_> = scheduled
+                .iter()
+                .filter(|pair| due.contains(&pair.id))
+                .cloned()
+                .collect();
+            let summary = run_pairs(&app_config, &due_pairs, RunTrigger::Schedule(run_number), &running, &events);
             
-            // Ejecutar cada backup pair secuencialmente
-            for (i, pair) in backup_pairs.iter().enumerate() {
-                if !pair.enabled {
-                    info!("⏭️ Backup pair #{} deshabilitado - omitiendo", i + 1);
-                    continue;
-                }
-                
-                info!("🔄 Procesando backup pair #{}: {} → {}", 
-                     i + 1, pair.source.display(), pair.destination.display());
-                
-                match execute_backup(&pair.source, &pair.destination, &robocopy_config) {
-                    Ok(result) => {
-                        match result {
-                            BackupResult::Success { files_copied, bytes_transferred } => {
-                                info!("✅ Backup automático pair #{} completado exitosamente - {} archivos, {} bytes", i + 1, files_copied, bytes_transferred);
-                                total_success += 1;
-                            }
-                            BackupResult::Warning(msg) => {
-                                warn!("⚠️ Backup automático pair #{} con advertencias: {}", i + 1, msg);
-                                total_warnings += 1;
-                            }
-                            BackupResult::Failed => {
-                                error!("❌ Backup automático pair #{} falló", i + 1);
-                                total_failures += 1;
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("❌ Error crítico en backup automático pair #{}: {}", i + 1, e);
-                        total_failures += 1;
-                    }
+            for id in &due {
+                scheduler.mark_ran(id, summary.completed_pairs.contains(id));
+                if let Some(next) = scheduler.next_run_at(id) {
+                    debug!("📅 Pair {}: próxima ejecución {}", id, next.format("%Y-%m-%d %H:%M"));
                 }
             }
             
-            // Notificación consolidada para daemon automático
-            if total_failures > 0 {
-                let msg = format!("Daemon #{}: {} exitosos, {} advertencias, {} fallidos", 
-                                 iteration, total_success, total_warnings, total_failures);
-                if let Err(e) = crate::system::notifications::show_backup_failed(&msg) {
-                    warn!("⚠️ Error mostrando notificación: {}", e);
-                }
-            } else if total_warnings > 0 {
-                let msg = format!("Daemon #{}: {} exitosos, {} advertencias", 
-                                 iteration, total_success, total_warnings);
-                if let Err(e) = crate::system::notifications::show_backup_warning(&msg) {
-                    warn!("⚠️ Error mostrando notificación: {}", e);
-                }
-            } else {
-                info!("✅ Daemon #{}: {} backups completados exitosamente", iteration, total_success);
-                if let Err(e) = crate::system::notifications::show_backup_success(Some(total_success as u32), Some("automático")) {
-                    warn!("⚠️ Error mostrando notificación: {}", e);
+            // Re-armar watches degradados (sus cambios perdidos ya se copiaron)
+            watcher.sync(backup_pairs, true);
+        }
+        
+        // Cambios constantes nunca esperan más que el intervalo
+        let max_wait = Duration::from_secs(app_config.check_interval_seconds.max(1));
+        let watch_due = watcher.due_pairs(Instant::now(), max_wait);
+        if !watch_due.is_empty() {
+            let due_pairs: Vec<_> = backup_pairs
+                .iter()
+                .filter(|pair| pair.enabled && watch_due.contains(&pair.id))
+                .cloned()
+                .collect();
+            if !due_pairs.is_empty() {
+                info!("👁️ Cambios detectados en {} pair(s) en modo continuo", due_pairs.len());
+                // Lo que el pre-hook de cada pair escribe en su origen lo copia esta misma ejecución
+                let hooks_finished = Mutex::new(Vec::new());
+                let on_pre_hook = |pair_id: &str| {
+                    if let Ok(mut finished) = hooks_finished.lock() {
+                        finished.push((pair_id.to_string(), Instant::now()));
+                    }
+                };
+                BackupRunner::new(&app_config, RunTrigger::Watch)
+                    .with_events(&events)
+                    .with_running_flag(&running)
+                    .with_pre_hook_listener(&on_pre_hook)
+                    .run(&due_pairs);
+                for (pair_id, at) in hooks_finished.into_inner().unwrap_or_else(|e| e.into_inner()) {
+                    watcher.pre_hook_finished(&pair_id, at);
                 }
             }
-            
-            info!("🏁 Backup automático #{} finalizado: {} éxito, {} advertencias, {} fallos", 
-                 iteration, total_success, total_warnings, total_failures);
         }
         
-        // Sleep hasta el próximo backup (o hasta que se detenga)
-        info!("😴 Próximo backup automático en {} segundos", interval);
+        // Publicar las próximas ejecuciones solo cuando cambian
+        let next_runs = scheduler.next_runs();
+        if published.as_ref() != Some(&next_runs) {
+            if let Some(next) = next_runs.values().min() {
+                info!("😴 Próximo backup automático: {}", next.format("%Y-%m-%d %H:%M:%S"));
+            }
+            events.publish(AppEvent::NextRunsChanged(next_runs.clone()));
+            published = Some(next_runs);
+        }
+        
+        if !running.load(Ordering::Relaxed) {
+            break;
+        }
         
-        // Sleep en chunks para poder responder rápido al stop
-        let sleep_chunks = interval.max(1); // Evitar división por 0
-        let chunk_size = if sleep_chunks > 60 { 60 } else { 1 }; // Chunks de máximo 60 segundos
-        let chunks = sleep_chunks / chunk_size;
+        // Dormir hasta la próxima ejecución (o revisar seguido si hay watches activos)
+        let mut wait = scheduler.earliest_next_run()
+            .map(|next| (next - Local::now()).to_std().unwrap_or_default())
+            .unwrap_or(MAX_IDLE_WAIT)
+            .min(MAX_IDLE_WAIT);
+        if watcher.has_watches() {
+            wait = wait.min(WATCH_POLL_INTERVAL);
+        }
         
-        for chunk in 0..chunks {
-            if !running.load(Ordering::Relaxed) {
-                info!("🛑 Daemon stop signal received during sleep - exiting");
-                return;
+        clock_jumps.start_wait();
+        match control.recv_timeout(wait) {
+            Ok(DaemonControl::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+            Ok(DaemonControl::RunNow) => {
+                info!("⚡ Ejecución inmediata solicitada");
+                scheduler.run_all_now();
             }
-            
-            debug!("😴 Sleep chunk {}/{} ({}s)", chunk + 1, chunks, chunk_size);
-            std::thread::sleep(Duration::from_secs(chunk_size));
+            Ok(DaemonControl::Reload) => {
+                info!("🔄 Configuración del daemon recargada");
+            }
+            Err(RecvTimeoutError::Timeout) => {}
         }
         
-        // Sleep del resto si no es exactamente divisible
-        let remainder = sleep_chunks % chunk_size;
-        if remainder > 0 && running.load(Ordering::Relaxed) {
-            std::thread::sleep(Duration::from_secs(remainder));
+        // Si la espera tardó mucho más (suspensión) o el reloj cambió, reprogramar
+        if let Some(jump) = clock_jumps.check(wait) {
+            warn!("⏰ Salto de reloj de {}s detectado (suspensión o cambio de hora) - reprogramando", jump);
+            scheduler.reschedule_after_clock_jump();
         }
     }
     
+    // Sin daemon no hay próximas ejecuciones
+    events.publish(AppEvent::NextRunsChanged(HashMap::new()));
+    
     info!("🏁 Daemon task terminado - loop finalizado");
-} 
\ No newline at end of file
+}
@@ -0,0 +1,134 @@
+//! Módulo de eventos - bus publish/subscribe tipado
+//! Runner, daemon y configuración publican; UI, notificaciones, tray e historial se suscriben
+//! cada uno por su cuenta sin que los productores los conozcan
+
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::error;
+
+use crate::core::backup::BackupResult;
+use crate::core::config::BackupEngine;
+use crate::core::progress::TransferProgress;
+use crate::core::retry::AttemptRecord;
+use crate::core::runner::{RunSummary, RunTrigger};
+
+/// Eventos de la aplicación
+#[derive(Debug, Clone)]
+pub enum AppEvent {
+    /// Comenzó una ejecución (manual, daemon, modo continuo o CLI) con estos pairs en cola
+    /// `run_id` es único en el proceso: distingue ejecuciones simultáneas del mismo origen
+    RunStarted { run_id: u64, trigger: RunTrigger, pair_ids: Vec<String> },
+    /// Avance de un pair dentro de una ejecución
+    Progress { pair_id: String, progress: PairProgress },
+    /// Resultado final del pair; `error` describe el fallo cuando el resultado es `Failed`
+    /// `engine` es None si el motor no llegó a ejecutarse (pre-hook fallido u origen sin cambios)
+    PairFinished {
+        pair_id: String,
+        trigger: RunTrigger,
+        result: BackupResult,
+        error: Option<String>,
+        engine: Option<BackupEngine>,
+        /// Tiempo del pair completo (hooks y reintentos incluidos)
+        duration: Duration,
+    },
+    RunFinished { run_id: u64, trigger: RunTrigger, summary: RunSummary },
+    /// La configuración guardada cambió (el daemon debe reprogramar)
+    ConfigChanged,
+    DaemonStateChanged { running: bool },
+    /// Próxima ejecución programada de cada pair (key = pair id); vacío si el daemon se detuvo
+    NextRunsChanged(HashMap<String, DateTime<Local>>),
+    /// La app se cierra: los suscriptores guardan lo que tengan pendiente
+    Shutdown,
+}
+
+/// Etapas de un pair en curso
+#[derive(Debug, Clone)]
+pub enum PairProgress {
+    Started,
+    /// Terminó un intento (con o sin reintento pendiente)
+    Attempt(AttemptRecord),
+    /// Avance de la copia: pendiente según el pre-escaneo (al arrancar la ejecución) y luego en vivo
+    Transfer(TransferProgress),
+}
+
+type Subscriber = Arc<dyn Fn(&AppEvent) + Send + Sync>;
+
+/// Bus de eventos compartible entre threads (clonar comparte los suscriptores)
+#[derive(Clone, Default)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recibir todos los eventos publicados a partir de ahora
+    pub fn subscribe(&self, subscriber: impl Fn(&AppEvent) + Send + Sync + 'static) {
+        match self.subscribers.lock() {
+            Ok(mut subscribers) => subscribers.push(Arc::new(subscriber)),
+            Err(e) => error!("❌ Error registrando suscriptor de eventos: {}", e),
+        }
+    }
+
+    /// Entregar un evento a cada suscriptor en el thread que publica
+    pub fn publish(&self, event: AppEvent) {
+        // Copiar la lista para que un suscriptor pueda publicar o suscribirse sin deadlock
+        let subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers.clone(),
+            Err(e) => {
+                error!("❌ Error accediendo suscriptores de eventos: {}", e);
+                return;
+            }
+        };
+
+        for subscriber in subscribers {
+            subscriber(&event);
+        }
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.subscribers.lock().map(|subscribers| subscribers.len()).unwrap_or(0);
+        f.debug_struct("EventBus").field("subscribers", &count).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_independent_subscribers() {
+        let bus = EventBus::new();
+        let configs = Arc::new(AtomicUsize::new(0));
+        let daemon_states = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&configs);
+        bus.subscribe(move |event| {
+            if matches!(event, AppEvent::ConfigChanged) {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        // Un suscriptor puede publicar otros eventos (el daemon reacciona a ConfigChanged)
+        let republish = bus.clone();
+        let counter = Arc::clone(&daemon_states);
+        bus.subscribe(move |event| match event {
+            AppEvent::ConfigChanged => republish.publish(AppEvent::DaemonStateChanged { running: true }),
+            AppEvent::DaemonStateChanged { .. } => {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        });
+
+        bus.publish(AppEvent::ConfigChanged);
+        assert_eq!(configs.load(Ordering::Relaxed), 1);
+        assert_eq!(daemon_states.load(Ordering::Relaxed), 1);
+    }
+}
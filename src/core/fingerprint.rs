@@ -0,0 +1,238 @@
+//! Módulo de fingerprints - detecta si el origen de un pair cambió desde el último backup
+//! Permite omitir pairs sin cambios y no despertar discos dormidos con robocopy
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::info;
+
+use crate::core::config::BackupPair;
+use crate::core::pair_state::PairStateStore;
+
+/// Archivo donde se persisten los fingerprints (junto a config.json)
+const FINGERPRINTS_FILE: &str = "fingerprints.json";
+
+/// Resumen barato del árbol de origen
+/// Los cambios de nombre se detectan por el hash de rutas y por el mtime de las carpetas
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SourceFingerprint {
+    pub file_count: u64,
+    pub total_size: u64,
+    /// mtime más reciente (segundos Unix) entre archivos y carpetas
+    pub max_mtime: u64,
+    /// Hash FNV-1a de (ruta relativa, tamaño, mtime) en orden determinista
+    pub tree_hash: u64,
+}
+
+/// Fingerprint registrado tras el último backup exitoso de un pair
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct StoredFingerprint {
+    /// Si el destino cambia (pair editado) el fingerprint deja de valer
+    destination: PathBuf,
+    /// Identidad de la carpeta destino al registrarlo (otro disco o una carpeta recreada no coinciden)
+    #[serde(default)]
+    destination_marker: Option<u64>,
+    fingerprint: SourceFingerprint,
+}
+
+/// Fingerprints persistidos por backup pair (key = pair id)
+#[derive(Debug)]
+pub struct FingerprintStore {
+    entries: PairStateStore<StoredFingerprint>,
+}
+
+impl FingerprintStore {
+    /// Cargar fingerprints guardados (vacío si no existen o están corruptos)
+    pub fn load() -> Self {
+        Self {
+            entries: PairStateStore::load(FINGERPRINTS_FILE),
+        }
+    }
+
+    /// Verificar si el origen del pair sigue igual que en el último backup exitoso
+    /// y si el destino sigue siendo la misma carpeta con la copia (discos rotativos, destino borrado)
+    pub fn is_unchanged(&self, pair: &BackupPair, current: &SourceFingerprint) -> bool {
+        let Some(stored) = self.entries.get(&pair.id) else {
+            return false;
+        };
+        if stored.destination != pair.destination || stored.fingerprint != *current {
+            return false;
+        }
+
+        let marker = destination_marker(&pair.destination);
+        if marker.is_none() || marker != stored.destination_marker {
+            info!("💽 Destino de {} ausente o reemplazado - se copia aunque el origen no cambió", pair.display_name());
+            return false;
+        }
+        // Un destino vaciado conserva su identidad: con archivos en origen debe tener contenido
+        current.file_count == 0 || fs::read_dir(&pair.destination).is_ok_and(|mut entries| entries.next().is_some())
+    }
+
+    /// Registrar el fingerprint tomado antes de un backup exitoso
+    pub fn record(&self, pair: &BackupPair, fingerprint: SourceFingerprint) {
+        self.entries.insert(&pair.id, StoredFingerprint {
+            destination: pair.destination.clone(),
+            destination_marker: destination_marker(&pair.destination),
+            fingerprint,
+        });
+    }
+
+    /// Tamaño del origen de cada pair en su último backup exitoso (datos protegidos)
+    pub fn source_sizes(&self) -> HashMap<String, u64> {
+        self.entries
+            .snapshot()
+            .into_iter()
+            .map(|(pair_id, stored)| (pair_id, stored.fingerprint.total_size))
+            .collect()
+    }
+
+    /// Guardar a disco conservando solo los pairs de `keep` (`pair_state::retained_pair_ids`)
+    pub fn save(&self, keep: &HashSet<String>) -> Result<()> {
+        self.entries.save(keep)
+    }
+}
+
+/// Calcular el fingerprint actual del origen de un pair
+pub fn compute_fingerprint(source: &Path) -> Result<SourceFingerprint> {
+    let mut fingerprint = SourceFingerprint {
+        file_count: 0,
+        total_size: 0,
+        max_mtime: 0,
+        tree_hash: FNV_OFFSET,
+    };
+
+    scan_dir(source, Path::new(""), &mut fingerprint)
+        .with_context(|| format!("Error analizando origen: {}", source.display()))?;
+
+    info!("🔎 Fingerprint de {}: {} archivos, {} bytes",
+         source.display(), fingerprint.file_count, fingerprint.total_size);
+    Ok(fingerprint)
+}
+
+fn scan_dir(dir: &Path, relative: &Path, fingerprint: &mut SourceFingerprint) -> Result<()> {
+    let dir_meta = fs::metadata(dir)?;
+    fingerprint.max_mtime = fingerprint.max_mtime.max(mtime_secs(&dir_meta));
+
+    // Orden determinista para que el hash no dependa del orden del sistema de archivos
+    let mut entries: Vec<_> = fs::read_dir(dir)?.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name();
+        let relative_path = relative.join(&name);
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+
+        if meta.is_dir() {
+            fnv_update(&mut fingerprint.tree_hash, relative_path.to_string_lossy().as_bytes());
+            scan_dir(&entry.path(), &relative_path, fingerprint)?;
+        } else if meta.is_file() {
+            let mtime = mtime_secs(&meta);
+            fingerprint.file_count += 1;
+            fingerprint.total_size += meta.len();
+            fingerprint.max_mtime = fingerprint.max_mtime.max(mtime);
+
+            fnv_update(&mut fingerprint.tree_hash, relative_path.to_string_lossy().as_bytes());
+            fnv_update(&mut fingerprint.tree_hash, &meta.len().to_le_bytes());
+            fnv_update(&mut fingerprint.tree_hash, &mtime.to_le_bytes());
+        }
+    }
+
+    Ok(())
+}
+
+/// Identidad barata de la carpeta destino: fecha de creación (y dispositivo e inodo en Unix)
+/// None si no existe o no es una carpeta
+fn destination_marker(destination: &Path) -> Option<u64> {
+    let meta = fs::metadata(destination).ok().filter(|meta| meta.is_dir())?;
+    let mut hash = FNV_OFFSET;
+    if let Some(created) = meta.created().ok().and_then(|time| time.duration_since(UNIX_EPOCH).ok()) {
+        fnv_update(&mut hash, &created.as_nanos().to_le_bytes());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        fnv_update(&mut hash, &meta.dev().to_le_bytes());
+        fnv_update(&mut hash, &meta.ino().to_le_bytes());
+    }
+    Some(hash)
+}
+
+fn mtime_secs(meta: &fs::Metadata) -> u64 {
+    meta.modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+// FNV-1a 64 bits: estable entre versiones (a diferencia de DefaultHasher)
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+fn fnv_update(hash: &mut u64, bytes: &[u8]) {
+    for byte in bytes {
+        *hash ^= *byte as u64;
+        *hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    // Separador para que ("ab","c") y ("a","bc") no colisionen
+    *hash ^= 0xff;
+    *hash = hash.wrapping_mul(FNV_PRIME);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_detects_changes() {
+        let dir = std::env::temp_dir().join(format!("rustyvault-fp-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("a.txt"), b"hola").unwrap();
+        fs::write(dir.join("sub").join("b.txt"), b"mundo").unwrap();
+
+        let first = compute_fingerprint(&dir).unwrap();
+        assert_eq!(first.file_count, 2);
+        assert_eq!(first.total_size, 9);
+        assert_eq!(compute_fingerprint(&dir).unwrap(), first);
+
+        // Renombrar mantiene tamaño y cantidad, pero cambia el hash
+        fs::rename(dir.join("a.txt"), dir.join("c.txt")).unwrap();
+        assert_ne!(compute_fingerprint(&dir).unwrap(), first);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_replaced_or_emptied_destination_is_not_skipped() {
+        let root = std::env::temp_dir().join(format!("rustyvault-fp-dest-{}", uuid::Uuid::new_v4()));
+        let (source, destination) = (root.join("src"), root.join("dst"));
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(&destination).unwrap();
+        fs::write(source.join("a.txt"), b"hola").unwrap();
+        fs::write(destination.join("a.txt"), b"hola").unwrap();
+
+        let pair = BackupPair::with_id("pair-1".to_string(), source.clone(), destination.clone());
+        let store = FingerprintStore { entries: PairStateStore::at(FINGERPRINTS_FILE, None) };
+        let fingerprint = compute_fingerprint(&source).unwrap();
+        store.record(&pair, fingerprint.clone());
+        assert!(store.is_unchanged(&pair, &fingerprint));
+
+        // Destino vaciado: misma carpeta pero sin la copia
+        fs::remove_file(destination.join("a.txt")).unwrap();
+        assert!(!store.is_unchanged(&pair, &fingerprint));
+
+        // Disco cambiado o destino borrado: la carpeta ya no es la registrada
+        fs::remove_dir_all(&destination).unwrap();
+        assert!(!store.is_unchanged(&pair, &fingerprint));
+        fs::create_dir_all(&destination).unwrap();
+        fs::write(destination.join("a.txt"), b"hola").unwrap();
+        assert!(!store.is_unchanged(&pair, &fingerprint));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
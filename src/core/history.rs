@@ -0,0 +1,583 @@
+//! Módulo de historial - registro persistente de cada ejecución por pair
+//! Append-only JSONL junto a config.json; se alimenta del bus de eventos y se relee al arrancar
+//! Las omisiones por origen sin cambios no ocupan una línea cada una: se cuentan en el siguiente registro
+//! del pair o, si el origen sigue sin cambios, en un registro agregado que se escribe como mucho cada
+//! `SKIP_FLUSH_INTERVAL` y al cerrar la app. Al superar `MAX_HISTORY_BYTES` se descartan los registros más antiguos
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+use crate::core::backup::{BackupResult, SkipReason};
+use crate::core::config::{data_file_path, BackupEngine};
+use crate::core::events::{AppEvent, EventBus, PairProgress};
+use crate::core::persistence::write_atomic;
+use crate::core::runner::{throughput_mb_s, RunTrigger};
+
+/// Archivo del historial (una ejecución de pair por línea)
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// Tamaño máximo del historial; al superarlo se conserva la mitad más reciente
+const MAX_HISTORY_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Antigüedad de las omisiones acumuladas a partir de la cual se escriben al terminar una ejecución
+const SKIP_FLUSH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Resultado resumido de una ejecución
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Success,
+    Warning,
+    Skipped,
+    Failed,
+}
+
+/// Una ejecución de un backup pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub pair_id: String,
+    pub trigger: RunTrigger,
+    /// Unix timestamps de inicio y fin
+    pub started_at: i64,
+    pub finished_at: i64,
+    /// Duración medida por el runner (los registros antiguos solo tienen los timestamps)
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    pub outcome: RunOutcome,
+    #[serde(default)]
+    pub files_copied: u32,
+    #[serde(default)]
+    pub bytes_transferred: u64,
+    /// Intentos realizados (0 si el motor no llegó a ejecutarse)
+    #[serde(default)]
+    pub attempts: u32,
+    /// Advertencia, motivo de omisión o error
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Motor que copió (None si el pair se abortó u omitió antes de copiar)
+    #[serde(default)]
+    pub engine: Option<BackupEngine>,
+    /// Omisiones por origen sin cambios desde el registro anterior del pair (no tienen línea propia)
+    /// En un registro `Skipped` agregado son las anteriores a la que representa el propio registro
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub unchanged_skips: u32,
+}
+
+impl RunRecord {
+    pub fn duration(&self) -> Duration {
+        self.duration_ms
+            .map(Duration::from_millis)
+            .unwrap_or_else(|| Duration::from_secs((self.finished_at - self.started_at).max(0) as u64))
+    }
+
+    pub fn throughput_mb_s(&self) -> Option<f64> {
+        throughput_mb_s(self.bytes_transferred, self.duration())
+    }
+
+    /// Ejecuciones que llegaron a copiar (los pairs omitidos no cuentan para promedios ni tasas)
+    pub fn counts_as_execution(&self) -> bool {
+        self.outcome != RunOutcome::Skipped
+    }
+}
+
+/// Totales y promedios de un conjunto de ejecuciones (para el panel de estadísticas)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryStats {
+    /// Ejecuciones sin contar las omitidas
+    pub runs: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub skipped: usize,
+    pub total_files: u64,
+    pub total_bytes: u64,
+    pub avg_duration_seconds: Option<f64>,
+    /// Velocidad media de las ejecuciones exitosas
+    pub avg_throughput_mb_s: Option<f64>,
+    pub last_run: Option<i64>,
+}
+
+impl HistoryStats {
+    pub fn from_records<'a>(records: impl IntoIterator<Item = &'a RunRecord>) -> Self {
+        let mut stats = Self::default();
+        let mut total_duration = Duration::ZERO;
+        let mut copy_duration = Duration::ZERO;
+        let mut copy_bytes = 0;
+
+        for record in records {
+            stats.skipped += record.unchanged_skips as usize;
+            if !record.counts_as_execution() {
+                stats.skipped += 1;
+                continue;
+            }
+            stats.runs += 1;
+            total_duration += record.duration();
+            stats.total_files += record.files_copied as u64;
+            stats.total_bytes += record.bytes_transferred;
+            stats.last_run = stats.last_run.max(Some(record.finished_at));
+            match record.outcome {
+                RunOutcome::Failed => stats.failures += 1,
+                RunOutcome::Success => {
+                    stats.successes += 1;
+                    copy_duration += record.duration();
+                    copy_bytes += record.bytes_transferred;
+                }
+                _ => stats.successes += 1,
+            }
+        }
+
+        if stats.runs > 0 {
+            stats.avg_duration_seconds = Some(total_duration.as_secs_f64() / stats.runs as f64);
+        }
+        stats.avg_throughput_mb_s = throughput_mb_s(copy_bytes, copy_duration);
+        stats
+    }
+
+    /// Porcentaje de ejecuciones exitosas (con advertencias cuentan como éxito)
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.runs > 0).then(|| self.successes as f64 * 100.0 / self.runs as f64)
+    }
+}
+
+/// Pair en curso: inicio e intentos hasta que llegue su resultado
+#[derive(Debug, Default)]
+struct PendingRun {
+    started_at: i64,
+    attempts: u32,
+}
+
+/// Omisiones sin cambios de un pair que todavía no se escribieron
+#[derive(Debug)]
+struct PendingSkips {
+    count: u32,
+    first_at: i64,
+    last_at: i64,
+    trigger: RunTrigger,
+    duration: Duration,
+}
+
+impl PendingSkips {
+    /// Registro agregado: abarca desde la primera omisión hasta la última
+    fn into_record(self, pair_id: &str) -> RunRecord {
+        RunRecord {
+            pair_id: pair_id.to_string(),
+            trigger: self.trigger,
+            started_at: self.first_at,
+            finished_at: self.last_at,
+            duration_ms: Some(self.duration.as_millis() as u64),
+            outcome: RunOutcome::Skipped,
+            files_copied: 0,
+            bytes_transferred: 0,
+            attempts: 0,
+            message: Some(SkipReason::NoChanges.describe().to_string()),
+            engine: None,
+            unchanged_skips: self.count - 1,
+        }
+    }
+}
+
+/// Historial persistente de ejecuciones
+#[derive(Debug)]
+pub struct HistoryStore {
+    path: Option<PathBuf>,
+    pending: Mutex<HashMap<String, PendingRun>>,
+    /// Omisiones sin cambios de cada pair que todavía no se escribieron
+    unchanged_skips: Mutex<HashMap<String, PendingSkips>>,
+    /// Serializa las escrituras de pairs que terminan en paralelo
+    write_lock: Mutex<()>,
+    max_bytes: u64,
+    skip_flush_interval: Duration,
+}
+
+impl HistoryStore {
+    /// Historial junto a config.json
+    pub fn open() -> Self {
+        Self::with_path(data_file_path(HISTORY_FILE).ok())
+    }
+
+    fn with_path(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            pending: Mutex::new(HashMap::new()),
+            unchanged_skips: Mutex::new(HashMap::new()),
+            write_lock: Mutex::new(()),
+            max_bytes: MAX_HISTORY_BYTES,
+            skip_flush_interval: SKIP_FLUSH_INTERVAL,
+        }
+    }
+
+    /// Registrar en disco cada pair que termina en una ejecución publicada en el bus
+    pub fn subscribe(events: &EventBus) {
+        let store = Arc::new(Self::open());
+        events.subscribe(move |event| store.handle_event(event));
+    }
+
+    fn handle_event(&self, event: &AppEvent) {
+        match event {
+            AppEvent::Progress { pair_id, progress } => {
+                let Ok(mut pending) = self.pending.lock() else { return };
+                match progress {
+                    PairProgress::Started => {
+                        pending.insert(pair_id.clone(), PendingRun { started_at: unix_now(), attempts: 0 });
+                    }
+                    PairProgress::Attempt(attempt) => {
+                        pending.entry(pair_id.clone()).or_default().attempts = attempt.attempt;
+                    }
+                    PairProgress::Transfer(_) => {}
+                }
+            }
+            AppEvent::PairFinished { pair_id, trigger, result, error, engine, duration } => {
+                let run = self.pending
+                    .lock()
+                    .ok()
+                    .and_then(|mut pending| pending.remove(pair_id))
+                    .unwrap_or_default();
+                let finished_at = unix_now();
+
+                // Sin cambios: solo se cuenta (el daemon omite cada pair sin cambios en cada ejecución)
+                if matches!(result, BackupResult::Skipped(SkipReason::NoChanges)) {
+                    if let Ok(mut skips) = self.unchanged_skips.lock() {
+                        let skip = skips.entry(pair_id.clone()).or_insert(PendingSkips {
+                            count: 0,
+                            first_at: finished_at,
+                            last_at: finished_at,
+                            trigger: *trigger,
+                            duration: *duration,
+                        });
+                        skip.count += 1;
+                        skip.last_at = finished_at;
+                        skip.trigger = *trigger;
+                        skip.duration = *duration;
+                    }
+                    return;
+                }
+                let unchanged_skips = self.unchanged_skips
+                    .lock()
+                    .ok()
+                    .and_then(|mut skips| skips.remove(pair_id))
+                    .map_or(0, |skip| skip.count);
+
+                let (outcome, files_copied, bytes_transferred, message) = match result {
+                    BackupResult::Success { files_copied, bytes_transferred } => {
+                        (RunOutcome::Success, *files_copied, *bytes_transferred, None)
+                    }
+                    BackupResult::Warning { message, files_copied, bytes_transferred } => {
+                        (RunOutcome::Warning, *files_copied, *bytes_transferred, Some(message.clone()))
+                    }
+                    BackupResult::Skipped(reason) => (RunOutcome::Skipped, 0, 0, Some(reason.describe().to_string())),
+                    BackupResult::Failed => (RunOutcome::Failed, 0, 0, error.clone()),
+                };
+
+                let record = RunRecord {
+                    pair_id: pair_id.clone(),
+                    trigger: *trigger,
+                    started_at: if run.started_at > 0 { run.started_at } else { finished_at - duration.as_secs() as i64 },
+                    finished_at,
+                    duration_ms: Some(duration.as_millis() as u64),
+                    outcome,
+                    files_copied,
+                    bytes_transferred,
+                    attempts: run.attempts,
+                    message,
+                    engine: *engine,
+                    unchanged_skips,
+                };
+                if let Err(e) = self.append(&record) {
+                    warn!("⚠️ Error guardando historial de ejecuciones: {}", e);
+                }
+            }
+            // Un origen que nunca cambia también deja rastro, sin una línea por ejecución
+            AppEvent::RunFinished { .. } => self.flush_unchanged_skips(Some(self.skip_flush_interval)),
+            AppEvent::Shutdown => self.flush_unchanged_skips(None),
+            _ => {}
+        }
+    }
+
+    /// Escribir las omisiones acumuladas como un registro por pair
+    /// Con `older_than` solo las de pairs cuya primera omisión pendiente tiene al menos esa antigüedad
+    fn flush_unchanged_skips(&self, older_than: Option<Duration>) {
+        let due: Vec<(String, PendingSkips)> = {
+            let Ok(mut skips) = self.unchanged_skips.lock() else { return };
+            let cutoff = older_than.map(|age| unix_now() - age.as_secs() as i64);
+            let due_ids: Vec<String> = skips
+                .iter()
+                .filter(|(_, skip)| cutoff.is_none_or(|cutoff| skip.first_at <= cutoff))
+                .map(|(pair_id, _)| pair_id.clone())
+                .collect();
+            due_ids
+                .into_iter()
+                .filter_map(|pair_id| skips.remove(&pair_id).map(|skip| (pair_id, skip)))
+                .collect()
+        };
+
+        for (pair_id, skip) in due {
+            if let Err(e) = self.append(&skip.into_record(&pair_id)) {
+                warn!("⚠️ Error guardando omisiones sin cambios en el historial: {}", e);
+            }
+        }
+    }
+
+    /// Agregar una ejecución al final del archivo
+    pub fn append(&self, record: &RunRecord) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut line = serde_json::to_string(record).context("Error serializando ejecución")?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Error abriendo {}", path.display()))?;
+        // Una escritura cortada a la mitad no debe arrastrar al registro siguiente
+        if !ends_with_newline(&mut file)? {
+            line.insert(0, '\n');
+        }
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("Error escribiendo {}", path.display()))?;
+        debug!("📝 Ejecución de pair {} registrada en historial", record.pair_id);
+
+        if file.metadata().map(|meta| meta.len()).unwrap_or(0) > self.max_bytes {
+            drop(file);
+            self.rotate(path)?;
+        }
+        Ok(())
+    }
+
+    /// Conservar los registros más recientes que entren en la mitad del tamaño máximo
+    fn rotate(&self, path: &Path) -> Result<()> {
+        let content = fs::read_to_string(path).with_context(|| format!("Error leyendo {}", path.display()))?;
+        let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+
+        let budget = self.max_bytes / 2;
+        let mut kept_bytes = 0;
+        let mut first = lines.len();
+        while first > 0 && kept_bytes + (lines[first - 1].len() as u64) < budget {
+            first -= 1;
+            kept_bytes += lines[first].len() as u64 + 1;
+        }
+
+        let mut rotated = lines[first..].join("\n");
+        rotated.push('\n');
+        write_atomic(path, rotated.as_bytes())?;
+        info!("🗂️ Historial rotado: {} registro(s) antiguos descartados", first);
+        Ok(())
+    }
+
+    /// Leer todas las ejecuciones en orden cronológico (las líneas corruptas se ignoran)
+    pub fn load(&self) -> Vec<RunRecord> {
+        let Some(path) = self.path.as_ref().filter(|path| path.exists()) else {
+            return Vec::new();
+        };
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("⚠️ Error leyendo {}: {}", path.display(), e);
+                return Vec::new();
+            }
+        };
+
+        let mut invalid = 0;
+        let records: Vec<RunRecord> = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).map_err(|_| invalid += 1).ok())
+            .collect();
+        if invalid > 0 {
+            warn!("⚠️ {} línea(s) inválidas en {} - ignoradas", invalid, HISTORY_FILE);
+        }
+        records
+    }
+
+    /// Ejecuciones agrupadas por pair (cada lista en orden cronológico)
+    pub fn load_by_pair(&self) -> HashMap<String, Vec<RunRecord>> {
+        let mut by_pair: HashMap<String, Vec<RunRecord>> = HashMap::new();
+        for record in self.load() {
+            by_pair.entry(record.pair_id.clone()).or_default().push(record);
+        }
+        by_pair
+    }
+}
+
+/// true si el archivo está vacío o su último byte es un salto de línea
+fn ends_with_newline(file: &mut fs::File) -> Result<bool> {
+    if file.metadata()?.len() == 0 {
+        return Ok(true);
+    }
+    let mut last = [0u8; 1];
+    file.seek(SeekFrom::End(-1))?;
+    file.read_exact(&mut last)?;
+    Ok(last[0] == b'\n')
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::runner::RunSummary;
+
+    #[test]
+    fn test_append_and_load_skips_corrupt_lines() {
+        let path = std::env::temp_dir().join(format!("rustyvault_history_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let store = HistoryStore::with_path(Some(path.clone()));
+
+        let record = RunRecord {
+            pair_id: "pair-1".to_string(),
+            trigger: RunTrigger::Schedule(3),
+            started_at: 100,
+            finished_at: 160,
+            duration_ms: None,
+            outcome: RunOutcome::Success,
+            files_copied: 12,
+            bytes_transferred: 4096,
+            attempts: 1,
+            message: None,
+            engine: Some(BackupEngine::Native),
+            unchanged_skips: 0,
+        };
+        store.append(&record).unwrap();
+        // Línea truncada por un corte de luz a mitad de escritura
+        fs::write(&path, format!("{}{{\"pair_id\":", fs::read_to_string(&path).unwrap())).unwrap();
+        store.append(&RunRecord { outcome: RunOutcome::Failed, ..record.clone() }).unwrap();
+
+        let by_pair = store.load_by_pair();
+        let _ = fs::remove_file(&path);
+        let runs = &by_pair["pair-1"];
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0], record);
+        assert_eq!(runs[1].outcome, RunOutcome::Failed);
+
+        let stats = HistoryStats::from_records(runs);
+        assert_eq!(stats.runs, 2);
+        assert_eq!(stats.success_rate(), Some(50.0));
+        assert_eq!(stats.avg_duration_seconds, Some(60.0));
+        assert_eq!(stats.total_bytes, 8192);
+        // 4096 bytes en 60s (solo la ejecución exitosa cuenta para la velocidad)
+        assert_eq!(stats.avg_throughput_mb_s, Some(4096.0 / (1024.0 * 1024.0) / 60.0));
+    }
+
+    #[test]
+    fn test_warning_run_keeps_copied_totals() {
+        let path = std::env::temp_dir().join(format!("rustyvault_history_warning_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let store = HistoryStore::with_path(Some(path.clone()));
+
+        store.handle_event(&AppEvent::PairFinished {
+            pair_id: "pair-1".to_string(),
+            trigger: RunTrigger::Manual,
+            result: BackupResult::Warning {
+                message: "Some mismatched files/dirs".to_string(),
+                files_copied: 7,
+                bytes_transferred: 2048,
+            },
+            error: None,
+            engine: None,
+            duration: Duration::from_secs(2),
+        });
+
+        let by_pair = store.load_by_pair();
+        let _ = fs::remove_file(&path);
+        let run = &by_pair["pair-1"][0];
+        assert_eq!(run.outcome, RunOutcome::Warning);
+        assert_eq!((run.files_copied, run.bytes_transferred), (7, 2048));
+    }
+
+    #[test]
+    fn test_unchanged_skips_are_counted_and_history_rotates() {
+        let path = std::env::temp_dir().join(format!("rustyvault_history_rotate_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut store = HistoryStore::with_path(Some(path.clone()));
+        store.max_bytes = 2048;
+        let finished = |result: BackupResult| AppEvent::PairFinished {
+            pair_id: "pair-1".to_string(),
+            trigger: RunTrigger::Schedule(0),
+            result,
+            error: None,
+            engine: None,
+            duration: Duration::from_secs(1),
+        };
+
+        // Tres ticks sin cambios no escriben nada: se cuentan en el siguiente registro
+        for _ in 0..3 {
+            store.handle_event(&finished(BackupResult::Skipped(SkipReason::NoChanges)));
+        }
+        assert!(store.load().is_empty());
+        store.handle_event(&finished(BackupResult::Success { files_copied: 1, bytes_transferred: 10 }));
+        let records = store.load();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].unchanged_skips, 3);
+        assert_eq!(HistoryStats::from_records(&records).skipped, 3);
+
+        // Superado el máximo se conservan solo los registros más recientes
+        for _ in 0..40 {
+            store.handle_event(&finished(BackupResult::Failed));
+        }
+        let size = fs::metadata(&path).unwrap().len();
+        let records = store.load();
+        let _ = fs::remove_file(&path);
+        assert!(size <= 2048);
+        assert!(records.len() < 41);
+        assert_eq!(records.last().unwrap().outcome, RunOutcome::Failed);
+        assert!(records.iter().all(|record| record.unchanged_skips == 0));
+    }
+
+    #[test]
+    fn test_unchanged_skips_are_flushed_without_a_later_copy() {
+        let path = std::env::temp_dir().join(format!("rustyvault_history_flush_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut store = HistoryStore::with_path(Some(path.clone()));
+        let skipped = AppEvent::PairFinished {
+            pair_id: "pair-1".to_string(),
+            trigger: RunTrigger::Schedule(0),
+            result: BackupResult::Skipped(SkipReason::NoChanges),
+            error: None,
+            engine: None,
+            duration: Duration::from_secs(1),
+        };
+        let run_finished = AppEvent::RunFinished { run_id: 1, trigger: RunTrigger::Schedule(0), summary: RunSummary::default() };
+
+        // Omisiones recientes: el fin de la ejecución todavía no escribe nada
+        store.handle_event(&skipped);
+        store.handle_event(&run_finished);
+        assert!(store.load().is_empty());
+
+        // Al cerrar la app se escriben en un único registro agregado
+        store.handle_event(&skipped);
+        store.handle_event(&AppEvent::Shutdown);
+        let records = store.load();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].outcome, RunOutcome::Skipped);
+        assert_eq!(HistoryStats::from_records(&records).skipped, 2);
+
+        // Pasado el intervalo, el fin de una ejecución también las escribe
+        store.skip_flush_interval = Duration::ZERO;
+        store.handle_event(&skipped);
+        store.handle_event(&run_finished);
+        let records = store.load();
+        let _ = fs::remove_file(&path);
+        assert_eq!(records.len(), 2);
+        assert_eq!(HistoryStats::from_records(&records).skipped, 3);
+    }
+}
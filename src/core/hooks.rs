@@ -0,0 +1,316 @@
+//! Módulo de hooks - comandos externos antes y después de cada backup
+//! Ej: volcar una base de datos antes de copiarla, expulsar un USB al terminar
+
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::core::backup::BackupResult;
+use crate::core::config::{BackupPair, RunHooks};
+
+/// Ejecutar el pre-hook de un pair; un error significa que el pair debe abortarse
+pub fn run_pair_pre_hook(pair: &BackupPair) -> Result<()> {
+    let Some(hooks) = &pair.hooks else {
+        return Ok(());
+    };
+    let Some(command) = hooks.pre_run.as_deref().filter(|cmd| !cmd.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    info!("🪝 Ejecutando pre-hook del pair {}", pair.id);
+    run_hook_command("pre", command, &pair_env(pair, None), hooks.timeout())
+}
+
+/// Ejecutar el post-hook de un pair con el resultado del backup
+fn run_pair_post_hook(pair: &BackupPair, result: &BackupResult) -> Result<()> {
+    let Some(hooks) = &pair.hooks else {
+        return Ok(());
+    };
+    let Some(command) = hooks.post_run.as_deref().filter(|cmd| !cmd.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    info!("🪝 Ejecutando post-hook del pair {}", pair.id);
+    run_hook_command("post", command, &pair_env(pair, Some(result)), hooks.timeout())
+}
+
+/// Ejecutar el post-hook del pair y combinar su resultado con el del backup
+/// Un post-hook fallido no invalida la copia, pero la marca como advertencia
+pub fn apply_pair_post_hook(pair: &BackupPair, result: BackupResult) -> BackupResult {
+    let Err(e) = run_pair_post_hook(pair, &result) else {
+        return result;
+    };
+
+    warn!("⚠️ Post-hook del pair {} falló: {}", pair.id, e);
+    match result {
+        BackupResult::Success { files_copied, bytes_transferred } => BackupResult::Warning {
+            message: format!("Post-hook falló: {}", e),
+            files_copied,
+            bytes_transferred,
+        },
+        BackupResult::Skipped(_) => BackupResult::Warning {
+            message: format!("Post-hook falló: {}", e),
+            files_copied: 0,
+            bytes_transferred: 0,
+        },
+        BackupResult::Warning { message, files_copied, bytes_transferred } => BackupResult::Warning {
+            message: format!("{} (post-hook falló: {})", message, e),
+            files_copied,
+            bytes_transferred,
+        },
+        BackupResult::Failed => BackupResult::Failed,
+    }
+}
+
+/// Ejecutar el pre-hook global (una vez antes de toda la ejecución)
+pub fn run_global_pre_hook(hooks: &RunHooks, pair_count: usize) -> Result<()> {
+    let Some(command) = hooks.pre_run.as_deref().filter(|cmd| !cmd.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    info!("🪝 Ejecutando pre-hook global");
+    let env = vec![("RUSTYVAULT_PAIR_COUNT", pair_count.to_string())];
+    run_hook_command("global-pre", command, &env, hooks.timeout())
+}
+
+/// Ejecutar el post-hook global con el resumen de la ejecución
+pub fn run_global_post_hook(hooks: &RunHooks, success: usize, warnings: usize, failures: usize) -> Result<()> {
+    let Some(command) = hooks.post_run.as_deref().filter(|cmd| !cmd.trim().is_empty()) else {
+        return Ok(());
+    };
+
+    let result = if failures > 0 {
+        "failed"
+    } else if warnings > 0 {
+        "warning"
+    } else {
+        "success"
+    };
+
+    info!("🪝 Ejecutando post-hook global");
+    let env = vec![
+        ("RUSTYVAULT_RESULT", result.to_string()),
+        ("RUSTYVAULT_SUCCESS_COUNT", success.to_string()),
+        ("RUSTYVAULT_WARNING_COUNT", warnings.to_string()),
+        ("RUSTYVAULT_FAILED_COUNT", failures.to_string()),
+    ];
+    run_hook_command("global-post", command, &env, hooks.timeout())
+}
+
+/// Variables de entorno de contexto para los hooks de un pair
+fn pair_env(pair: &BackupPair, result: Option<&BackupResult>) -> Vec<(&'static str, String)> {
+    let mut env = vec![
+        ("RUSTYVAULT_PAIR_ID", pair.id.clone()),
+        ("RUSTYVAULT_SOURCE", pair.source.display().to_string()),
+        ("RUSTYVAULT_DESTINATION", pair.destination.display().to_string()),
+    ];
+
+    match result {
+        Some(BackupResult::Success { files_copied, bytes_transferred }) => {
+            env.push(("RUSTYVAULT_RESULT", "success".to_string()));
+            env.push(("RUSTYVAULT_FILES_COPIED", files_copied.to_string()));
+            env.push(("RUSTYVAULT_BYTES_TRANSFERRED", bytes_transferred.to_string()));
+        }
+        Some(BackupResult::Warning { message, files_copied, bytes_transferred }) => {
+            env.push(("RUSTYVAULT_RESULT", "warning".to_string()));
+            env.push(("RUSTYVAULT_MESSAGE", message.clone()));
+            env.push(("RUSTYVAULT_FILES_COPIED", files_copied.to_string()));
+            env.push(("RUSTYVAULT_BYTES_TRANSFERRED", bytes_transferred.to_string()));
+        }
+        Some(BackupResult::Failed) => {
+            env.push(("RUSTYVAULT_RESULT", "failed".to_string()));
+        }
+        Some(BackupResult::Skipped(_)) => {
+            env.push(("RUSTYVAULT_RESULT", "skipped".to_string()));
+        }
+        None => {}
+    }
+
+    env
+}
+
+/// Ejecutar un comando de shell con timeout, registrando su salida en el log
+fn run_hook_command(label: &str, command: &str, env: &[(&str, String)], timeout: Duration) -> Result<()> {
+    let mut shell = shell_command(command);
+    shell
+        .envs(env.iter().map(|(key, value)| (*key, value.as_str())))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Solo en Windows: ocultar la consola del hook
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        shell.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    // En Unix el hook lidera su propio grupo: al vencer el timeout se termina el grupo entero
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        shell.process_group(0);
+    }
+
+    let mut child = shell
+        .spawn()
+        .with_context(|| format!("No se pudo ejecutar hook '{}'", command))?;
+
+    // La salida se registra a medida que llega: un hook colgado deja en el log lo que alcanzó a imprimir
+    let stdout = spawn_reader(child.stdout.take(), label, false);
+    let stderr = spawn_reader(child.stderr.take(), label, true);
+
+    let status = wait_with_timeout(&mut child, timeout)?;
+    join_reader(stdout);
+    join_reader(stderr);
+
+    let Some(status) = status else {
+        bail!("hook '{}' excedió el timeout de {}s", command, timeout.as_secs());
+    };
+
+    if !status.success() {
+        match status.code() {
+            Some(code) => bail!("hook '{}' terminó con código {}", command, code),
+            None => bail!("hook '{}' terminó por una señal", command),
+        }
+    }
+
+    Ok(())
+}
+
+/// Espera máxima por los lectores de salida tras terminar el hook (un proceso suelto puede
+/// mantener los pipes abiertos)
+const READER_GRACE: Duration = Duration::from_secs(2);
+
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+/// Esperar al proceso hasta `timeout`; si se excede se mata y devuelve None
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<Option<std::process::ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            warn!("⏱️ Hook excedió el timeout - terminando proceso");
+            kill_tree(child);
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Terminar el hook y todo lo que lanzó: `sh -c`/`cmd /C` solo envuelven al comando real
+fn kill_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        let _ = Command::new("kill")
+            .args(["-KILL", "--"])
+            .arg(format!("-{}", child.id()))
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .creation_flags(CREATE_NO_WINDOW)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+type OutputReader = Option<std::thread::JoinHandle<()>>;
+
+/// Registrar un pipe línea a línea en un thread aparte (el proceso no se bloquea al llenarlo)
+fn spawn_reader<R: Read + Send + 'static>(pipe: Option<R>, label: &str, is_stderr: bool) -> OutputReader {
+    let label = label.to_string();
+    pipe.map(|pipe| {
+        std::thread::spawn(move || {
+            for line in BufReader::new(pipe).split(b'\n').map_while(Result::ok) {
+                let line = String::from_utf8_lossy(&line);
+                let line = line.trim_end();
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if is_stderr {
+                    warn!("🪝 [{}] {}", label, line);
+                } else {
+                    info!("🪝 [{}] {}", label, line);
+                }
+            }
+        })
+    })
+}
+
+/// Esperar al lector hasta `READER_GRACE`; si sigue abierto se abandona (el thread termina solo)
+fn join_reader(reader: OutputReader) {
+    let Some(handle) = reader else {
+        return;
+    };
+    let deadline = Instant::now() + READER_GRACE;
+    while !handle.is_finished() {
+        if Instant::now() >= deadline {
+            debug!("🪝 Salida del hook todavía abierta - se deja de esperar");
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let _ = handle.join();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_receives_env_and_reports_failures() {
+        let pair = BackupPair::new("/tmp/src", "/tmp/dst");
+        let env = pair_env(&pair, Some(&BackupResult::Failed));
+        let timeout = Duration::from_secs(5);
+
+        let check = format!("test \"$RUSTYVAULT_PAIR_ID\" = \"{}\" && test \"$RUSTYVAULT_RESULT\" = failed", pair.id);
+        assert!(run_hook_command("post", &check, &env, timeout).is_ok());
+        assert!(run_hook_command("post", "exit 3", &env, timeout).is_err());
+        assert!(run_hook_command("post", "sleep 5", &env, Duration::from_millis(200)).is_err());
+    }
+
+    #[test]
+    fn test_timeout_kills_the_whole_hook() {
+        let marker = std::env::temp_dir().join(format!("rustyvault_hook_marker_{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+
+        // El proceso real es un nieto del shell: el timeout también debe terminarlo
+        let command = format!("echo antes; (sleep 1; touch '{}') & wait", marker.display());
+        let started = Instant::now();
+        assert!(run_hook_command("pre", &command, &[], Duration::from_millis(300)).is_err());
+        assert!(started.elapsed() < READER_GRACE + Duration::from_secs(1));
+
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+}
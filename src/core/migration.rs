@@ -0,0 +1,183 @@
+//! Módulo de migración - actualiza config.json de cualquier versión anterior a la actual
+//! Cada paso sube exactamente una versión trabajando sobre el JSON crudo, así los campos
+//! que ya no existen en `AppConfig` todavía pueden leerse antes de descartarlos
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Versión del esquema que escribe esta build
+/// - 0: un solo backup (`source_folder`/`destination_folder`), sin versión
+/// - 1: lista `backup_pairs` (v2.0), sin versión
+/// - 2: campo `config_version` y prioridades explícitas en cada pair
+pub const CURRENT_CONFIG_VERSION: u32 = 2;
+
+/// Un paso de la cadena: convierte la versión `from` en `from + 1`
+struct MigrationStep {
+    from: u32,
+    description: &'static str,
+    apply: fn(&mut Map<String, Value>) -> Result<()>,
+}
+
+/// Pasos en orden; agregar uno nuevo aquí al subir `CURRENT_CONFIG_VERSION`
+const MIGRATIONS: &[MigrationStep] = &[
+    MigrationStep {
+        from: 0,
+        description: "backup único → lista de backup pairs",
+        apply: migrate_v0_to_v1,
+    },
+    MigrationStep {
+        from: 1,
+        description: "versión explícita y prioridades por orden",
+        apply: migrate_v1_to_v2,
+    },
+];
+
+/// Versión de un config.json crudo (los archivos anteriores a v2 se reconocen por su forma)
+pub fn detect_version(raw: &Value) -> u32 {
+    if let Some(version) = raw.get("config_version").and_then(Value::as_u64) {
+        return version as u32;
+    }
+
+    let has_pairs = raw
+        .get("backup_pairs")
+        .and_then(Value::as_array)
+        .is_some_and(|pairs| !pairs.is_empty());
+    let has_legacy_folder = raw
+        .get("source_folder")
+        .and_then(Value::as_str)
+        .is_some_and(|folder| !folder.is_empty());
+
+    if !has_pairs && has_legacy_folder {
+        0
+    } else {
+        1
+    }
+}
+
+/// Aplicar los pasos pendientes hasta la versión actual
+/// Devuelve el JSON migrado y la versión de la que partió
+pub fn migrate(mut raw: Value) -> Result<(Value, u32)> {
+    let original = detect_version(&raw);
+    if original > CURRENT_CONFIG_VERSION {
+        warn!("⚠️ config.json es de una versión más nueva ({} > {}) - se carga sin migrar y en solo lectura",
+              original, CURRENT_CONFIG_VERSION);
+        return Ok((raw, original));
+    }
+
+    let Some(object) = raw.as_object_mut() else {
+        bail!("config.json no contiene un objeto JSON");
+    };
+
+    for step in MIGRATIONS.iter().filter(|step| step.from >= original) {
+        info!("🔄 Migrando config.json v{} → v{}: {}", step.from, step.from + 1, step.description);
+        (step.apply)(object)
+            .with_context(|| format!("Error migrando config.json desde v{}", step.from))?;
+        object.insert("config_version".to_string(), json!(step.from + 1));
+    }
+
+    Ok((raw, original))
+}
+
+/// Copiar el archivo original antes de migrarlo (`config.v1.bak.json` junto a config.json)
+pub fn backup_before_migration(config_path: &Path, version: u32) -> Result<PathBuf> {
+    let backup_path = config_path.with_file_name(format!("config.v{}.bak.json", version));
+    fs::copy(config_path, &backup_path)
+        .with_context(|| format!("Error respaldando config.json en {}", backup_path.display()))?;
+    info!("💾 Copia de config.json previa a la migración: {}", backup_path.display());
+    Ok(backup_path)
+}
+
+/// v0 → v1: el backup único pasa a ser el primer pair
+fn migrate_v0_to_v1(config: &mut Map<String, Value>) -> Result<()> {
+    let take_folder = |config: &mut Map<String, Value>, key: &str| {
+        config
+            .insert(key.to_string(), json!(""))
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default()
+    };
+    let source = take_folder(config, "source_folder");
+    let destination = take_folder(config, "destination_folder");
+
+    let pairs = config
+        .entry("backup_pairs")
+        .or_insert_with(|| json!([]))
+        .as_array_mut()
+        .context("backup_pairs no es una lista")?;
+    if pairs.is_empty() && !source.is_empty() && !destination.is_empty() {
+        pairs.push(json!({
+            "id": uuid::Uuid::new_v4().to_string(),
+            "source": source,
+            "destination": destination,
+            "enabled": true,
+            "priority": 0,
+        }));
+    }
+    Ok(())
+}
+
+/// v1 → v2: las prioridades se numeran según el orden de la lista
+/// v1 ejecutaba en el orden de la lista e ignoraba `priority` (que podía tener valores sueltos
+/// como el `"priority": 1` del README), así que se renumeran siempre para conservar ese orden
+fn migrate_v1_to_v2(config: &mut Map<String, Value>) -> Result<()> {
+    let Some(pairs) = config.get_mut("backup_pairs").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+
+    for (index, pair) in pairs.iter_mut().enumerate() {
+        if let Some(pair) = pair.as_object_mut() {
+            pair.insert("priority".to_string(), json!(index + 1));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::AppConfig;
+
+    /// Un config.json real de cada versión publicada
+    const FIXTURES: &[(u32, &str)] = &[
+        (0, include_str!("../../tests/fixtures/config_v0.json")),
+        (1, include_str!("../../tests/fixtures/config_v1.json")),
+        // Prioridades sueltas que v1 ignoraba: el orden de la lista debe mantenerse
+        (1, include_str!("../../tests/fixtures/config_v1_mixed_priority.json")),
+        (2, include_str!("../../tests/fixtures/config_v2.json")),
+    ];
+
+    #[test]
+    fn test_fixtures_from_every_version_load() {
+        for (version, content) in FIXTURES {
+            let (config, migrated_from) = AppConfig::from_json(content)
+                .unwrap_or_else(|e| panic!("config v{} no carga: {:#}", version, e));
+            assert_eq!(migrated_from, (*version < CURRENT_CONFIG_VERSION).then_some(*version));
+            assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+            let expected_pairs = if *version == 0 { 1 } else { 2 };
+            assert_eq!(config.backup_pairs.len(), expected_pairs, "v{}", version);
+            assert!(config.source_folder.is_empty());
+
+            let priorities: Vec<_> = config.backup_pairs.iter().map(|pair| pair.priority).collect();
+            assert!(priorities.iter().enumerate().all(|(i, priority)| *priority == i + 1), "v{}: {:?}", version, priorities);
+            assert_eq!(config.backup_pairs[0].destination.to_string_lossy(), "D:\\Backup\\Documents");
+        }
+    }
+
+    #[test]
+    fn test_newer_config_is_read_only() {
+        let mut raw: Value = serde_json::from_str(include_str!("../../tests/fixtures/config_v2.json")).unwrap();
+        raw["config_version"] = json!(CURRENT_CONFIG_VERSION + 1);
+        raw["future_option"] = json!(true);
+
+        let (config, migrated_from) = AppConfig::from_json(&raw.to_string()).unwrap();
+        assert_eq!(migrated_from, None);
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION + 1);
+
+        // No se reescribe (se perdería `future_option`) y la validación lo bloquea
+        assert!(config.ensure_writable().is_err());
+        assert!(config.validate().blocking().any(|issue| issue.subject == "Versión"));
+        assert!(AppConfig::default().ensure_writable().is_ok());
+    }
+}
@@ -1,7 +1,30 @@
 pub mod config;
+pub mod migration;
+pub mod persistence;
+pub mod config_exchange;
+pub mod config_watch;
+pub mod paths;
+pub mod profiles;
+pub mod path_vars;
+pub mod preferences;
 pub mod backup;
 pub mod daemon;
 pub mod path_validation;
+pub mod validation;
+pub mod parallel;
+pub mod throttle;
+pub mod native;
+pub mod retry;
+pub mod hooks;
+pub mod pair_state;
+pub mod fingerprint;
+pub mod watcher;
+pub mod schedule;
+pub mod catch_up;
+pub mod events;
+pub mod history;
+pub mod runner;
+pub mod progress;
 
 pub use config::*;
 pub use path_validation::*;
\ No newline at end of file
@@ -0,0 +1,391 @@
+//! Motor de copia nativo - alternativa a robocopy escrita en Rust
+//! Copia solo archivos nuevos o modificados (tamaño/fecha) y opcionalmente espeja borrados
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, warn};
+
+use crate::core::backup::BackupResult;
+use crate::core::progress::ProgressTracker;
+use crate::core::throttle::Throttle;
+
+/// Tamaño de bloque para copiar (igual al de robocopy con /IPG)
+const COPY_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Tolerancia de fechas para discos FAT32/exFAT (equivalente a /FFT)
+const FAT_TIME_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// Estadísticas acumuladas durante una copia nativa
+#[derive(Debug, Default)]
+struct NativeCopyStats {
+    files_copied: u32,
+    bytes_transferred: u64,
+    files_deleted: u32,
+    errors: u32,
+    /// Carpetas del origen que no se pudieron listar completas: el espejo no borra nada debajo
+    unreadable_dirs: HashSet<PathBuf>,
+}
+
+/// Ejecutar backup con el motor nativo
+/// Si `running` pasa a false (daemon detenido) la copia se corta entre bloques y termina con error
+pub fn execute_native_backup(
+    source: &Path,
+    destination: &Path,
+    mirror_mode: bool,
+    throttle: &Throttle,
+    progress: &ProgressTracker,
+    running: Option<&AtomicBool>,
+) -> Result<BackupResult> {
+    info!("🦀 Ejecutando copia nativa: {} -> {}", source.display(), destination.display());
+
+    if throttle.is_limited() {
+        debug!("🐢 Copia nativa con límite de ancho de banda");
+    }
+
+    let mut stats = NativeCopyStats::default();
+    copy_tree(source, destination, throttle, progress, running, &mut stats)?;
+    ensure_running(running)?;
+
+    if mirror_mode {
+        let unreadable_dirs = std::mem::take(&mut stats.unreadable_dirs);
+        remove_extras(source, destination, &unreadable_dirs, &mut stats);
+    }
+
+    info!("✅ Copia nativa terminada: {} archivos, {} bytes, {} eliminados, {} errores",
+         stats.files_copied, stats.bytes_transferred, stats.files_deleted, stats.errors);
+
+    if stats.errors > 0 {
+        return Ok(BackupResult::Warning {
+            message: format!("{} archivos no pudieron copiarse", stats.errors),
+            files_copied: stats.files_copied,
+            bytes_transferred: stats.bytes_transferred,
+        });
+    }
+
+    Ok(BackupResult::Success {
+        files_copied: stats.files_copied,
+        bytes_transferred: stats.bytes_transferred,
+    })
+}
+
+/// Copiar recursivamente los archivos nuevos o modificados
+fn copy_tree(
+    source: &Path,
+    destination: &Path,
+    throttle: &Throttle,
+    progress: &ProgressTracker,
+    running: Option<&AtomicBool>,
+    stats: &mut NativeCopyStats,
+) -> Result<()> {
+    fs::create_dir_all(destination)
+        .with_context(|| format!("Error creando carpeta destino: {}", destination.display()))?;
+
+    let entries = fs::read_dir(source)
+        .with_context(|| format!("Error leyendo carpeta origen: {}", source.display()))?;
+
+    for entry in entries {
+        ensure_running(running)?;
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!("⚠️ Error listando {}: {}", source.display(), e);
+                stats.errors += 1;
+                stats.unreadable_dirs.insert(source.to_path_buf());
+                continue;
+            }
+        };
+        let src_path = entry.path();
+        let dest_path = destination.join(entry.file_name());
+
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(e) => {
+                warn!("⚠️ No se pudo leer tipo de {}: {}", src_path.display(), e);
+                stats.errors += 1;
+                stats.unreadable_dirs.insert(src_path);
+                continue;
+            }
+        };
+
+        if file_type.is_dir() {
+            if let Err(e) = copy_tree(&src_path, &dest_path, throttle, progress, running, stats) {
+                ensure_running(running)?;
+                warn!("⚠️ Error copiando carpeta {}: {}", src_path.display(), e);
+                stats.errors += 1;
+                stats.unreadable_dirs.insert(src_path);
+            }
+        } else if file_type.is_file() {
+            match needs_copy(&src_path, &dest_path) {
+                Ok(false) => {}
+                Ok(true) => match copy_file(&src_path, &dest_path, throttle, progress, running) {
+                    Ok(bytes) => {
+                        progress.add_file();
+                        stats.files_copied += 1;
+                        stats.bytes_transferred += bytes;
+                    }
+                    Err(e) => {
+                        ensure_running(running)?;
+                        warn!("⚠️ Error copiando {}: {}", src_path.display(), e);
+                        stats.errors += 1;
+                    }
+                },
+                Err(e) => {
+                    warn!("⚠️ Error comparando {}: {}", src_path.display(), e);
+                    stats.errors += 1;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Un archivo se copia si no existe en destino o si cambió tamaño/fecha
+pub(crate) fn needs_copy(src: &Path, dest: &Path) -> Result<bool> {
+    let src_meta = fs::metadata(src)?;
+    let dest_meta = match fs::metadata(dest) {
+        Ok(meta) => meta,
+        Err(_) => return Ok(true),
+    };
+
+    if src_meta.len() != dest_meta.len() {
+        return Ok(true);
+    }
+
+    let src_time = src_meta.modified()?;
+    let dest_time = dest_meta.modified()?;
+    let diff = match src_time.duration_since(dest_time) {
+        Ok(diff) => diff,
+        Err(e) => e.duration(),
+    };
+
+    Ok(diff > FAT_TIME_TOLERANCE)
+}
+
+/// Copiar un archivo por bloques aplicando el throttle, preservando la fecha de modificación
+/// Se escribe en un temporal junto al destino y solo al completarse reemplaza la copia anterior
+fn copy_file(src: &Path, dest: &Path, throttle: &Throttle, progress: &ProgressTracker, running: Option<&AtomicBool>) -> Result<u64> {
+    let temp_path = temp_sibling(dest);
+    let result = copy_into(src, &temp_path, throttle, progress, running)
+        .and_then(|total| {
+            fs::rename(&temp_path, dest)
+                .with_context(|| format!("Error reemplazando {}", dest.display()))?;
+            Ok(total)
+        });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn copy_into(src: &Path, temp_path: &Path, throttle: &Throttle, progress: &ProgressTracker, running: Option<&AtomicBool>) -> Result<u64> {
+    let mut reader = fs::File::open(src)?;
+    let mut writer = fs::File::create(temp_path)?;
+    let mut buffer = vec![0u8; COPY_CHUNK_BYTES];
+    let mut total = 0u64;
+
+    loop {
+        ensure_running(running)?;
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+        throttle.consume(read as u64);
+        progress.add_bytes(read as u64);
+    }
+
+    writer.flush()?;
+
+    let modified = fs::metadata(src)?.modified().unwrap_or_else(|_| SystemTime::now());
+    writer.set_modified(modified)?;
+    writer.sync_all()?;
+
+    Ok(total)
+}
+
+/// Cortar la copia si el daemon se detuvo (un archivo a medias queda en su temporal y se descarta)
+fn ensure_running(running: Option<&AtomicBool>) -> Result<()> {
+    if running.is_some_and(|running| !running.load(Ordering::Relaxed)) {
+        bail!("Copia cancelada: daemon detenido");
+    }
+    Ok(())
+}
+
+/// Temporal oculto en la misma carpeta (mismo disco, el rename es atómico): `.nombre.rustyvault-tmp`
+fn temp_sibling(dest: &Path) -> PathBuf {
+    let name = dest.file_name().unwrap_or_default().to_string_lossy();
+    dest.with_file_name(format!(".{}.rustyvault-tmp", name))
+}
+
+/// Modo espejo: eliminar del destino lo que ya no existe en el origen
+/// Solo se borra lo que el origen confirma como inexistente; un error al consultarlo no cuenta
+fn remove_extras(source: &Path, destination: &Path, unreadable_dirs: &HashSet<PathBuf>, stats: &mut NativeCopyStats) {
+    if unreadable_dirs.contains(source) {
+        debug!("🛡️ Origen incompleto, no se espeja: {}", source.display());
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir(destination) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let dest_path = entry.path();
+        let src_path = source.join(entry.file_name());
+
+        let src_meta = match fs::symlink_metadata(&src_path) {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+                let result = if is_dir {
+                    fs::remove_dir_all(&dest_path)
+                } else {
+                    fs::remove_file(&dest_path)
+                };
+
+                match result {
+                    Ok(_) => {
+                        debug!("🗑️ Eliminado extra en destino: {}", dest_path.display());
+                        stats.files_deleted += 1;
+                    }
+                    Err(e) => {
+                        warn!("⚠️ No se pudo eliminar {}: {}", dest_path.display(), e);
+                        stats.errors += 1;
+                    }
+                }
+                continue;
+            }
+            Err(e) => {
+                warn!("⚠️ No se pudo consultar {} - se conserva en destino: {}", src_path.display(), e);
+                stats.errors += 1;
+                continue;
+            }
+        };
+
+        if src_meta.is_dir() && entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            remove_extras(&src_path, &dest_path, unreadable_dirs, stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rustyvault-native-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn run_mirror(source: &Path, destination: &Path) -> BackupResult {
+        execute_native_backup(source, destination, true, &Throttle::unlimited(), &ProgressTracker::new(), None).unwrap()
+    }
+
+    #[test]
+    fn test_mirror_removes_files_deleted_from_source() {
+        let root = temp_dir("mirror");
+        let (source, destination) = (root.join("src"), root.join("dst"));
+        fs::create_dir_all(source.join("sub")).unwrap();
+        fs::write(source.join("keep.txt"), b"keep").unwrap();
+        fs::write(source.join("sub").join("gone.txt"), b"gone").unwrap();
+        fs::write(source.join("old.txt"), b"old").unwrap();
+        run_mirror(&source, &destination);
+
+        fs::remove_file(source.join("sub").join("gone.txt")).unwrap();
+        fs::remove_file(source.join("old.txt")).unwrap();
+        run_mirror(&source, &destination);
+
+        let kept = fs::read(destination.join("keep.txt")).unwrap();
+        let gone_exists = destination.join("sub").join("gone.txt").exists();
+        let old_exists = destination.join("old.txt").exists();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(kept, b"keep");
+        assert!(!gone_exists);
+        assert!(!old_exists);
+    }
+
+    #[test]
+    fn test_mirror_keeps_backup_of_unreadable_source_folder() {
+        let root = temp_dir("unreadable");
+        let (source, destination) = (root.join("src"), root.join("dst"));
+        fs::create_dir_all(source.join("locked")).unwrap();
+        fs::write(source.join("locked").join("data.txt"), b"data").unwrap();
+        run_mirror(&source, &destination);
+
+        // La carpeta falló al leerse durante la copia: nada debajo de su copia se borra
+        let mut stats = NativeCopyStats::default();
+        let unreadable: HashSet<PathBuf> = [source.join("locked")].into_iter().collect();
+        fs::remove_file(source.join("locked").join("data.txt")).unwrap();
+        remove_extras(&source, &destination, &unreadable, &mut stats);
+        assert!(destination.join("locked").join("data.txt").exists());
+        assert_eq!(stats.files_deleted, 0);
+
+        // Y de punta a punta con permisos reales (sin efecto si los tests corren como root)
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let locked = source.join("locked");
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+            if fs::read_dir(&locked).is_err() {
+                let result = run_mirror(&source, &destination);
+                assert!(matches!(result, BackupResult::Warning { .. }));
+                assert!(destination.join("locked").join("data.txt").exists());
+            }
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_failed_copy_keeps_previous_destination() {
+        let root = temp_dir("failed");
+        let dest = root.join("backup.txt");
+        fs::write(&dest, b"copia anterior").unwrap();
+
+        // Una carpeta se abre pero no se puede leer como archivo: la copia falla a mitad
+        let unreadable_source = root.join("not-a-file");
+        fs::create_dir_all(&unreadable_source).unwrap();
+        let result = copy_file(&unreadable_source, &dest, &Throttle::unlimited(), &ProgressTracker::new(), None);
+
+        let contents = fs::read(&dest).unwrap();
+        let leftover_temp = temp_sibling(&dest).exists();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(result.is_err());
+        assert_eq!(contents, b"copia anterior");
+        assert!(!leftover_temp);
+    }
+
+    #[test]
+    fn test_stopped_daemon_cancels_copy() {
+        let root = temp_dir("cancel");
+        let (source, destination) = (root.join("src"), root.join("dst"));
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("big.bin"), vec![7u8; COPY_CHUNK_BYTES * 4]).unwrap();
+        fs::create_dir_all(&destination).unwrap();
+        fs::write(destination.join("big.bin"), b"copia anterior").unwrap();
+
+        // Daemon detenido: no se copia nada más y la copia anterior queda intacta
+        let running = AtomicBool::new(false);
+        let result = execute_native_backup(&source, &destination, true, &Throttle::unlimited(), &ProgressTracker::new(), Some(&running));
+
+        let contents = fs::read(destination.join("big.bin")).unwrap();
+        let leftover_temp = temp_sibling(&destination.join("big.bin")).exists();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(result.is_err());
+        assert_eq!(contents, b"copia anterior");
+        assert!(!leftover_temp);
+    }
+}
@@ -0,0 +1,150 @@
+//! Módulo de estado por pair - archivos JSON con un valor por pair id (fingerprints, últimas ejecuciones)
+//! Cada ejecución carga el archivo, registra sus pairs y al guardar lo combina con lo que haya en disco,
+//! así dos ejecuciones simultáneas no se pisan y los pairs de otros perfiles no se pierden
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::core::config::{data_file_path, BackupPair};
+use crate::core::persistence::write_atomic;
+use crate::core::profiles::ProfileStore;
+
+/// Serializa los guardados del proceso: cada ejecución relee el archivo y solo pisa sus propios pairs
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Valores persistidos por backup pair (key = pair id)
+#[derive(Debug)]
+pub struct PairStateStore<T> {
+    /// Nombre del archivo (para los logs)
+    file_name: &'static str,
+    path: Option<PathBuf>,
+    entries: Mutex<HashMap<String, T>>,
+    /// Pairs registrados por esta ejecución; al guardar se combinan con lo que haya en disco
+    recorded: Mutex<HashSet<String>>,
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> PairStateStore<T> {
+    /// Cargar `file_name` junto a config.json (vacío si no existe o está corrupto)
+    pub fn load(file_name: &'static str) -> Self {
+        Self::at(file_name, data_file_path(file_name).ok())
+    }
+
+    /// Store sobre `path` (None = solo en memoria, nunca se guarda)
+    pub(crate) fn at(file_name: &'static str, path: Option<PathBuf>) -> Self {
+        let entries = path.as_deref().and_then(|path| read_entries(file_name, path)).unwrap_or_default();
+        Self {
+            file_name,
+            path,
+            entries: Mutex::new(entries),
+            recorded: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Valor guardado para un pair
+    pub fn get(&self, pair_id: &str) -> Option<T> {
+        self.entries.lock().ok()?.get(pair_id).cloned()
+    }
+
+    /// Copia de todas las entradas
+    pub fn snapshot(&self) -> HashMap<String, T> {
+        self.entries.lock().map(|entries| entries.clone()).unwrap_or_default()
+    }
+
+    /// Registrar el valor de un pair en esta ejecución
+    pub fn insert(&self, pair_id: &str, value: T) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(pair_id.to_string(), value);
+        }
+        if let Ok(mut recorded) = self.recorded.lock() {
+            recorded.insert(pair_id.to_string());
+        }
+    }
+
+    /// Guardar a disco, descartando los pairs que no están en `keep` (ver `retained_pair_ids`)
+    pub fn save(&self, keep: &HashSet<String>) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let _guard = SAVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let json = {
+            let mut entries = self.entries
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Error accediendo {}: {}", self.file_name, e))?;
+            let recorded = self.recorded
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Error accediendo {}: {}", self.file_name, e))?;
+
+            // Otra ejecución pudo guardar mientras esta copiaba: se parte de lo que hay en disco
+            let mut merged: HashMap<String, T> = read_entries(self.file_name, path).unwrap_or_default();
+            for id in recorded.iter() {
+                if let Some(value) = entries.get(id) {
+                    merged.insert(id.clone(), value.clone());
+                }
+            }
+            merged.retain(|id, _| keep.contains(id));
+            *entries = merged;
+            serde_json::to_string_pretty(&*entries)
+                .with_context(|| format!("Error serializando {}", self.file_name))?
+        };
+
+        write_atomic(path, json.as_bytes())?;
+        debug!("💾 {} guardado en: {}", self.file_name, path.display());
+        Ok(())
+    }
+}
+
+/// Pairs cuyos datos se conservan al guardar: los de la ejecución y los de todos los perfiles
+/// (se calcula una vez por ejecución: lee cada archivo de perfil)
+pub fn retained_pair_ids(pairs: &[BackupPair]) -> HashSet<String> {
+    let mut keep = ProfileStore::open().referenced_pair_ids();
+    keep.extend(pairs.iter().map(|pair| pair.id.clone()));
+    keep
+}
+
+/// Leer el archivo (None si no existe o está corrupto)
+fn read_entries<T: DeserializeOwned>(file_name: &str, path: &Path) -> Option<HashMap<String, T>> {
+    if !path.exists() {
+        return None;
+    }
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| warn!("⚠️ {} inválido - se ignorará: {}", file_name, e))
+            .ok(),
+        Err(e) => {
+            warn!("⚠️ Error leyendo {}: {}", file_name, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrent_saves_merge_and_prune() {
+        let path = std::env::temp_dir().join(format!("rustyvault_pair_state_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let keep: HashSet<String> = ["a", "b"].iter().map(|id| id.to_string()).collect();
+
+        // Dos ejecuciones cargan el mismo archivo y registran pairs distintos
+        let first = PairStateStore::<i64>::at("test.json", Some(path.clone()));
+        let second = PairStateStore::<i64>::at("test.json", Some(path.clone()));
+        first.insert("a", 1);
+        first.insert("gone", 3);
+        second.insert("b", 2);
+        first.save(&keep).unwrap();
+        second.save(&keep).unwrap();
+
+        let stored = PairStateStore::<i64>::at("test.json", Some(path.clone())).snapshot();
+        let _ = fs::remove_file(&path);
+        assert_eq!(stored, HashMap::from([("a".to_string(), 1), ("b".to_string(), 2)]));
+    }
+}
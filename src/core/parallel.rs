@@ -0,0 +1,190 @@
+//! Módulo de ejecución paralela - corre backup pairs independientes en simultáneo
+//! Los pairs que comparten volumen destino se agrupan en una misma "lane" y se ejecutan en serie
+
+use std::collections::VecDeque;
+use std::path::{Component, Path};
+use std::sync::Mutex;
+
+use tracing::{debug, info};
+
+use crate::core::config::BackupPair;
+
+/// Ejecutar `job` sobre cada pair respetando el límite global de concurrencia
+///
+/// - Los pairs se agrupan por volumen destino (ver `destination_volume_key`)
+/// - Cada grupo se procesa secuencialmente en el orden recibido
+/// - Hasta `max_parallel` grupos se procesan al mismo tiempo
+///
+/// Devuelve los resultados en el mismo orden que `pairs`
+pub fn run_pairs_concurrently<R, F>(pairs: &[BackupPair], max_parallel: usize, job: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(usize, &BackupPair) -> R + Sync,
+{
+    if pairs.is_empty() {
+        return Vec::new();
+    }
+
+    let lanes = group_by_destination_volume(pairs);
+    let workers = worker_count(lanes.len(), max_parallel);
+
+    info!("⚡ Ejecutando {} pair(s) en {} grupo(s) de volumen con {} worker(s)",
+         pairs.len(), lanes.len(), workers);
+
+    let queue = Mutex::new(lanes.into_iter().collect::<VecDeque<_>>());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..pairs.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for worker in 0..workers {
+            let queue = &queue;
+            let results = &results;
+            let job = &job;
+
+            scope.spawn(move || loop {
+                // Tomar la siguiente lane completa; cada lane la procesa un único worker
+                let lane = match queue.lock() {
+                    Ok(mut queue) => queue.pop_front(),
+                    Err(_) => None,
+                };
+
+                let Some(lane) = lane else {
+                    debug!("🏁 Worker #{} sin más trabajo", worker + 1);
+                    break;
+                };
+
+                for index in lane {
+                    let result = job(index, &pairs[index]);
+                    if let Ok(mut results) = results.lock() {
+                        results[index] = Some(result);
+                    }
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Cuántas lanes copian a la vez en una ejecución con estos pairs
+pub fn concurrent_lanes(pairs: &[BackupPair], max_parallel: usize) -> usize {
+    worker_count(group_by_destination_volume(pairs).len(), max_parallel)
+}
+
+fn worker_count(lanes: usize, max_parallel: usize) -> usize {
+    max_parallel.max(1).min(lanes)
+}
+
+/// Agrupar índices de pairs por volumen destino conservando el orden original
+fn group_by_destination_volume(pairs: &[BackupPair]) -> Vec<Vec<usize>> {
+    let mut keys: Vec<String> = Vec::new();
+    let mut lanes: Vec<Vec<usize>> = Vec::new();
+
+    for (index, pair) in pairs.iter().enumerate() {
+        let key = destination_volume_key(&pair.destination);
+        match keys.iter().position(|existing| *existing == key) {
+            Some(lane) => lanes[lane].push(index),
+            None => {
+                keys.push(key);
+                lanes.push(vec![index]);
+            }
+        }
+    }
+
+    lanes
+}
+
+/// Clave que identifica el volumen de una ruta destino
+/// - `D:\Backup` → `d:`
+/// - `\\nas\share\docs` → `\\nas\share`
+/// - `/mnt/usb/backup` → `dev:2049` en Unix: dispositivo de la carpeta existente más cercana
+///   (el destino puede no existir todavía); `/mnt` si no se puede consultar ninguna
+pub fn destination_volume_key(path: &Path) -> String {
+    let path_str = path.to_string_lossy();
+
+    // Rutas UNC: servidor + recurso compartido identifican el volumen
+    if let Some(unc) = path_str.strip_prefix("\\\\") {
+        let mut parts = unc.split('\\').filter(|part| !part.is_empty());
+        let server = parts.next().unwrap_or_default();
+        let share = parts.next().unwrap_or_default();
+        return format!("\\\\{}\\{}", server, share).to_lowercase();
+    }
+
+    // Letra de unidad estilo Windows (válido también fuera de Windows)
+    let bytes = path_str.as_bytes();
+    if bytes.len() >= 2 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic() {
+        return path_str[..2].to_lowercase();
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if let Some(meta) = path.ancestors().find_map(|ancestor| std::fs::metadata(ancestor).ok()) {
+            return format!("dev:{}", meta.dev());
+        }
+    }
+
+    let mut components = path.components();
+    match components.next() {
+        Some(Component::Prefix(prefix)) => prefix.as_os_str().to_string_lossy().to_lowercase(),
+        Some(Component::RootDir) => match components.next() {
+            Some(Component::Normal(first)) => format!("/{}", first.to_string_lossy()),
+            _ => "/".to_string(),
+        },
+        Some(Component::Normal(first)) => first.to_string_lossy().to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_volume_keys() {
+        assert_eq!(destination_volume_key(Path::new("D:\\Backup\\Docs")), "d:");
+        assert_eq!(destination_volume_key(Path::new("d:\\Other")), "d:");
+        assert_eq!(destination_volume_key(Path::new("\\\\NAS\\Share\\docs")), "\\\\nas\\share");
+        #[cfg(not(unix))]
+        assert_eq!(destination_volume_key(Path::new("/mnt/usb/backup")), "/mnt");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unix_volume_key_follows_the_device() {
+        let temp = std::env::temp_dir();
+        let key = destination_volume_key(&temp);
+        assert!(key.starts_with("dev:"));
+        // Un destino que todavía no existe usa el volumen de su carpeta existente más cercana
+        assert_eq!(destination_volume_key(&temp.join("rustyvault-missing").join("backup")), key);
+        // Otro sistema de archivos montado es otro volumen aunque la ruta empiece igual
+        #[cfg(target_os = "linux")]
+        assert_ne!(destination_volume_key(Path::new("/proc/self")), destination_volume_key(Path::new("/")));
+    }
+
+    #[test]
+    fn test_pairs_sharing_volume_share_a_lane() {
+        let pairs = vec![
+            BackupPair::new("C:\\a", PathBuf::from("D:\\Backup\\a")),
+            BackupPair::new("C:\\b", PathBuf::from("\\\\nas\\share\\b")),
+            BackupPair::new("C:\\c", PathBuf::from("D:\\Backup\\c")),
+        ];
+
+        assert_eq!(group_by_destination_volume(&pairs), vec![vec![0, 2], vec![1]]);
+    }
+
+    #[test]
+    fn test_results_keep_input_order() {
+        let pairs: Vec<_> = (0..6)
+            .map(|i| BackupPair::new(format!("C:\\src{}", i), format!("{}:\\dst", (b'D' + i as u8) as char)))
+            .collect();
+
+        let results = run_pairs_concurrently(&pairs, 3, |index, _| index * 10);
+        assert_eq!(results, vec![0, 10, 20, 30, 40, 50]);
+    }
+}
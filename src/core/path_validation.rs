@@ -1,6 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::fs;
 use crate::core::config::BackupPair;
+use crate::core::path_vars;
 
 /// Resultado de validación de una ruta
 #[derive(Debug, Clone, PartialEq)]
@@ -79,12 +80,29 @@ impl PathValidator {
         existing_pairs: &[BackupPair],
         editing_index: Option<usize>
     ) -> BackupPairValidation {
-        let source_path = PathBuf::from(source);
-        let dest_path = PathBuf::from(destination);
-        
+        // Se valida la ruta expandida (`%USERPROFILE%`, `~`, `{Documents}`...) en esta máquina
+        let (raw_source, raw_destination) = (Path::new(source), Path::new(destination));
+        let source = path_vars::expand(raw_source);
+        let destination = path_vars::expand(raw_destination);
+        let source_result = match &source {
+            Ok(path) => Self::warn_undefined_variables(raw_source, Self::validate_source_path(path)),
+            Err(e) => PathValidationResult::Error(e.to_string()),
+        };
+        let destination_result = match &destination {
+            Ok(path) => Self::warn_undefined_variables(raw_destination, Self::validate_destination_path(path)),
+            Err(e) => PathValidationResult::Error(e.to_string()),
+        };
+        let (Ok(source_path), Ok(dest_path)) = (source, destination) else {
+            return BackupPairValidation {
+                source_result,
+                destination_result,
+                cross_validation_result: PathValidationResult::Valid,
+            };
+        };
+
         BackupPairValidation {
-            source_result: Self::validate_source_path(&source_path),
-            destination_result: Self::validate_destination_path(&dest_path),
+            source_result,
+            destination_result,
             cross_validation_result: Self::validate_cross_dependencies(
                 &source_path, 
                 &dest_path, 
@@ -94,7 +112,19 @@ impl PathValidator {
         }
     }
     
-    /// Validar ruta de origen
+    /// Una ruta válida con variables no definidas (que quedan literales) merece un aviso
+    fn warn_undefined_variables(raw: &Path, result: PathValidationResult) -> PathValidationResult {
+        let undefined = path_vars::undefined_variables(raw);
+        match result {
+            PathValidationResult::Valid if !undefined.is_empty() => PathValidationResult::Warning(format!(
+                "{} no está definida en esta máquina - se usa como texto literal",
+                undefined.join(", ")
+            )),
+            result => result,
+        }
+    }
+    
+        /// Validar ruta de origen
     fn validate_source_path(path: &Path) -> PathValidationResult {
         // 1. Verificar que no esté vacía
         if path.as_os_str().is_empty() {
@@ -210,6 +240,9 @@ impl PathValidator {
                 }
             }
             
+            // Comparar contra las rutas expandidas del pair existente
+            let existing_pair = existing_pair.resolved().unwrap_or_else(|_| existing_pair.clone());
+
             // Verificar duplicado exacto
             if existing_pair.source == source && existing_pair.destination == destination {
                 return PathValidationResult::Error("Ya existe un backup con estas mismas rutas".to_string());
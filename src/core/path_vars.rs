@@ -0,0 +1,175 @@
+//! Módulo de variables en rutas - una misma configuración para todas las máquinas del equipo
+//! Los pairs guardan la ruta tal como se escribió (`%USERPROFILE%\Documents`, `~/fotos`,
+//! `{Documents}`...) y se expande al momento de usarla: al ejecutar, vigilar y validar
+//!
+//! Soportado:
+//! - `%VAR%`, `$VAR` y `${VAR}`: variables de entorno; si no están definidas quedan como texto
+//!   (igual que cmd.exe), porque `D:\$Data` o `C:\$Recycle.Bin` son rutas reales
+//! - `~` al inicio: carpeta del usuario
+//! - `{Home}`, `{Documents}`, `{Desktop}`, `{Downloads}`, `{Pictures}`, `{Music}`, `{Videos}`,
+//!   `{AppData}`, `{LocalAppData}`: carpetas conocidas del sistema
+
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+/// Expandir las variables de una ruta de backup pair
+pub fn expand(raw: &Path) -> Result<PathBuf> {
+    expand_env(raw).map(|(path, _)| PathBuf::from(path))
+}
+
+/// Variables de la ruta que no están definidas en esta máquina (quedan literales al expandir)
+pub fn undefined_variables(raw: &Path) -> Vec<String> {
+    expand_env(raw).map(|(_, undefined)| undefined).unwrap_or_default()
+}
+
+fn expand_env(raw: &Path) -> Result<(String, Vec<String>)> {
+    expand_with(&raw.to_string_lossy(), |name| std::env::var(name).ok(), known_folder)
+}
+
+/// Ruta expandida para mostrar en la UI; None si no tiene variables
+pub fn preview(raw: &Path) -> Option<Result<PathBuf>> {
+    match expand(raw) {
+        Ok(resolved) if resolved == raw => None,
+        result => Some(result),
+    }
+}
+
+/// Carpeta conocida por nombre (sin distinguir mayúsculas)
+fn known_folder(name: &str) -> Option<PathBuf> {
+    match name.to_lowercase().as_str() {
+        "home" => dirs::home_dir(),
+        "documents" => dirs::document_dir(),
+        "desktop" => dirs::desktop_dir(),
+        "downloads" => dirs::download_dir(),
+        "pictures" => dirs::picture_dir(),
+        "music" => dirs::audio_dir(),
+        "videos" => dirs::video_dir(),
+        "appdata" => dirs::config_dir(),
+        "localappdata" => dirs::data_local_dir(),
+        _ => None,
+    }
+}
+
+/// Expansión con fuentes inyectables (entorno y carpetas conocidas) para poder testearla
+/// Devuelve la ruta y las variables no definidas (que quedaron tal como se escribieron)
+fn expand_with(
+    raw: &str,
+    env: impl Fn(&str) -> Option<String>,
+    folder: impl Fn(&str) -> Option<PathBuf>,
+) -> Result<(String, Vec<String>)> {
+    let mut out = String::with_capacity(raw.len());
+    let mut undefined = Vec::new();
+    let mut lookup = |name: &str, written: String, out: &mut String| match env(name) {
+        Some(value) => out.push_str(&value),
+        None => {
+            out.push_str(&written);
+            undefined.push(written);
+        }
+    };
+    let mut rest = raw;
+
+    // `~` solo al inicio y como componente completo (`~user` o `archivo~1` quedan igual)
+    if let Some(after) = rest.strip_prefix('~') {
+        if after.is_empty() || after.starts_with(['/', '\\']) {
+            let Some(home) = folder("home") else {
+                bail!("No se pudo determinar la carpeta del usuario para expandir '~'");
+            };
+            out.push_str(&home.to_string_lossy());
+            rest = after;
+        }
+    }
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        match c {
+            '%' => match after.split_once('%') {
+                // `%%` o un `%` sin cierre quedan literales
+                Some((name, tail)) if is_env_name(name) => {
+                    lookup(name, format!("%{}%", name), &mut out);
+                    rest = tail;
+                    continue;
+                }
+                _ => {}
+            },
+            '$' => {
+                if let Some((name, tail)) = after.strip_prefix('{').and_then(|inner| inner.split_once('}')) {
+                    if is_env_name(name) {
+                        lookup(name, format!("${{{}}}", name), &mut out);
+                        rest = tail;
+                        continue;
+                    }
+                } else {
+                    let len = after
+                        .char_indices()
+                        .find(|(i, ch)| !(ch.is_ascii_alphanumeric() || *ch == '_') || (*i == 0 && ch.is_ascii_digit()))
+                        .map_or(after.len(), |(i, _)| i);
+                    // `$` sin nombre (p. ej. recursos administrativos `\\server\C$`) queda literal
+                    if len > 0 {
+                        let name = &after[..len];
+                        lookup(name, format!("${}", name), &mut out);
+                        rest = &after[len..];
+                        continue;
+                    }
+                }
+            }
+            '{' => {
+                // Un `{Nombre}` que no es carpeta conocida queda literal (puede ser parte del nombre real)
+                if let Some((name, tail)) = after.split_once('}') {
+                    if let Some(path) = folder(name) {
+                        out.push_str(&path.to_string_lossy());
+                        rest = tail;
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        out.push(c);
+        rest = after;
+    }
+
+    Ok((out, undefined))
+}
+
+/// Nombre de variable de entorno razonable (Windows admite `ProgramFiles(x86)`)
+fn is_env_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['/', '\\', ' ', ':'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_variables_and_known_folders() {
+        let env = |name: &str| match name {
+            "USERNAME" => Some("ana".to_string()),
+            "BACKUP_ROOT" => Some("E:\\Backups".to_string()),
+            _ => None,
+        };
+        let folder = |name: &str| match name.to_lowercase().as_str() {
+            "home" => Some(PathBuf::from("C:\\Users\\ana")),
+            "documents" => Some(PathBuf::from("C:\\Users\\ana\\Documents")),
+            _ => None,
+        };
+        let expand = |raw: &str| expand_with(raw, env, folder).map(|(path, _)| path);
+
+        assert_eq!(expand("C:\\Users\\%USERNAME%\\Documents").unwrap(), "C:\\Users\\ana\\Documents");
+        assert_eq!(expand("$BACKUP_ROOT\\${USERNAME}").unwrap(), "E:\\Backups\\ana");
+        assert_eq!(expand("~\\fotos").unwrap(), "C:\\Users\\ana\\fotos");
+        assert_eq!(expand("{documents}\\work").unwrap(), "C:\\Users\\ana\\Documents\\work");
+
+        // Sin variables reconocibles la ruta no cambia
+        for literal in ["\\\\server\\C$\\data", "D:\\100%", "D:\\{draft}\\~old", "D:\\x%%y"] {
+            assert_eq!(expand(literal).unwrap(), literal);
+        }
+
+        // Variables no definidas quedan literales (como en cmd.exe) y se informan
+        for literal in ["D:\\$Data", "C:\\$Recycle.Bin", "a%b%c", "%NOPE%\\data"] {
+            assert_eq!(expand(literal).unwrap(), literal);
+        }
+        let (path, undefined) = expand_with("${NOPE}\\%USERNAME%", env, folder).unwrap();
+        assert_eq!(path, "${NOPE}\\ana");
+        assert_eq!(undefined, vec!["${NOPE}".to_string()]);
+    }
+}
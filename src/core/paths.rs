@@ -0,0 +1,170 @@
+//! Módulo de rutas - dónde viven config.json, los datos auxiliares y los logs
+//! Prioridad: `--config <ruta>` > `RUSTYVAULT_CONFIG` > modo portable (config.json junto al
+//! ejecutable) > modo instalado (carpetas del usuario: `%APPDATA%`, `$XDG_CONFIG_HOME`)
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Variable de entorno con la ruta de config.json
+pub const CONFIG_ENV_VAR: &str = "RUSTYVAULT_CONFIG";
+
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// Carpeta de la aplicación dentro de las carpetas del usuario
+const APP_DIR_NAME: &str = "RustyVault";
+
+static APP_PATHS: OnceLock<AppPaths> = OnceLock::new();
+
+/// Cómo se eligió la ubicación de config.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageMode {
+    /// `--config <ruta>`
+    CommandLine,
+    /// Variable `RUSTYVAULT_CONFIG`
+    Environment,
+    /// config.json junto al ejecutable (carpeta copiada a un USB, ZIP descomprimido...)
+    Portable,
+    /// Carpetas del usuario (ejecutable en Program Files o compartido entre usuarios)
+    Installed,
+}
+
+impl StorageMode {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StorageMode::CommandLine => "--config",
+            StorageMode::Environment => CONFIG_ENV_VAR,
+            StorageMode::Portable => "portable",
+            StorageMode::Installed => "instalado",
+        }
+    }
+}
+
+/// Rutas resueltas una vez al arrancar
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppPaths {
+    pub config_file: PathBuf,
+    pub log_dir: PathBuf,
+    pub mode: StorageMode,
+}
+
+/// Resolver las rutas con la ruta de `--config` (si se pasó); debe llamarse antes de cargar nada
+pub fn init(cli_config: Option<PathBuf>) -> &'static AppPaths {
+    APP_PATHS.get_or_init(|| {
+        let env_config = env::var_os(CONFIG_ENV_VAR)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+        let exe_dir = env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf));
+        AppPaths::resolve(cli_config, env_config, exe_dir, dirs::config_dir(), dirs::data_local_dir())
+    })
+}
+
+/// Rutas de esta ejecución
+pub fn current() -> &'static AppPaths {
+    init(None)
+}
+
+impl AppPaths {
+    fn resolve(
+        cli_config: Option<PathBuf>,
+        env_config: Option<PathBuf>,
+        exe_dir: Option<PathBuf>,
+        user_config_dir: Option<PathBuf>,
+        user_data_dir: Option<PathBuf>,
+    ) -> Self {
+        let explicit = cli_config
+            .map(|path| (path, StorageMode::CommandLine))
+            .or_else(|| env_config.map(|path| (path, StorageMode::Environment)));
+        if let Some((path, mode)) = explicit {
+            let config_file = explicit_config_file(path);
+            let log_dir = config_file.parent().map(Path::to_path_buf).unwrap_or_default();
+            return Self { config_file, log_dir, mode };
+        }
+
+        // Un config.json junto al ejecutable mantiene el comportamiento portable de siempre
+        if let Some(exe_dir) = &exe_dir {
+            let portable = exe_dir.join(CONFIG_FILE_NAME);
+            if portable.exists() {
+                return Self { config_file: portable, log_dir: exe_dir.clone(), mode: StorageMode::Portable };
+            }
+        }
+
+        if let Some(config_dir) = user_config_dir {
+            let app_dir = config_dir.join(APP_DIR_NAME);
+            let log_dir = user_data_dir
+                .map(|data_dir| data_dir.join(APP_DIR_NAME))
+                .unwrap_or_else(|| app_dir.clone())
+                .join("logs");
+            return Self { config_file: app_dir.join(CONFIG_FILE_NAME), log_dir, mode: StorageMode::Installed };
+        }
+
+        let dir = exe_dir.unwrap_or_else(|| PathBuf::from("."));
+        Self { config_file: dir.join(CONFIG_FILE_NAME), log_dir: dir, mode: StorageMode::Portable }
+    }
+
+    /// Carpeta de config.json (historial, fingerprints y demás datos van junto a él)
+    pub fn data_dir(&self) -> PathBuf {
+        self.config_file
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+}
+
+/// Ruta explícita: absoluta respecto a la carpeta actual; si es una carpeta se usa su config.json
+fn explicit_config_file(path: PathBuf) -> PathBuf {
+    let path = if path.is_absolute() {
+        path
+    } else {
+        env::current_dir().map(|dir| dir.join(&path)).unwrap_or(path)
+    };
+    if path.is_dir() {
+        path.join(CONFIG_FILE_NAME)
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_resolution_order() {
+        let root = env::temp_dir().join(format!("rustyvault_paths_{}", std::process::id()));
+        let (exe_dir, appdata, local) = (root.join("app"), root.join("appdata"), root.join("local"));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&exe_dir).unwrap();
+        let resolve = |cli: Option<&Path>, env: Option<&Path>| {
+            AppPaths::resolve(
+                cli.map(Path::to_path_buf),
+                env.map(Path::to_path_buf),
+                Some(exe_dir.clone()),
+                Some(appdata.clone()),
+                Some(local.clone()),
+            )
+        };
+
+        // Sin config junto al ejecutable: carpetas del usuario
+        let installed = resolve(None, None);
+        assert_eq!(installed.mode, StorageMode::Installed);
+        assert_eq!(installed.config_file, appdata.join("RustyVault").join("config.json"));
+        assert_eq!(installed.log_dir, local.join("RustyVault").join("logs"));
+
+        fs::write(exe_dir.join("config.json"), "{}").unwrap();
+        let portable = resolve(None, None);
+        assert_eq!(portable, AppPaths { config_file: exe_dir.join("config.json"), log_dir: exe_dir.clone(), mode: StorageMode::Portable });
+
+        // Una carpeta explícita usa su config.json; la línea de comandos gana a la variable
+        let shared = root.join("shared.json");
+        assert_eq!(resolve(None, Some(&shared)).mode, StorageMode::Environment);
+        let from_cli = resolve(Some(&root), Some(&shared));
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(from_cli.mode, StorageMode::CommandLine);
+        assert_eq!(from_cli.config_file, root.join("config.json"));
+        assert_eq!(from_cli.data_dir(), root);
+    }
+}
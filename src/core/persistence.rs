@@ -0,0 +1,159 @@
+//! Módulo de persistencia - escritura atómica y copias rotativas de archivos de datos
+//! Un corte de luz a mitad de guardado deja el archivo anterior intacto (temporal + fsync + rename)
+//! y las últimas versiones guardadas quedan como `nombre.bak.N.ext` para recuperarlas
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, warn};
+
+/// Distingue los temporales de guardados simultáneos dentro del proceso
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Reemplazar `path` con `contents` sin pasar nunca por un archivo vacío o a medias
+/// Cada guardado usa su propio temporal: dos guardados a la vez nunca mezclan sus escrituras
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let temp_path = sibling_path(
+        path,
+        &format!("tmp.{}.{}", std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)),
+    );
+    let result = write_and_rename(&temp_path, path, contents);
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result?;
+
+    // En Unix el rename solo es durable cuando se sincroniza la carpeta
+    #[cfg(unix)]
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+fn write_and_rename(temp_path: &Path, path: &Path, contents: &[u8]) -> Result<()> {
+    {
+        let mut file = File::create(temp_path)
+            .with_context(|| format!("Error creando {}", temp_path.display()))?;
+        file.write_all(contents)
+            .with_context(|| format!("Error escribiendo {}", temp_path.display()))?;
+        file.sync_all()
+            .with_context(|| format!("Error sincronizando {}", temp_path.display()))?;
+    }
+
+    fs::rename(temp_path, path)
+        .with_context(|| format!("Error reemplazando {}", path.display()))
+}
+
+/// Ruta de la copia número `index` (1 = la más reciente): `config.json` → `config.bak.1.json`
+pub fn backup_copy_path(path: &Path, index: usize) -> PathBuf {
+    sibling_path(path, &format!("bak.{}", index))
+}
+
+/// Registrar `contents` (lo que se acaba de guardar en `path`) como copia 1 desplazando las anteriores
+/// Se conservan `kept` copias; guardar lo mismo otra vez no expulsa copias distintas
+pub fn push_copy(path: &Path, kept: usize, contents: &str) {
+    if kept == 0 {
+        return;
+    }
+    let newest = backup_copy_path(path, 1);
+    if fs::read_to_string(&newest).is_ok_and(|previous| previous == contents) {
+        return;
+    }
+
+    let _ = fs::remove_file(backup_copy_path(path, kept));
+    for index in (1..kept).rev() {
+        let from = backup_copy_path(path, index);
+        if from.exists() {
+            if let Err(e) = fs::rename(&from, backup_copy_path(path, index + 1)) {
+                warn!("⚠️ Error rotando copia {}: {}", from.display(), e);
+            }
+        }
+    }
+    match write_atomic(&newest, contents.as_bytes()) {
+        Ok(()) => debug!("💾 Copia de seguridad guardada: {}", newest.display()),
+        Err(e) => warn!("⚠️ Error guardando copia de seguridad {}: {}", newest.display(), e),
+    }
+}
+
+/// La copia válida más reciente de `path`
+pub fn latest_valid_copy(path: &Path, kept: usize, is_valid: impl Fn(&str) -> bool) -> Option<PathBuf> {
+    (1..=kept)
+        .map(|index| backup_copy_path(path, index))
+        .find(|copy| fs::read_to_string(copy).is_ok_and(|content| is_valid(&content)))
+}
+
+/// Misma carpeta y nombre con un sufijo antes de la extensión (`config.bak.1.json`)
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, suffix, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, suffix),
+    };
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copies_rotate_and_skip_corrupt() {
+        let dir = std::env::temp_dir().join(format!("rustyvault_persistence_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        let is_valid = |content: &str| serde_json::from_str::<serde_json::Value>(content).is_ok();
+
+        for version in [1, 2, 3, 3] {
+            let contents = format!("{{\"version\": {}}}", version);
+            write_atomic(&path, contents.as_bytes()).unwrap();
+            push_copy(&path, 2, &contents);
+        }
+        // Copia más reciente dañada: se recupera la anterior
+        fs::write(backup_copy_path(&path, 1), "{\"version\":").unwrap();
+
+        let latest = latest_valid_copy(&path, 2, is_valid);
+        let oldest = fs::read_to_string(backup_copy_path(&path, 2)).unwrap();
+        let leftover_temp = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .any(|entry| entry.file_name().to_string_lossy().contains(".tmp."));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(oldest, "{\"version\": 2}");
+        assert_eq!(latest, Some(backup_copy_path(&path, 2)));
+        assert!(!leftover_temp);
+    }
+
+    #[test]
+    fn test_concurrent_saves_never_tear() {
+        let dir = std::env::temp_dir().join(format!("rustyvault_persistence_concurrent_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+
+        // Contenidos de distinto largo: un temporal compartido dejaría mezclas o colas de otro guardado
+        let contents: Vec<String> = (0..8).map(|i| format!("{{\"writer\": \"{}\"}}", "x".repeat(i * 4096))).collect();
+        std::thread::scope(|scope| {
+            for content in &contents {
+                let path = &path;
+                scope.spawn(move || {
+                    for _ in 0..20 {
+                        write_atomic(path, content.as_bytes()).unwrap();
+                    }
+                });
+            }
+        });
+
+        let saved = fs::read_to_string(&path).unwrap();
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(contents.contains(&saved));
+        assert_eq!(leftovers, 1);
+    }
+}
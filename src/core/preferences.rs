@@ -0,0 +1,197 @@
+//! Módulo de preferencias de interfaz - tema, notificaciones, nivel de log, tray e idioma
+//! Son preferencias de la máquina y no del perfil: se conservan al cambiar de perfil
+
+use serde::{Deserialize, Serialize};
+
+/// Preferencias de interfaz guardadas en config.json (`ui_preferences`)
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UiPreferences {
+    pub theme: AppTheme,
+    pub notifications: NotificationPreferences,
+    /// Nivel del archivo de log (la consola sigue `RUST_LOG`)
+    pub log_level: LogLevel,
+    pub window: WindowBehavior,
+    /// Idioma de las notificaciones del sistema
+    pub language: Language,
+}
+
+/// Tema de la ventana
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppTheme {
+    /// Sigue el tema del sistema
+    Auto,
+    Light,
+    /// Tema oscuro propio de RustyVault (el de siempre)
+    #[default]
+    Dark,
+}
+
+impl AppTheme {
+    pub const ALL: [AppTheme; 3] = [AppTheme::Auto, AppTheme::Light, AppTheme::Dark];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AppTheme::Auto => "Auto (System)",
+            AppTheme::Light => "Light",
+            AppTheme::Dark => "Dark",
+        }
+    }
+}
+
+/// Qué notificaciones del sistema mostrar
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotificationPreferences {
+    /// Interruptor general: apagado no se muestra ninguna
+    pub enabled: bool,
+    pub backup_success: bool,
+    pub backup_warning: bool,
+    pub backup_failed: bool,
+    /// Daemon iniciado/detenido
+    pub daemon: bool,
+    /// Cambio de perfil y config.json rechazado al recargar
+    pub config: bool,
+    /// Aviso al minimizar al tray
+    pub tray: bool,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            backup_success: true,
+            backup_warning: true,
+            backup_failed: true,
+            daemon: true,
+            config: true,
+            tray: true,
+        }
+    }
+}
+
+/// Tipo de notificación, para filtrar según las preferencias
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    BackupSuccess,
+    BackupWarning,
+    BackupFailed,
+    Daemon,
+    Config,
+    Tray,
+}
+
+impl NotificationPreferences {
+    /// Mostrar (o no) una notificación de este tipo
+    pub fn allows(&self, kind: NotificationKind) -> bool {
+        self.enabled && match kind {
+            NotificationKind::BackupSuccess => self.backup_success,
+            NotificationKind::BackupWarning => self.backup_warning,
+            NotificationKind::BackupFailed => self.backup_failed,
+            NotificationKind::Daemon => self.daemon,
+            NotificationKind::Config => self.config,
+            NotificationKind::Tray => self.tray,
+        }
+    }
+}
+
+/// Nivel mínimo que se escribe en el archivo de log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Error,
+    /// Solo advertencias y errores (el comportamiento de siempre)
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+
+    /// Directiva para `EnvFilter`
+    pub fn as_filter(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "Error",
+            LogLevel::Warn => "Warning",
+            LogLevel::Info => "Info",
+            LogLevel::Debug => "Debug",
+            LogLevel::Trace => "Trace",
+        }
+    }
+}
+
+/// Comportamiento de la ventana respecto del tray (todo apagado = como siempre: barra de tareas)
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WindowBehavior {
+    /// Minimizar la ventana la oculta en el tray en vez de la barra de tareas
+    pub minimize_to_tray: bool,
+    /// Cerrar la ventana la oculta en el tray; se sale desde el menú del tray
+    pub close_to_tray: bool,
+    /// Arrancar oculto en el tray
+    pub start_minimized: bool,
+}
+
+/// Idioma de las notificaciones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    #[default]
+    Spanish,
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Spanish, Language::English];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::Spanish => "Español",
+            Language::English => "English",
+        }
+    }
+
+    /// Elegir el texto en el idioma
+    pub fn pick<'a>(&self, spanish: &'a str, english: &'a str) -> &'a str {
+        match self {
+            Language::Spanish => spanish,
+            Language::English => english,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_preferences_keep_defaults() {
+        let prefs: UiPreferences = serde_json::from_str(
+            r#"{"theme": "light", "notifications": {"backup_success": false}, "log_level": "debug"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(prefs.theme, AppTheme::Light);
+        assert_eq!(prefs.log_level, LogLevel::Debug);
+        assert_eq!(prefs.window, WindowBehavior::default());
+        assert!(!prefs.notifications.allows(NotificationKind::BackupSuccess));
+        assert!(prefs.notifications.allows(NotificationKind::BackupFailed));
+
+        let muted = NotificationPreferences { enabled: false, ..NotificationPreferences::default() };
+        assert!(!muted.allows(NotificationKind::BackupFailed));
+    }
+}
@@ -0,0 +1,226 @@
+//! Módulo de perfiles - varias configuraciones con nombre ("Office", "Travel laptop"...)
+//! config.json siempre contiene el perfil activo; los demás se guardan en `profiles/<nombre>.json`
+//! junto a él. Cambiar de perfil guarda el actual en su archivo y devuelve el elegido
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::core::config::AppConfig;
+use crate::core::paths;
+use crate::core::persistence;
+
+/// Perfil de las configuraciones creadas antes de existir los perfiles
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// Largo máximo del nombre (se muestra en el tray y en el selector)
+const MAX_NAME_LEN: usize = 40;
+
+/// Perfiles guardados junto a config.json
+pub struct ProfileStore {
+    dir: PathBuf,
+}
+
+impl ProfileStore {
+    /// Carpeta `profiles` junto a config.json
+    pub fn open() -> Self {
+        Self::at(paths::current().data_dir().join("profiles"))
+    }
+
+    fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Nombres de todos los perfiles (guardados + el activo), ordenados
+    pub fn names(&self, active: &AppConfig) -> Vec<String> {
+        let mut names = vec![active.profile.clone()];
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.extension().is_some_and(|extension| extension == "json") {
+                    match read_profile(&path) {
+                        Ok(config) => names.push(config.profile),
+                        Err(e) => warn!("⚠️ Perfil ignorado {}: {:#}", path.display(), e),
+                    }
+                }
+            }
+        }
+        names.sort_by_key(|name| name.to_lowercase());
+        names.dedup_by(|a, b| same_name(a, b));
+        names
+    }
+
+    /// Ids de los pairs de todos los perfiles (config.json y los guardados)
+    /// Los datos por pair (fingerprints, últimas ejecuciones) solo se podan si ningún perfil usa el id
+    pub fn referenced_pair_ids(&self) -> HashSet<String> {
+        let mut files = vec![paths::current().config_file.clone()];
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            files.extend(
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|extension| extension == "json")),
+            );
+        }
+        files
+            .iter()
+            .filter_map(|path| read_profile(path).ok())
+            .flat_map(|config| config.backup_pairs.into_iter().map(|pair| pair.id))
+            .collect()
+    }
+
+    /// Guardar `config` en el archivo de su perfil
+    pub fn store(&self, config: &AppConfig) -> Result<()> {
+        config.ensure_writable()?;
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Error creando carpeta de perfiles: {}", self.dir.display()))?;
+        let json = serde_json::to_string_pretty(config).context("Error serializando perfil")?;
+        persistence::write_atomic(&self.file_for(&config.profile), json.as_bytes())
+            .with_context(|| format!("Error guardando perfil \"{}\"", config.profile))
+    }
+
+    /// Leer un perfil guardado
+    pub fn load(&self, name: &str) -> Result<AppConfig> {
+        let path = self.file_for(name);
+        if !path.exists() {
+            bail!("El perfil \"{}\" no existe", name);
+        }
+        let mut config = read_profile(&path)?;
+        // El nombre del archivo manda si alguien editó el campo a mano
+        if !same_name(&config.profile, name) {
+            config.profile = name.to_string();
+        }
+        Ok(config)
+    }
+
+    /// Configuración del perfil `name` sin activarlo (`--backup-now --profile`)
+    pub fn resolve(&self, active: &AppConfig, name: &str) -> Result<AppConfig> {
+        if same_name(&active.profile, name) {
+            Ok(active.clone())
+        } else {
+            self.load(name)
+        }
+    }
+
+    /// Configuración del perfil `target` para reemplazar a `current` (que queda guardado)
+    /// Los ajustes de la máquina (iniciar con Windows, preferencias de interfaz) no cambian con el perfil
+    pub fn switch(&self, current: &AppConfig, target: &str) -> Result<AppConfig> {
+        if same_name(&current.profile, target) {
+            return Ok(current.clone());
+        }
+        let mut next = self.load(target)?;
+        self.store(current)?;
+        next.start_with_windows = current.start_with_windows;
+        next.ui_preferences = current.ui_preferences.clone();
+        info!("👤 Perfil \"{}\" → \"{}\"", current.profile, next.profile);
+        Ok(next)
+    }
+
+    /// Nuevo perfil `name` a partir de una copia de `current` (que queda guardado)
+    pub fn create(&self, current: &AppConfig, name: &str) -> Result<AppConfig> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("El nombre del perfil no puede estar vacío");
+        }
+        if name.chars().count() > MAX_NAME_LEN {
+            bail!("El nombre del perfil admite hasta {} caracteres", MAX_NAME_LEN);
+        }
+        if self.names(current).iter().any(|existing| same_name(existing, name)) {
+            bail!("Ya existe un perfil \"{}\"", name);
+        }
+        // "Travel laptop" y "travel_laptop" comparten archivo: no pisar el otro perfil
+        let file = self.file_for(name);
+        if file == self.file_for(&current.profile) {
+            bail!("El nombre \"{}\" usa el mismo archivo que el perfil \"{}\" - elige otro", name, current.profile);
+        }
+        if file.exists() {
+            let other = read_profile(&file).map(|config| config.profile).unwrap_or_else(|_| file.display().to_string());
+            bail!("El nombre \"{}\" usa el mismo archivo que el perfil \"{}\" - elige otro", name, other);
+        }
+        self.store(current)?;
+        let mut created = current.clone();
+        created.profile = name.to_string();
+        self.store(&created)?;
+        info!("👤 Perfil \"{}\" creado a partir de \"{}\"", name, current.profile);
+        Ok(created)
+    }
+
+    /// `profiles/office.json`: nombre en minúsculas y sin caracteres inválidos en Windows
+    fn file_for(&self, name: &str) -> PathBuf {
+        let stem: String = name
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{}.json", stem))
+    }
+}
+
+/// Los nombres de perfil no distinguen mayúsculas (igual que los archivos en Windows)
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+fn read_profile(path: &Path) -> Result<AppConfig> {
+    let content = fs::read_to_string(path).with_context(|| format!("Error leyendo {}", path.display()))?;
+    AppConfig::from_json(&content).map(|(config, _)| config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::BackupPair;
+
+    #[test]
+    fn test_create_and_switch_profiles() {
+        let dir = std::env::temp_dir().join(format!("rustyvault_profiles_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let store = ProfileStore::at(dir.clone());
+
+        let office = AppConfig {
+            backup_pairs: vec![BackupPair::new("C:\\docs", "D:\\docs")],
+            ..AppConfig::default()
+        };
+        let mut travel = store.create(&office, " Travel laptop ").unwrap();
+        assert_eq!(travel.profile, "Travel laptop");
+        assert!(store.create(&travel, "travel LAPTOP").is_err());
+
+        travel.backup_pairs.clear();
+        travel.start_with_windows = true;
+        travel.ui_preferences.theme = crate::core::preferences::AppTheme::Light;
+        let back = store.switch(&travel, "default").unwrap();
+        let names = store.names(&back);
+        let stored_travel = store.load("Travel laptop").unwrap();
+        let referenced = store.referenced_pair_ids();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(back.profile, DEFAULT_PROFILE);
+        assert_eq!(back.backup_pairs.len(), 1);
+        assert!(back.start_with_windows);
+        assert_eq!(back.ui_preferences, travel.ui_preferences);
+        assert!(stored_travel.backup_pairs.is_empty());
+        assert!(referenced.contains(&office.backup_pairs[0].id));
+        assert_eq!(names, vec![DEFAULT_PROFILE.to_string(), "Travel laptop".to_string()]);
+        assert!(store.switch(&back, "Weekend").is_err());
+    }
+
+    #[test]
+    fn test_create_rejects_colliding_file_names() {
+        let dir = std::env::temp_dir().join(format!("rustyvault_profiles_collide_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let store = ProfileStore::at(dir.clone());
+
+        let office = AppConfig { profile: "Office!".to_string(), ..AppConfig::default() };
+        let travel = store.create(&office, "Travel laptop").unwrap();
+        let same_file = store.create(&travel, "travel_laptop");
+        let same_as_active = store.create(&travel, "Office?");
+        let stored = store.load("Travel laptop").unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(same_file.unwrap_err().to_string().contains("Travel laptop"));
+        assert!(same_as_active.is_err());
+        assert_eq!(stored.profile, "Travel laptop");
+    }
+}
@@ -0,0 +1,305 @@
+//! Módulo de progreso - pre-escaneo de lo pendiente y avance en vivo de la copia
+//! El runner escanea cada pair justo antes de copiarlo (nunca los omitidos); los motores suman bytes al tracker y
+//! el porcentaje y el tiempo restante salen de la velocidad medida hasta ahora
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::core::native::needs_copy;
+use crate::core::runner::throughput_mb_s;
+
+/// Segundos mínimos de copia antes de estimar el tiempo restante (evita ETAs absurdas al arrancar)
+const MIN_ELAPSED_FOR_ETA: Duration = Duration::from_secs(2);
+
+/// Archivos y bytes que el próximo backup tendrá que copiar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingWork {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// Recorrer el origen con el mismo criterio que el motor nativo (tamaño/fecha)
+/// Los errores de lectura se ignoran: el escaneo solo alimenta la estimación
+pub fn scan_pending(source: &Path, destination: &Path) -> PendingWork {
+    let mut pending = PendingWork::default();
+    scan_tree(source, destination, &mut pending);
+    debug!("🔍 Pendiente en {}: {} archivos, {} bytes", source.display(), pending.files, pending.bytes);
+    pending
+}
+
+fn scan_tree(source: &Path, destination: &Path, pending: &mut PendingWork) {
+    let Ok(entries) = fs::read_dir(source) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let src_path = entry.path();
+        let dest_path = destination.join(entry.file_name());
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => scan_tree(&src_path, &dest_path, pending),
+            Ok(file_type) if file_type.is_file() && needs_copy(&src_path, &dest_path).unwrap_or(true) => {
+                pending.files += 1;
+                pending.bytes += entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Foto del avance de una copia (de un pair o de la ejecución completa)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransferProgress {
+    pub files_done: u64,
+    pub files_total: u64,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    /// Tiempo copiando hasta esta foto
+    pub elapsed: Duration,
+}
+
+impl TransferProgress {
+    /// Avance de 0.0 a 1.0 (por bytes; por archivos si solo quedan archivos vacíos)
+    pub fn fraction(&self) -> f32 {
+        let (done, total) = if self.bytes_total > 0 {
+            (self.bytes_done, self.bytes_total)
+        } else {
+            (self.files_done, self.files_total)
+        };
+        if total == 0 {
+            return 1.0;
+        }
+        (done.min(total) as f64 / total as f64) as f32
+    }
+
+    pub fn percent(&self) -> u32 {
+        (self.fraction() * 100.0).floor() as u32
+    }
+
+    pub fn throughput_mb_s(&self) -> Option<f64> {
+        throughput_mb_s(self.bytes_done, self.elapsed)
+    }
+
+    /// Tiempo restante a la velocidad medida; None hasta tener suficiente muestra
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.bytes_total.saturating_sub(self.bytes_done);
+        if remaining == 0 {
+            return Some(Duration::ZERO);
+        }
+        if self.bytes_done == 0 || self.elapsed < MIN_ELAPSED_FOR_ETA {
+            return None;
+        }
+        let bytes_per_second = self.bytes_done as f64 / self.elapsed.as_secs_f64();
+        Some(Duration::from_secs_f64(remaining as f64 / bytes_per_second))
+    }
+
+    /// Avance conjunto de copias en paralelo, pairs o ejecuciones (None si no hay ninguna)
+    /// Los pairs copian en paralelo: la duración es la del que lleva más tiempo, no la suma
+    pub fn combined<'a>(transfers: impl IntoIterator<Item = &'a TransferProgress>) -> Option<TransferProgress> {
+        transfers.into_iter().fold(None, |total, transfer| {
+            let mut total = total.unwrap_or_default();
+            total.files_done += transfer.files_done;
+            total.files_total += transfer.files_total;
+            total.bytes_done += transfer.bytes_done;
+            total.bytes_total += transfer.bytes_total;
+            total.elapsed = total.elapsed.max(transfer.elapsed);
+            Some(total)
+        })
+    }
+}
+
+/// Avance de una ejecución completa: pairs terminados, copiando y todavía en cola
+/// Un pair se escanea recién al empezar a copiar: los que siguen en cola cuentan con el promedio de
+/// los ya escaneados, y los terminados conservan sus bytes como completos
+#[derive(Debug, Clone)]
+pub struct RunProgress {
+    queued: Vec<String>,
+    transfers: HashMap<String, TransferProgress>,
+    /// Pairs terminados: lo que alcanzaron a copiar cuenta como total y hecho
+    finished: TransferProgress,
+    /// Pairs terminados que habían escaneado (los omitidos no suman al promedio)
+    finished_scanned: u64,
+    started: Instant,
+}
+
+impl RunProgress {
+    pub fn new(pair_ids: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            queued: pair_ids.into_iter().collect(),
+            transfers: HashMap::new(),
+            finished: TransferProgress::default(),
+            finished_scanned: 0,
+            started: Instant::now(),
+        }
+    }
+
+    /// El pair pertenece a esta ejecución y todavía no terminó
+    pub fn contains(&self, pair_id: &str) -> bool {
+        self.transfers.contains_key(pair_id) || self.queued.iter().any(|id| id == pair_id)
+    }
+
+    pub fn update(&mut self, pair_id: &str, transfer: TransferProgress) {
+        self.queued.retain(|id| id != pair_id);
+        self.transfers.insert(pair_id.to_string(), transfer);
+    }
+
+    /// El pair terminó (copiado, fallido u omitido): sus bytes pasan a contar como completos
+    pub fn finish(&mut self, pair_id: &str) {
+        self.queued.retain(|id| id != pair_id);
+        if let Some(transfer) = self.transfers.remove(pair_id) {
+            self.finished.files_done += transfer.files_done.max(transfer.files_total);
+            self.finished.files_total += transfer.files_done.max(transfer.files_total);
+            self.finished.bytes_done += transfer.bytes_done.max(transfer.bytes_total);
+            self.finished.bytes_total += transfer.bytes_done.max(transfer.bytes_total);
+            self.finished_scanned += 1;
+        }
+    }
+
+    /// Avance de la ejecución; None mientras ningún pair haya escaneado (no hay con qué estimar la cola)
+    pub fn snapshot(&self) -> Option<TransferProgress> {
+        let mut total = TransferProgress::combined(self.transfers.values()).unwrap_or_default();
+        let scanned = self.finished_scanned + self.transfers.len() as u64;
+        if scanned == 0 {
+            return None;
+        }
+
+        total.files_done += self.finished.files_done;
+        total.bytes_done += self.finished.bytes_done;
+        let queued = self.queued.len() as u64;
+        let files_total = total.files_total + self.finished.files_total;
+        let bytes_total = total.bytes_total + self.finished.bytes_total;
+        total.files_total = files_total + files_total * queued / scanned;
+        total.bytes_total = bytes_total + bytes_total * queued / scanned;
+        total.elapsed = self.started.elapsed();
+        Some(total)
+    }
+}
+
+/// Contadores compartidos entre el motor que copia y el runner que publica el avance
+#[derive(Debug, Clone, Default)]
+pub struct ProgressTracker {
+    inner: Arc<TrackerState>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    files_total: AtomicU64,
+    bytes_total: AtomicU64,
+    files_done: AtomicU64,
+    bytes_done: AtomicU64,
+    started: Mutex<Option<Instant>>,
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empezar (o reiniciar en un reintento) con lo pendiente según el pre-escaneo
+    pub fn start(&self, pending: PendingWork) {
+        let state = &self.inner;
+        state.files_total.store(pending.files, Ordering::Relaxed);
+        state.bytes_total.store(pending.bytes, Ordering::Relaxed);
+        state.files_done.store(0, Ordering::Relaxed);
+        state.bytes_done.store(0, Ordering::Relaxed);
+        if let Ok(mut started) = state.started.lock() {
+            *started = Some(Instant::now());
+        }
+    }
+
+    pub fn add_bytes(&self, bytes: u64) {
+        self.inner.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn add_file(&self) {
+        self.inner.files_done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Avance actual; None si la copia todavía no empezó
+    pub fn snapshot(&self) -> Option<TransferProgress> {
+        let state = &self.inner;
+        let started = (*state.started.lock().ok()?)?;
+        Some(TransferProgress {
+            files_done: state.files_done.load(Ordering::Relaxed),
+            files_total: state.files_total.load(Ordering::Relaxed),
+            bytes_done: state.bytes_done.load(Ordering::Relaxed),
+            bytes_total: state.bytes_total.load(Ordering::Relaxed),
+            elapsed: started.elapsed(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_and_eta() {
+        let root = std::env::temp_dir().join(format!("rustyvault_progress_{}", std::process::id()));
+        let (source, destination) = (root.join("src"), root.join("dest"));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(source.join("sub")).unwrap();
+        fs::create_dir_all(&destination).unwrap();
+        fs::write(source.join("a.txt"), vec![0u8; 300]).unwrap();
+        fs::write(source.join("sub").join("b.txt"), vec![0u8; 700]).unwrap();
+
+        let pending = scan_pending(&source, &destination);
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(pending, PendingWork { files: 2, bytes: 1000 });
+
+        // 250 de 1000 bytes en 10s: faltan 750 bytes a 25 B/s
+        let progress = TransferProgress {
+            files_done: 1,
+            files_total: 2,
+            bytes_done: 250,
+            bytes_total: 1000,
+            elapsed: Duration::from_secs(10),
+        };
+        assert_eq!(progress.percent(), 25);
+        assert_eq!(progress.eta(), Some(Duration::from_secs(30)));
+        assert_eq!(TransferProgress { elapsed: Duration::from_secs(1), ..progress }.eta(), None);
+
+        // Un segundo pair que recién empieza: la duración conjunta es la del que lleva más tiempo
+        let starting = TransferProgress { files_total: 2, bytes_total: 1000, elapsed: Duration::from_secs(1), ..TransferProgress::default() };
+        let run = TransferProgress::combined([&progress, &starting]).unwrap();
+        assert_eq!(run.percent(), 12);
+        assert_eq!(run.elapsed, Duration::from_secs(10));
+        assert!(TransferProgress::combined([]).is_none());
+    }
+
+    #[test]
+    fn test_run_progress_keeps_finished_and_queued_pairs() {
+        let transfer = |done: u64, total: u64| TransferProgress {
+            files_done: done / 100,
+            files_total: total / 100,
+            bytes_done: done,
+            bytes_total: total,
+            elapsed: Duration::from_secs(1),
+        };
+        let mut run = RunProgress::new(["a", "b", "c"].map(String::from));
+        assert!(run.snapshot().is_none());
+
+        // a y b copiando, c en cola: c cuenta con el promedio de los escaneados (500)
+        run.update("a", transfer(200, 400));
+        run.update("b", transfer(300, 600));
+        let before = run.snapshot().unwrap();
+        assert_eq!((before.bytes_done, before.bytes_total), (500, 1500));
+
+        // a termina mientras b sigue: sus bytes no salen del total y el porcentaje no retrocede
+        run.finish("a");
+        let after = run.snapshot().unwrap();
+        assert_eq!((after.bytes_done, after.bytes_total), (700, 1500));
+        assert!(after.percent() >= before.percent());
+        assert!(!run.contains("a"));
+        assert!(run.contains("b") && run.contains("c"));
+
+        // c omitido sin escanear: deja la cola sin sumar bytes
+        run.finish("c");
+        let skipped = run.snapshot().unwrap();
+        assert_eq!((skipped.bytes_done, skipped.bytes_total), (700, 1000));
+    }
+}
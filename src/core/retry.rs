@@ -0,0 +1,195 @@
+//! Módulo de reintentos a nivel de pair - backoff exponencial con jitter
+//! Complementa /R y /W de robocopy (que solo reintentan archivos individuales)
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+use crate::core::backup::BackupResult;
+
+/// Política de reintentos para pairs que fallan por completo (ej. share inaccesible)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RetryPolicy {
+    /// Intentos totales por ejecución (1 = sin reintentos)
+    pub max_attempts: u32,
+    /// Espera antes del primer reintento (segundos)
+    pub initial_delay_seconds: u64,
+    /// Espera máxima entre reintentos (segundos)
+    pub max_delay_seconds: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,            // 1 intento + 3 reintentos
+            initial_delay_seconds: 30,  // 30s, 60s, 120s...
+            max_delay_seconds: 900,     // Nunca más de 15 minutos entre intentos
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Espera antes del reintento número `retry` (1 = primer reintento)
+    /// `jitter` en [0, 1) desplaza la espera ±20% para no sincronizar reintentos
+    pub fn delay_for_retry(&self, retry: u32, jitter: f64) -> Duration {
+        let exponent = retry.saturating_sub(1).min(16);
+        let base = self.initial_delay_seconds.saturating_mul(1u64 << exponent);
+        let capped = base.min(self.max_delay_seconds) as f64;
+        let factor = 0.8 + 0.4 * jitter.clamp(0.0, 1.0);
+        Duration::from_secs_f64(capped * factor)
+    }
+}
+
+/// Registro de un intento individual de backup
+#[derive(Debug, Clone)]
+pub struct AttemptRecord {
+    pub attempt: u32,
+    pub max_attempts: u32,
+    /// Unix timestamp de inicio del intento
+    pub started_at: u64,
+    /// Descripción del resultado ("ok", "advertencia: ...", "falló: ...")
+    pub outcome: String,
+    /// Espera hasta el siguiente intento (None si fue el último)
+    pub next_retry_in: Option<Duration>,
+}
+
+/// Resultado final de `run_with_retry`
+#[derive(Debug)]
+pub struct RetryOutcome {
+    /// Resultado del último intento; `Err` si el motor no pudo ejecutarse
+    pub result: Result<BackupResult>,
+    /// Intentos realizados
+    pub attempts: u32,
+}
+
+/// Ejecutar `run` reintentando los fallos según la política
+///
+/// - Solo se reintentan fallos completos (`Failed` o error); las advertencias se aceptan
+/// - `on_attempt` recibe cada intento terminado (para UI/logs)
+/// - `should_abort` permite cortar la espera (ej. daemon detenido)
+pub fn run_with_retry<F, A, S>(
+    policy: &RetryPolicy,
+    mut run: F,
+    mut on_attempt: A,
+    should_abort: S,
+) -> RetryOutcome
+where
+    F: FnMut() -> Result<BackupResult>,
+    A: FnMut(&AttemptRecord),
+    S: Fn() -> bool,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        let started_at = unix_now();
+
+        let result = run();
+        let outcome = match &result {
+            Ok(BackupResult::Failed) => "falló".to_string(),
+            Ok(BackupResult::Warning { message, .. }) => format!("advertencia: {}", message),
+            Ok(_) => "ok".to_string(),
+            Err(e) => format!("falló: {}", e),
+        };
+
+        let failed = matches!(result, Ok(BackupResult::Failed) | Err(_));
+        let retry_delay = (failed && attempt < max_attempts && !should_abort())
+            .then(|| policy.delay_for_retry(attempt, jitter()));
+
+        on_attempt(&AttemptRecord {
+            attempt,
+            max_attempts,
+            started_at,
+            outcome,
+            next_retry_in: retry_delay,
+        });
+
+        let Some(delay) = retry_delay else {
+            return RetryOutcome { result, attempts: attempt };
+        };
+
+        warn!("🔁 Intento {}/{} falló - reintentando en {}s", attempt, max_attempts, delay.as_secs());
+        if !sleep_unless_aborted(delay, &should_abort) {
+            info!("🛑 Reintentos cancelados");
+            return RetryOutcome { result, attempts: attempt };
+        }
+    }
+}
+
+/// Dormir en tramos cortos para poder cancelar; devuelve false si se abortó
+fn sleep_unless_aborted<S: Fn() -> bool>(delay: Duration, should_abort: &S) -> bool {
+    let step = Duration::from_millis(500);
+    let mut remaining = delay;
+
+    while !remaining.is_zero() {
+        if should_abort() {
+            return false;
+        }
+        let chunk = remaining.min(step);
+        std::thread::sleep(chunk);
+        remaining -= chunk;
+    }
+
+    !should_abort()
+}
+
+/// Jitter pseudoaleatorio en [0, 1) a partir del reloj (suficiente para desincronizar)
+fn jitter() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    (nanos % 1_000_000) as f64 / 1_000_000.0
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let policy = RetryPolicy { max_attempts: 6, initial_delay_seconds: 10, max_delay_seconds: 60 };
+
+        // jitter 0.5 → factor exacto 1.0
+        assert_eq!(policy.delay_for_retry(1, 0.5), Duration::from_secs(10));
+        assert_eq!(policy.delay_for_retry(2, 0.5), Duration::from_secs(20));
+        assert_eq!(policy.delay_for_retry(3, 0.5), Duration::from_secs(40));
+        assert_eq!(policy.delay_for_retry(4, 0.5), Duration::from_secs(60));
+        assert_eq!(policy.delay_for_retry(30, 0.5), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_retries_until_success() {
+        let policy = RetryPolicy { max_attempts: 3, initial_delay_seconds: 0, max_delay_seconds: 0 };
+        let mut calls = 0;
+        let mut records = Vec::new();
+
+        let outcome = run_with_retry(
+            &policy,
+            || {
+                calls += 1;
+                if calls < 3 {
+                    Ok(BackupResult::Failed)
+                } else {
+                    Ok(BackupResult::Success { files_copied: 1, bytes_transferred: 1 })
+                }
+            },
+            |record| records.push(record.clone()),
+            || false,
+        );
+
+        assert!(matches!(outcome.result, Ok(BackupResult::Success { .. })));
+        assert_eq!(outcome.attempts, 3);
+        assert_eq!(records.len(), 3);
+        assert!(records[2].next_retry_in.is_none());
+    }
+}
@@ -0,0 +1,521 @@
+//! Módulo runner - orquestador único de ejecuciones de backup
+//! Manual, daemon, modo continuo y CLI pasan por aquí: hooks, prioridad, paralelismo,
+//! fingerprints y reintentos; el avance se publica en el bus de eventos
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+use crate::core::backup::{execute_backup, BackupOptions, BackupResult, SkipReason};
+use crate::core::catch_up::LastRunStore;
+use crate::core::config::{AppConfig, BackupEngine, BackupPair};
+use crate::core::events::{AppEvent, EventBus, PairProgress};
+use crate::core::fingerprint::{compute_fingerprint, FingerprintStore};
+use crate::core::hooks;
+use crate::core::pair_state::retained_pair_ids;
+use crate::core::parallel::{concurrent_lanes, run_pairs_concurrently};
+use crate::core::progress::{scan_pending, PendingWork, ProgressTracker, TransferProgress};
+use crate::core::retry::{run_with_retry, RetryOutcome};
+use crate::core::throttle;
+
+/// Cada cuánto se publica el avance de un pair que está copiando
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Id de la próxima ejecución (`AppEvent::RunStarted`)
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Pairs que alguna ejecución está procesando ahora (compartido por UI, daemon y modo continuo)
+static IN_FLIGHT: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Pairs reservados por una ejecución; se liberan al terminar aunque la ejecución haga panic
+struct InFlightGuard {
+    pair_ids: Vec<String>,
+}
+
+impl InFlightGuard {
+    /// Reservar los pairs libres; devuelve la reserva y los pairs que ya estaban en ejecución
+    fn claim(pairs: Vec<BackupPair>) -> (Self, Vec<BackupPair>, Vec<BackupPair>) {
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+        let (busy, free): (Vec<_>, Vec<_>) = pairs.into_iter().partition(|pair| in_flight.contains(&pair.id));
+        let pair_ids: Vec<String> = free.iter().map(|pair| pair.id.clone()).collect();
+        in_flight.extend(pair_ids.iter().cloned());
+        (Self { pair_ids }, free, busy)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.retain(|id| !self.pair_ids.contains(id));
+    }
+}
+
+/// Origen de una ejecución
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTrigger {
+    /// Botón "Ejecutar" de la UI
+    Manual,
+    /// Schedule del daemon (número de ejecución)
+    Schedule(u64),
+    /// Cambios detectados en pairs en modo continuo
+    Watch,
+    /// Línea de comandos (`--backup-now`)
+    Cli,
+}
+
+impl RunTrigger {
+    /// Nombre para logs y notificaciones
+    pub fn label(&self) -> String {
+        match self {
+            RunTrigger::Manual => "Backup manual".to_string(),
+            RunTrigger::Schedule(run_number) => format!("Daemon #{}", run_number),
+            RunTrigger::Watch => "Modo continuo".to_string(),
+            RunTrigger::Cli => "Backup CLI".to_string(),
+        }
+    }
+
+    /// Las ejecuciones automáticas omiten orígenes sin cambios; las pedidas explícitamente siempre copian
+    fn skips_unchanged(&self) -> bool {
+        matches!(self, RunTrigger::Schedule(_) | RunTrigger::Watch)
+    }
+}
+
+/// Totales de una ejecución
+#[derive(Debug, Clone, Default)]
+pub struct RunSummary {
+    pub success: usize,
+    pub warnings: usize,
+    pub failures: usize,
+    pub skipped: usize,
+    /// El pre-hook global falló y no se procesó ningún pair
+    pub aborted: Option<String>,
+    /// Duración total (hooks globales incluidos)
+    pub duration: Duration,
+    /// Totales de los pairs exitosos
+    pub files_copied: u64,
+    pub bytes_transferred: u64,
+    /// Pairs que quedaron al día (`BackupResult::leaves_destination_current`); el scheduler
+    /// solo da por ejecutados a estos, así un pre-hook fallido o un pair ocupado se recuperan después
+    pub completed_pairs: Vec<String>,
+}
+
+impl RunSummary {
+    /// Velocidad media de la ejecución completa
+    pub fn throughput_mb_s(&self) -> Option<f64> {
+        throughput_mb_s(self.bytes_transferred, self.duration)
+    }
+}
+
+/// MB/s de una copia; None si no se copió nada o fue demasiado breve para medirse
+pub fn throughput_mb_s(bytes: u64, duration: Duration) -> Option<f64> {
+    let seconds = duration.as_secs_f64();
+    (bytes > 0 && seconds >= 0.1).then(|| bytes as f64 / (1024.0 * 1024.0) / seconds)
+}
+
+/// Prioridad ascendente; a igual prioridad se respeta el orden de la config
+fn by_priority(pairs: &[BackupPair]) -> Vec<BackupPair> {
+    let mut pairs = pairs.to_vec();
+    pairs.sort_by_key(|pair| pair.priority);
+    pairs
+}
+
+/// Expandir las variables de las rutas (`path_vars`) antes de ejecutar
+/// Un pair cuya ruta no se puede expandir (`~` sin carpeta de usuario) se mantiene en la lista y falla con el motivo
+fn resolve_paths(pairs: &[BackupPair]) -> (Vec<BackupPair>, HashMap<String, String>) {
+    let mut unresolved = HashMap::new();
+    let resolved = pairs
+        .iter()
+        .map(|pair| match pair.resolved() {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                error!("❌ Backup pair {}: {}", pair.display_name(), e);
+                unresolved.insert(pair.id.clone(), e.to_string());
+                pair.clone()
+            }
+        })
+        .collect();
+    (resolved, unresolved)
+}
+
+/// Ejecuta un conjunto de pairs con la configuración dada
+pub struct BackupRunner<'a> {
+    config: &'a AppConfig,
+    trigger: RunTrigger,
+    events: Option<&'a EventBus>,
+    /// Flag del daemon: si pasa a false se cortan la copia en curso y los reintentos pendientes
+    running: Option<Arc<AtomicBool>>,
+    /// Avisado con el id de cada pair al terminar su pre-hook, justo antes de copiar
+    pre_hook_listener: Option<&'a (dyn Fn(&str) + Sync)>,
+}
+
+impl<'a> BackupRunner<'a> {
+    pub fn new(config: &'a AppConfig, trigger: RunTrigger) -> Self {
+        Self {
+            config,
+            trigger,
+            events: None,
+            running: None,
+            pre_hook_listener: None,
+        }
+    }
+
+    /// Publicar el avance de la ejecución en `events`
+    pub fn with_events(mut self, events: &'a EventBus) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Cancelar copias y reintentos cuando `running` pase a false (daemon detenido)
+    pub fn with_running_flag(mut self, running: &Arc<AtomicBool>) -> Self {
+        self.running = Some(Arc::clone(running));
+        self
+    }
+
+    /// Avisar a `listener` cuando termina el pre-hook de cada pair (modo continuo: lo que escribió el hook
+    /// en el origen entra en esta copia y no debe disparar otra)
+    pub fn with_pre_hook_listener(mut self, listener: &'a (dyn Fn(&str) + Sync)) -> Self {
+        self.pre_hook_listener = Some(listener);
+        self
+    }
+
+    /// Ejecutar los pairs (por prioridad; pairs independientes en paralelo)
+    pub fn run(&self, pairs: &[BackupPair]) -> RunSummary {
+        let label = self.trigger.label();
+        let started = Instant::now();
+
+        let (pairs, unresolved) = resolve_paths(&by_priority(pairs));
+
+        // Un pair que otra ejecución está copiando se omite: no se publica nada para no pisar su avance
+        let (_in_flight, pairs, busy) = InFlightGuard::claim(pairs);
+        for pair in &busy {
+            info!("⏭️ Backup pair {} ya se está ejecutando - omitido en {}", pair.display_name(), label);
+        }
+        // Todos ocupados: no hay ejecución que anunciar (la UI seguiría mostrando la que copia)
+        if pairs.is_empty() && !busy.is_empty() {
+            return RunSummary {
+                skipped: busy.len(),
+                duration: started.elapsed(),
+                ..RunSummary::default()
+            };
+        }
+
+        let run_id = NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed);
+        self.emit(AppEvent::RunStarted {
+            run_id,
+            trigger: self.trigger,
+            pair_ids: pairs.iter().map(|pair| pair.id.clone()).collect(),
+        });
+
+        // Pre-hook global fallido: se omite esta ejecución completa
+        if let Err(e) = hooks::run_global_pre_hook(&self.config.hooks, pairs.len()) {
+            error!("❌ Pre-hook global falló - {} abortado: {}", label, e);
+            let summary = RunSummary {
+                aborted: Some(format!("Pre-hook global falló: {}", e)),
+                duration: started.elapsed(),
+                ..RunSummary::default()
+            };
+            self.emit(AppEvent::RunFinished { run_id, trigger: self.trigger, summary: summary.clone() });
+            return summary;
+        }
+
+        info!("🚀 {} iniciado - {} pair(s) a procesar", label, pairs.len());
+
+        // Límite global compartido por todos los pairs de esta ejecución
+        let global_bucket = throttle::shared_bucket(&self.config.bandwidth_limit);
+        let lanes = concurrent_lanes(&pairs, self.config.max_parallel_backups);
+
+        // Fingerprints del último backup exitoso; solo las ejecuciones automáticas omiten pairs
+        let fingerprints = self.config.skip_unchanged.then(FingerprintStore::load);
+        let last_runs = LastRunStore::load();
+
+        let results = run_pairs_concurrently(&pairs, self.config.max_parallel_backups, |i, pair| {
+            let pair_started = Instant::now();
+            let (result, error, engine) = match unresolved.get(&pair.id) {
+                Some(e) => (BackupResult::Failed, Some(e.clone()), None),
+                None => self.run_pair(i, pair, global_bucket.clone(), lanes, fingerprints.as_ref()),
+            };
+            let duration = pair_started.elapsed();
+            if let BackupResult::Success { bytes_transferred, .. } = &result {
+                let speed = throughput_mb_s(*bytes_transferred, duration)
+                    .map(|mb_s| format!(" ({:.1} MB/s)", mb_s))
+                    .unwrap_or_default();
+                info!("⏱️ Backup pair #{} tardó {:.1}s{}", i + 1, duration.as_secs_f64(), speed);
+            }
+            last_runs.record(pair, &result);
+            self.emit(AppEvent::PairFinished {
+                pair_id: pair.id.clone(),
+                trigger: self.trigger,
+                result: result.clone(),
+                error,
+                engine,
+                duration,
+            });
+            (pair.id.clone(), result)
+        });
+        let completed_pairs = results
+            .iter()
+            .filter(|(_, result)| result.leaves_destination_current())
+            .map(|(id, _)| id.clone())
+            .collect();
+        let mut results: Vec<_> = results.into_iter().map(|(_, result)| result).collect();
+        results.extend(busy.iter().map(|_| BackupResult::Skipped(SkipReason::AlreadyRunning)));
+
+        let mut summary = RunSummary {
+            success: results.iter().filter(|r| matches!(r, BackupResult::Success { .. })).count(),
+            warnings: results.iter().filter(|r| matches!(r, BackupResult::Warning { .. })).count(),
+            failures: results.iter().filter(|r| matches!(r, BackupResult::Failed)).count(),
+            skipped: results.iter().filter(|r| matches!(r, BackupResult::Skipped(_))).count(),
+            completed_pairs,
+            ..RunSummary::default()
+        };
+        for result in &results {
+            if let BackupResult::Success { files_copied, bytes_transferred } = result {
+                summary.files_copied += *files_copied as u64;
+                summary.bytes_transferred += bytes_transferred;
+            }
+        }
+
+        // Los pairs de otros perfiles conservan sus datos para cuando se vuelva a ese perfil
+        let keep = retained_pair_ids(&self.config.backup_pairs);
+        if let Some(store) = &fingerprints {
+            if let Err(e) = store.save(&keep) {
+                warn!("⚠️ Error guardando fingerprints: {}", e);
+            }
+        }
+        if let Err(e) = last_runs.save(&keep) {
+            warn!("⚠️ Error guardando últimas ejecuciones: {}", e);
+        }
+
+        if let Err(e) = hooks::run_global_post_hook(&self.config.hooks, summary.success, summary.warnings, summary.failures) {
+            warn!("⚠️ Post-hook global falló: {}", e);
+        }
+
+        summary.duration = started.elapsed();
+        info!("🏁 {} finalizado en {:.1}s: {} éxito, {} advertencias, {} fallos, {} sin cambios",
+             label, summary.duration.as_secs_f64(), summary.success, summary.warnings, summary.failures, summary.skipped);
+        self.emit(AppEvent::RunFinished { run_id, trigger: self.trigger, summary: summary.clone() });
+        summary
+    }
+
+    fn emit(&self, event: AppEvent) {
+        if let Some(events) = self.events {
+            events.publish(event);
+        }
+    }
+
+    /// Escaneo de lo pendiente de un pair que va a copiar, para el porcentaje y el tiempo restante
+    /// Sin nadie escuchando el avance no vale la pena recorrer origen y destino
+    fn scan_pair(&self, i: usize, pair: &BackupPair) -> Option<PendingWork> {
+        self.events?;
+
+        let scan_started = Instant::now();
+        let work = scan_pending(&pair.source, &pair.destination);
+        self.emit(AppEvent::Progress {
+            pair_id: pair.id.clone(),
+            progress: PairProgress::Transfer(TransferProgress {
+                files_total: work.files,
+                bytes_total: work.bytes,
+                ..TransferProgress::default()
+            }),
+        });
+        info!("🔍 Backup pair #{}: escaneo en {:.1}s - {} bytes pendientes", i + 1, scan_started.elapsed().as_secs_f64(), work.bytes);
+        Some(work)
+    }
+
+    /// Publicar el avance del pair hasta que `copying` pase a false (el runner despierta al hilo al terminar)
+    fn report_transfer(&self, pair_id: &str, progress: &ProgressTracker, copying: &AtomicBool) {
+        loop {
+            thread::park_timeout(PROGRESS_INTERVAL);
+            if !copying.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Some(snapshot) = progress.snapshot() {
+                self.emit(AppEvent::Progress {
+                    pair_id: pair_id.to_string(),
+                    progress: PairProgress::Transfer(snapshot),
+                });
+            }
+        }
+    }
+
+    /// Ejecutar un pair: pre-hook, detección de cambios, copia con reintentos y post-hook
+    /// Devuelve el resultado, la descripción del fallo para la UI y el motor usado (si llegó a copiar)
+    fn run_pair(
+        &self,
+        i: usize,
+        pair: &BackupPair,
+        global_bucket: Option<Arc<Mutex<throttle::TokenBucket>>>,
+        lanes: usize,
+        fingerprints: Option<&FingerprintStore>,
+    ) -> (BackupResult, Option<String>, Option<BackupEngine>) {
+        info!("🔄 Procesando backup pair #{}: {} → {}",
+             i + 1, pair.source.display(), pair.destination.display());
+        self.emit(AppEvent::Progress { pair_id: pair.id.clone(), progress: PairProgress::Started });
+
+        // Pre-hook: si falla, el pair se aborta sin ejecutar el backup
+        let pre_hook = hooks::run_pair_pre_hook(pair);
+        if let Some(listener) = self.pre_hook_listener {
+            listener(&pair.id);
+        }
+        if let Err(e) = pre_hook {
+            error!("❌ Pre-hook del backup pair #{} falló - pair abortado: {}", i + 1, e);
+            return (BackupResult::Failed, Some(format!("Pre-hook falló: {}", e)), None);
+        }
+
+        let fingerprint = fingerprints
+            .and_then(|_| compute_fingerprint(&pair.source)
+                .map_err(|e| warn!("⚠️ No se pudo calcular fingerprint del pair #{}: {}", i + 1, e))
+                .ok());
+
+        // Origen sin cambios desde el último backup exitoso: no lanzar el motor de copia
+        if let (Some(store), Some(current), true) = (fingerprints, &fingerprint, self.trigger.skips_unchanged()) {
+            if store.is_unchanged(pair, current) {
+                info!("⏭️ Backup pair #{} sin cambios - omitido", i + 1);
+                return (hooks::apply_pair_post_hook(pair, BackupResult::Skipped(SkipReason::NoChanges)), None, None);
+            }
+        }
+
+        // Solo los pairs que van a copiar se escanean (un pair omitido no despierta el disco destino);
+        // los reintentos reutilizan el mismo escaneo
+        let pending = self.scan_pair(i, pair);
+        let options = BackupOptions::for_pair(self.config, pair, global_bucket, lanes)
+            .with_running_flag(self.running.clone());
+        let copying = AtomicBool::new(true);
+        let RetryOutcome { result, attempts } = thread::scope(|scope| {
+            let reporter = self.events.is_some().then(|| {
+                scope.spawn(|| self.report_transfer(&pair.id, &options.progress, &copying))
+            });
+            let outcome = run_with_retry(
+                &self.config.pair_retry,
+                || {
+                    if let Some(work) = pending {
+                        options.progress.start(work);
+                    }
+                    execute_backup(&pair.source, &pair.destination, &options)
+                },
+                |attempt| {
+                    if attempt.attempt > 1 || attempt.next_retry_in.is_some() {
+                        info!("🔁 Backup pair #{} intento {}/{}: {}",
+                             i + 1, attempt.attempt, attempt.max_attempts, attempt.outcome);
+                    }
+                    self.emit(AppEvent::Progress {
+                        pair_id: pair.id.clone(),
+                        progress: PairProgress::Attempt(attempt.clone()),
+                    });
+                },
+                || self.running.as_ref().is_some_and(|running| !running.load(Ordering::Relaxed)),
+            );
+            copying.store(false, Ordering::Relaxed);
+            if let Some(reporter) = reporter {
+                reporter.thread().unpark();
+            }
+            outcome
+        });
+        // Un error del motor (ej. origen inaccesible) es un fallo con su detalle
+        let (result, failure) = match result {
+            Ok(result) => (result, None),
+            Err(e) => (BackupResult::Failed, Some(e)),
+        };
+        if let (Some(store), Some(fingerprint), BackupResult::Success { .. }) = (fingerprints, fingerprint, &result) {
+            store.record(pair, fingerprint);
+        }
+        let result = hooks::apply_pair_post_hook(pair, result);
+
+        let error = match &result {
+            BackupResult::Success { files_copied, bytes_transferred } => {
+                info!("✅ Backup pair #{} completado exitosamente - {} archivos, {} bytes", i + 1, files_copied, bytes_transferred);
+                None
+            }
+            BackupResult::Warning { message, .. } => {
+                warn!("⚠️ Backup pair #{} completado con advertencias: {}", i + 1, message);
+                None
+            }
+            BackupResult::Skipped(reason) => {
+                info!("⏭️ Backup pair #{} omitido: {}", i + 1, reason.describe());
+                None
+            }
+            BackupResult::Failed => {
+                match failure {
+                    Some(e) => {
+                        error!("❌ Backup pair #{} falló tras {} intento(s): {}", i + 1, attempts, e);
+                        Some(format!("Error crítico: {}", e))
+                    }
+                    None => {
+                        error!("❌ Backup pair #{} falló tras {} intento(s)", i + 1, attempts);
+                        Some("Backup falló".to_string())
+                    }
+                }
+            }
+        };
+
+        (result, error, Some(options.effective_engine()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_order_and_triggers() {
+        let mut pairs: Vec<BackupPair> = ["a", "b", "c"]
+            .iter()
+            .map(|name| BackupPair::with_id(name.to_string(), name.into(), "dest".into()))
+            .collect();
+        pairs[0].priority = 2;
+        pairs[1].priority = 1;
+        pairs[2].priority = 2;
+
+        let order: Vec<_> = by_priority(&pairs).into_iter().map(|pair| pair.id).collect();
+        assert_eq!(order, ["b", "a", "c"]);
+
+        assert!(RunTrigger::Schedule(1).skips_unchanged());
+        assert!(RunTrigger::Watch.skips_unchanged());
+        assert!(!RunTrigger::Manual.skips_unchanged());
+        assert!(!RunTrigger::Cli.skips_unchanged());
+    }
+
+    #[test]
+    fn test_aborted_run_completes_no_pairs() {
+        let mut config = AppConfig::default();
+        config.hooks.pre_run = Some("exit 1".to_string());
+        let pairs = vec![BackupPair::with_id("aborted-run".to_string(), "origen".into(), "destino".into())];
+
+        // El daemon no debe dar por ejecutado ningún pair si el pre-hook global aborta la ejecución
+        let summary = BackupRunner::new(&config, RunTrigger::Schedule(1)).run(&pairs);
+        assert!(summary.aborted.is_some());
+        assert!(summary.completed_pairs.is_empty());
+
+        assert!(BackupResult::Skipped(SkipReason::NoChanges).leaves_destination_current());
+        assert!(!BackupResult::Skipped(SkipReason::AlreadyRunning).leaves_destination_current());
+        assert!(!BackupResult::Failed.leaves_destination_current());
+    }
+
+    #[test]
+    fn test_in_flight_pairs_are_skipped() {
+        let pairs: Vec<BackupPair> = ["in-flight-a", "in-flight-b"]
+            .iter()
+            .map(|name| BackupPair::with_id(name.to_string(), name.into(), "dest".into()))
+            .collect();
+
+        let (first, claimed, busy) = InFlightGuard::claim(pairs[..1].to_vec());
+        assert_eq!(claimed.len(), 1);
+        assert!(busy.is_empty());
+
+        // La segunda ejecución solo se queda con el pair libre
+        let (second, claimed, busy) = InFlightGuard::claim(pairs.clone());
+        assert_eq!(claimed.iter().map(|pair| pair.id.as_str()).collect::<Vec<_>>(), ["in-flight-b"]);
+        assert_eq!(busy.iter().map(|pair| pair.id.as_str()).collect::<Vec<_>>(), ["in-flight-a"]);
+
+        drop(first);
+        drop(second);
+        let (_third, claimed, busy) = InFlightGuard::claim(pairs);
+        assert_eq!(claimed.len(), 2);
+        assert!(busy.is_empty());
+    }
+}
@@ -0,0 +1,763 @@
+//! Módulo de scheduling - calendario (diario, semanal, mensual, cron) y ventanas horarias
+//! Los cálculos usan hora local con reglas explícitas para los cambios de horario (DST)
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{error, info};
+
+use crate::core::config::{BackupPair, CatchUpPolicy};
+
+/// Cuánto hacia adelante se buscan ocurrencias (cubre expresiones como "29 de febrero")
+const MAX_SEARCH_DAYS: i64 = 366 * 8;
+
+/// Cuándo ejecutar un backup
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Schedule {
+    /// Cada N segundos desde la última ejecución (comportamiento clásico del daemon)
+    Interval { seconds: u64 },
+    /// Todos los días a la hora indicada ("HH:MM")
+    Daily { time: String },
+    /// Días de la semana seleccionados ("mon", "tue", ... "sun")
+    Weekly { days: Vec<String>, time: String },
+    /// Un día del mes; en meses más cortos se usa el último día
+    Monthly { day: u8, time: String },
+    /// Expresión cron estándar de 5 campos: minuto hora día-mes mes día-semana
+    Cron { expression: String },
+}
+
+/// Ventana horaria local en la que se permite ejecutar ("02:00" - "06:00")
+/// Si `end` es menor que `start` la ventana cruza medianoche
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TimeWindow {
+    pub start: String,
+    pub end: String,
+}
+
+/// Schedule con sus ventanas permitidas (global o por pair)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ScheduleConfig {
+    #[serde(flatten)]
+    pub schedule: Schedule,
+    /// Vacío = cualquier hora
+    #[serde(default)]
+    pub allowed_windows: Vec<TimeWindow>,
+}
+
+impl ScheduleConfig {
+    /// Schedule clásico: cada `seconds` segundos sin restricciones
+    pub fn interval(seconds: u64) -> Self {
+        Self {
+            schedule: Schedule::Interval { seconds },
+            allowed_windows: Vec::new(),
+        }
+    }
+
+    /// Validar y preparar el schedule para calcular ocurrencias
+    pub fn compile(&self) -> Result<CompiledSchedule> {
+        let rule = match &self.schedule {
+            Schedule::Interval { seconds } => Rule::Interval(ChronoDuration::seconds((*seconds).max(1) as i64)),
+            Schedule::Daily { time } => {
+                let time = parse_time(time)?;
+                Rule::Calendar(CalendarRule::at_time(time))
+            }
+            Schedule::Weekly { days, time } => {
+                if days.is_empty() {
+                    bail!("El schedule semanal necesita al menos un día");
+                }
+                let mut rule = CalendarRule::at_time(parse_time(time)?);
+                rule.days_of_week = 0;
+                rule.any_day_of_week = false;
+                for day in days {
+                    rule.days_of_week |= 1 << parse_weekday(day)?;
+                }
+                Rule::Calendar(rule)
+            }
+            Schedule::Monthly { day, time } => {
+                if !(1..=31).contains(day) {
+                    bail!("Día del mes inválido: {} (1-31)", day);
+                }
+                let mut rule = CalendarRule::at_time(parse_time(time)?);
+                rule.monthly_day = Some(*day as u32);
+                Rule::Calendar(rule)
+            }
+            Schedule::Cron { expression } => Rule::Calendar(CalendarRule::parse_cron(expression)?),
+        };
+
+        let windows = self.allowed_windows
+            .iter()
+            .map(|window| Ok((parse_time(&window.start)?, parse_time(&window.end)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(CompiledSchedule { rule, windows })
+    }
+
+    /// Descripción corta para UI y logs
+    pub fn describe(&self) -> String {
+        let base = match &self.schedule {
+            Schedule::Interval { seconds } => format!("cada {}s", seconds),
+            Schedule::Daily { time } => format!("diario a las {}", time),
+            Schedule::Weekly { days, time } => format!("{} a las {}", days.join(","), time),
+            Schedule::Monthly { day, time } => format!("día {} de cada mes a las {}", day, time),
+            Schedule::Cron { expression } => format!("cron '{}'", expression),
+        };
+
+        if self.allowed_windows.is_empty() {
+            base
+        } else {
+            let windows: Vec<_> = self.allowed_windows
+                .iter()
+                .map(|window| format!("{}-{}", window.start, window.end))
+                .collect();
+            format!("{} (solo {})", base, windows.join(", "))
+        }
+    }
+}
+
+/// Schedule validado, listo para calcular la próxima ejecución
+#[derive(Debug, Clone)]
+pub struct CompiledSchedule {
+    rule: Rule,
+    windows: Vec<(NaiveTime, NaiveTime)>,
+}
+
+#[derive(Debug, Clone)]
+enum Rule {
+    Interval(ChronoDuration),
+    Calendar(CalendarRule),
+}
+
+impl CompiledSchedule {
+    /// Primera ejecución al arrancar el daemon
+    /// Los intervalos corren de inmediato (como siempre); los calendarios esperan su hora
+    pub fn first_run<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        match &self.rule {
+            Rule::Interval(_) => self.adjust_to_window(now.clone()),
+            Rule::Calendar(_) => self.next_after(now),
+        }
+    }
+
+    /// Próxima ejecución estrictamente posterior a `after`
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let candidate = match &self.rule {
+            Rule::Interval(interval) => after.clone() + *interval,
+            Rule::Calendar(rule) => rule.next_after(after)?,
+        };
+        self.adjust_to_window(candidate)
+    }
+
+    /// Si el candidato cae fuera de las ventanas permitidas, moverlo a la próxima apertura
+    fn adjust_to_window<Tz: TimeZone>(&self, candidate: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        if self.windows.is_empty() || self.in_window(candidate.naive_local().time()) {
+            return Some(candidate);
+        }
+
+        let tz = candidate.timezone();
+        let local = candidate.naive_local();
+        (0..=1)
+            .flat_map(|offset| {
+                let date = local.date() + ChronoDuration::days(offset);
+                self.windows.iter().map(move |(start, _)| date.and_time(*start))
+            })
+            .filter(|opening| *opening > local)
+            .filter_map(|opening| resolve_local(&tz, opening))
+            .min()
+    }
+
+    fn in_window(&self, time: NaiveTime) -> bool {
+        self.windows.iter().any(|(start, end)| {
+            if start == end {
+                true
+            } else if start < end {
+                time >= *start && time < *end
+            } else {
+                // Ventana que cruza medianoche (ej. 22:00 → 06:00)
+                time >= *start || time < *end
+            }
+        })
+    }
+}
+
+/// Regla de calendario estilo cron (máscaras de bits por campo)
+#[derive(Debug, Clone)]
+struct CalendarRule {
+    minutes: u64,
+    hours: u32,
+    /// Bits 1-31
+    days_of_month: u32,
+    /// Bits 1-12
+    months: u16,
+    /// Bits 0-6 (domingo = 0)
+    days_of_week: u8,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+    /// Schedule mensual: día fijo, ajustado al último día en meses cortos
+    monthly_day: Option<u32>,
+}
+
+impl CalendarRule {
+    /// Todos los días a una hora fija
+    fn at_time(time: NaiveTime) -> Self {
+        Self {
+            minutes: 1 << time.minute(),
+            hours: 1 << time.hour(),
+            days_of_month: u32::MAX,
+            months: u16::MAX,
+            days_of_week: u8::MAX,
+            any_day_of_month: true,
+            any_day_of_week: true,
+            monthly_day: None,
+        }
+    }
+
+    fn parse_cron(expression: &str) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            bail!("La expresión cron debe tener 5 campos (minuto hora día mes día-semana): '{}'", expression);
+        }
+
+        let minutes = parse_cron_field(fields[0], 0, 59, &[])?;
+        let hours = parse_cron_field(fields[1], 0, 23, &[])?;
+        let days_of_month = parse_cron_field(fields[2], 1, 31, &[])?;
+        let months = parse_cron_field(fields[3], 1, 12, &MONTH_NAMES)?;
+        // 7 también es domingo
+        let mut days_of_week = parse_cron_field(fields[4], 0, 7, &WEEKDAY_NAMES)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+
+        Ok(Self {
+            minutes,
+            hours: hours as u32,
+            days_of_month: days_of_month as u32,
+            months: months as u16,
+            days_of_week: (days_of_week & 0x7f) as u8,
+            // Como en Vixie cron, un campo que empieza por `*` (`*/2` incluido) no restringe el día
+            any_day_of_month: fields[2].starts_with('*'),
+            any_day_of_week: fields[4].starts_with('*'),
+            monthly_day: None,
+        })
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+
+        if let Some(day) = self.monthly_day {
+            return date.day() == day.min(last_day_of_month(date));
+        }
+
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+
+        // Semántica cron: si ambos campos están restringidos basta con cumplir uno
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => dow,
+            (false, true) => dom,
+            (false, false) => dom || dow,
+        }
+    }
+
+    /// Buscar la próxima hora local que cumple la regla y convertirla a un instante real
+    /// - Hora inexistente (adelanto de reloj): se ejecuta al final del salto
+    /// - Hora repetida (atraso de reloj): se ejecuta solo en la primera ocurrencia
+    fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = truncate_to_minute(after.naive_local()) + ChronoDuration::minutes(1);
+
+        for day_offset in 0..MAX_SEARCH_DAYS {
+            let date = start.date() + ChronoDuration::days(day_offset);
+            if !self.matches_date(date) {
+                continue;
+            }
+
+            for hour in (0..24).filter(|hour| self.hours & (1 << hour) != 0) {
+                for minute in (0..60).filter(|minute| self.minutes & (1u64 << minute) != 0) {
+                    let Some(local) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    if local < start {
+                        continue;
+                    }
+
+                    let resolved = match tz.from_local_datetime(&local) {
+                        LocalResult::Single(instant) => Some(instant),
+                        // Hora repetida: la segunda ocurrencia no vuelve a disparar
+                        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+                        LocalResult::None => resolve_local(&tz, local),
+                    };
+
+                    if let Some(instant) = resolved.filter(|instant| instant > after) {
+                        return Some(instant);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Convertir hora local a instante; si cae en un salto DST, usar el primer minuto válido posterior
+fn resolve_local<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> Option<DateTime<Tz>> {
+    (0..=24 * 60).find_map(|minutes| {
+        tz.from_local_datetime(&(local + ChronoDuration::minutes(minutes))).earliest()
+    })
+}
+
+fn truncate_to_minute(local: NaiveDateTime) -> NaiveDateTime {
+    local.with_second(0).and_then(|local| local.with_nanosecond(0)).unwrap_or(local)
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+fn parse_time(text: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M")
+        .with_context(|| format!("Hora inválida '{}' (formato HH:MM)", text))
+}
+
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+const MONTH_NAMES: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+fn parse_weekday(text: &str) -> Result<u32> {
+    let lower = text.trim().to_lowercase();
+    WEEKDAY_NAMES
+        .iter()
+        .position(|name| lower.starts_with(name))
+        .map(|index| index as u32)
+        .ok_or_else(|| anyhow!("Día de la semana inválido: '{}'", text))
+}
+
+/// Parsear un campo cron: `*`, `5`, `1-5`, `*/15`, `1-30/5`, listas con comas y nombres
+fn parse_cron_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64> {
+    let mut mask = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().with_context(|| format!("Paso inválido en '{}'", part))?;
+                if step == 0 {
+                    bail!("Paso 0 inválido en '{}'", part);
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_cron_value(start, min, names)?, parse_cron_value(end, min, names)?)
+        } else {
+            let value = parse_cron_value(range, min, names)?;
+            // "5/10" significa desde 5 hasta el máximo cada 10
+            (value, if part.contains('/') { max } else { value })
+        };
+
+        if start < min || end > max || start > end {
+            bail!("Valor fuera de rango en '{}' ({}-{})", part, min, max);
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1u64 << value;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_cron_value(text: &str, min: u32, names: &[&str]) -> Result<u32> {
+    if let Ok(value) = text.parse::<u32>() {
+        return Ok(value);
+    }
+    let lower = text.to_lowercase();
+    names
+        .iter()
+        .position(|name| *name == lower)
+        // Meses empiezan en 1, días de la semana en 0
+        .map(|index| index as u32 + min)
+        .ok_or_else(|| anyhow!("Valor cron inválido: '{}'", text))
+}
+
+/// Fuente de la hora actual (inyectable para tests)
+pub trait Clock {
+    type Tz: TimeZone;
+    fn now(&self) -> DateTime<Self::Tz>;
+}
+
+/// Reloj del sistema en hora local
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    type Tz = Local;
+
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+/// Próxima ejecución calculada para un pair
+struct PairSchedule<Tz: TimeZone> {
+    config: ScheduleConfig,
+    compiled: Option<CompiledSchedule>,
+    /// Última ejecución (restaurada de disco al arrancar)
+    last_run: Option<DateTime<Tz>>,
+    next_run: Option<DateTime<Tz>>,
+}
+
+/// Lleva la cuenta de cuándo le toca a cada pair según su schedule (o el global)
+pub struct PairScheduler<C: Clock = SystemClock> {
+    clock: C,
+    pairs: HashMap<String, PairSchedule<C::Tz>>,
+    /// Últimas ejecuciones exitosas persistidas (Unix timestamp)
+    restored_runs: HashMap<String, i64>,
+    /// Espera antes de recuperar ejecuciones perdidas (None = no se recuperan)
+    catch_up_delay: Option<ChronoDuration>,
+}
+
+impl<C: Clock> PairScheduler<C> {
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            pairs: HashMap::new(),
+            restored_runs: HashMap::new(),
+            catch_up_delay: None,
+        }
+    }
+
+    /// Usar las últimas ejecuciones exitosas de sesiones anteriores al programar cada pair
+    pub fn restore_last_runs(&mut self, runs: HashMap<String, i64>) {
+        self.restored_runs = runs;
+    }
+
+    /// Configurar la recuperación de ejecuciones perdidas
+    pub fn set_catch_up(&mut self, policy: &CatchUpPolicy) {
+        self.catch_up_delay = policy.enabled
+            .then(|| ChronoDuration::seconds(policy.grace_delay_seconds as i64));
+    }
+
+    /// Devolver los ids de los pairs cuya hora ya llegó
+    /// Pairs nuevos o con schedule modificado se (re)programan automáticamente
+    pub fn due_pairs(&mut self, pairs: &[BackupPair], global: &ScheduleConfig) -> Vec<String> {
+        let now = self.clock.now();
+        self.pairs.retain(|id, _| pairs.iter().any(|pair| pair.id == *id));
+
+        let mut due = Vec::new();
+        for pair in pairs {
+            let config = pair.schedule.as_ref().unwrap_or(global);
+            let needs_schedule = self.pairs
+                .get(&pair.id)
+                .is_none_or(|entry| entry.config != *config);
+
+            if needs_schedule {
+                let compiled = match config.compile() {
+                    Ok(compiled) => Some(compiled),
+                    Err(e) => {
+                        error!("❌ Schedule inválido para pair {} - no se ejecutará: {:#}", pair.id, e);
+                        None
+                    }
+                };
+
+                let last_run = match self.pairs.get(&pair.id) {
+                    Some(entry) => entry.last_run.clone(),
+                    None => self.restored_runs
+                        .get(&pair.id)
+                        .and_then(|secs| now.timezone().timestamp_opt(*secs, 0).single()),
+                };
+
+                let next_run = compiled.as_ref().and_then(|compiled| {
+                    match (&last_run, self.catch_up_delay) {
+                        (Some(last_run), Some(_)) => {
+                            let (next, overdue) = next_from(compiled, last_run, &now, self.catch_up_delay);
+                            if overdue {
+                                info!("⏰ Pair {} tiene una ejecución atrasada - se recuperará", pair.id);
+                            }
+                            next
+                        }
+                        _ => compiled.first_run(&now),
+                    }
+                });
+                if let Some(next) = &next_run {
+                    info!("📅 Pair {} programado ({}): próxima ejecución {}",
+                         pair.id, config.describe(), next.naive_local().format("%Y-%m-%d %H:%M"));
+                }
+                self.pairs.insert(pair.id.clone(), PairSchedule {
+                    config: config.clone(),
+                    compiled,
+                    last_run,
+                    next_run,
+                });
+            }
+
+            if let Some(entry) = self.pairs.get(&pair.id) {
+                if entry.next_run.as_ref().is_some_and(|next| *next <= now) {
+                    due.push(pair.id.clone());
+                }
+            }
+        }
+
+        due
+    }
+
+    /// Registrar que el pair se ejecutó y calcular su próxima ejecución
+    /// Igual que `LastRunStore::record`, una ejecución fallida no cuenta como última ejecución
+    /// y la recuperación tras una suspensión la vuelve a intentar
+    pub fn mark_ran(&mut self, pair_id: &str, succeeded: bool) {
+        let now = self.clock.now();
+        if let Some(entry) = self.pairs.get_mut(pair_id) {
+            entry.next_run = entry.compiled.as_ref().and_then(|compiled| compiled.next_after(&now));
+            if succeeded {
+                entry.last_run = Some(now);
+            }
+        }
+    }
+
+    /// Recalcular todo tras un salto del reloj (suspensión o cambio de hora)
+    /// Las ejecuciones que quedaron atrás se recuperan o se saltan según la política
+    pub fn reschedule_after_clock_jump(&mut self) {
+        let now = self.clock.now();
+        for (id, entry) in &mut self.pairs {
+            let Some(compiled) = &entry.compiled else {
+                continue;
+            };
+            let last_run = entry.last_run.clone().unwrap_or_else(|| now.clone());
+            let (next, overdue) = next_from(compiled, &last_run, &now, self.catch_up_delay);
+            if overdue {
+                info!("⏰ Pair {} perdió su ejecución durante el salto de reloj - se recuperará", id);
+            }
+            entry.next_run = next;
+        }
+    }
+
+    /// Adelantar todos los pairs programados para que corran ya
+    pub fn run_all_now(&mut self) {
+        let now = self.clock.now();
+        for entry in self.pairs.values_mut().filter(|entry| entry.compiled.is_some()) {
+            entry.next_run = Some(now.clone());
+        }
+    }
+
+    /// Próxima ejecución de cada pair programado (key = pair id)
+    pub fn next_runs(&self) -> HashMap<String, DateTime<C::Tz>> {
+        self.pairs
+            .iter()
+            .filter_map(|(id, entry)| entry.next_run.clone().map(|next| (id.clone(), next)))
+            .collect()
+    }
+
+    /// Próxima ejecución programada de un pair
+    pub fn next_run_at(&self, pair_id: &str) -> Option<DateTime<C::Tz>> {
+        self.pairs.get(pair_id).and_then(|entry| entry.next_run.clone())
+    }
+
+    /// La próxima ejecución de cualquier pair
+    pub fn earliest_next_run(&self) -> Option<DateTime<C::Tz>> {
+        self.pairs.values().filter_map(|entry| entry.next_run.clone()).min()
+    }
+}
+
+/// Próxima ejecución a partir de la última; si ya pasó, recuperar tras `catch_up_delay`
+/// o saltar a la siguiente ocurrencia futura. Devuelve también si hubo que recuperar
+fn next_from<Tz: TimeZone>(
+    compiled: &CompiledSchedule,
+    last_run: &DateTime<Tz>,
+    now: &DateTime<Tz>,
+    catch_up_delay: Option<ChronoDuration>,
+) -> (Option<DateTime<Tz>>, bool) {
+    // Un reloj atrasado puede dejar la última ejecución en el futuro
+    let base = if last_run > now { now } else { last_run };
+
+    match compiled.next_after(base) {
+        Some(next) if next <= *now => match catch_up_delay {
+            Some(delay) => (compiled.adjust_to_window(now.clone() + delay), true),
+            None => (compiled.next_after(now), false),
+        },
+        next => (next, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Madrid;
+    use std::cell::Cell;
+
+    fn madrid(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<chrono_tz::Tz> {
+        Madrid.with_ymd_and_hms(y, m, d, h, min, 0).earliest().unwrap()
+    }
+
+    fn daily(time: &str) -> ScheduleConfig {
+        ScheduleConfig {
+            schedule: Schedule::Daily { time: time.to_string() },
+            allowed_windows: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_daily_weekly_monthly() {
+        let after = madrid(2024, 1, 10, 12, 0); // miércoles
+
+        let next = daily("02:30").compile().unwrap().next_after(&after).unwrap();
+        assert_eq!(next, madrid(2024, 1, 11, 2, 30));
+
+        let weekly = ScheduleConfig {
+            schedule: Schedule::Weekly { days: vec!["mon".into(), "fri".into()], time: "08:00".into() },
+            allowed_windows: Vec::new(),
+        };
+        assert_eq!(weekly.compile().unwrap().next_after(&after).unwrap(), madrid(2024, 1, 12, 8, 0));
+
+        // Día 31 en febrero bisiesto → 29
+        let monthly = ScheduleConfig {
+            schedule: Schedule::Monthly { day: 31, time: "23:00".into() },
+            allowed_windows: Vec::new(),
+        };
+        let compiled = monthly.compile().unwrap();
+        assert_eq!(compiled.next_after(&madrid(2024, 2, 1, 0, 0)).unwrap(), madrid(2024, 2, 29, 23, 0));
+    }
+
+    #[test]
+    fn test_cron_expressions() {
+        let cron = |expr: &str| ScheduleConfig {
+            schedule: Schedule::Cron { expression: expr.to_string() },
+            allowed_windows: Vec::new(),
+        }.compile();
+
+        let business = cron("*/15 9-17 * * mon-fri").unwrap();
+        // Sábado → lunes 9:00
+        assert_eq!(business.next_after(&madrid(2024, 1, 13, 10, 0)).unwrap(), madrid(2024, 1, 15, 9, 0));
+        assert_eq!(business.next_after(&madrid(2024, 1, 15, 9, 0)).unwrap(), madrid(2024, 1, 15, 9, 15));
+
+        // Un día con paso (`*/2`) no restringe: solo cuenta el otro campo
+        let after = madrid(2024, 1, 10, 12, 0); // miércoles
+        assert_eq!(cron("0 8 */2 * mon").unwrap().next_after(&after).unwrap(), madrid(2024, 1, 15, 8, 0));
+        assert_eq!(cron("0 8 1 * */2").unwrap().next_after(&after).unwrap(), madrid(2024, 2, 1, 8, 0));
+
+        assert!(cron("* * *").is_err());
+        assert!(cron("61 * * * *").is_err());
+        assert!(cron("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn test_dst_transitions() {
+        // 31/03/2024: en Madrid las 02:00 saltan a 03:00 → 02:30 no existe
+        let spring = daily("02:30").compile().unwrap().next_after(&madrid(2024, 3, 30, 12, 0)).unwrap();
+        assert_eq!(spring, madrid(2024, 3, 31, 3, 0));
+
+        // 27/10/2024: 02:30 ocurre dos veces → solo la primera
+        let compiled = daily("02:30").compile().unwrap();
+        let first = compiled.next_after(&madrid(2024, 10, 26, 12, 0)).unwrap();
+        assert_eq!(first, madrid(2024, 10, 27, 2, 30));
+        let second = compiled.next_after(&(first + ChronoDuration::minutes(30))).unwrap();
+        assert_eq!(second, madrid(2024, 10, 28, 2, 30));
+
+        // Los intervalos son duraciones reales: 1h después de 01:30 CET es 03:30 CEST
+        let hourly = ScheduleConfig::interval(3600).compile().unwrap();
+        assert_eq!(hourly.next_after(&madrid(2024, 3, 31, 1, 30)).unwrap(), madrid(2024, 3, 31, 3, 30));
+    }
+
+    #[test]
+    fn test_time_windows_defer_runs() {
+        let mut config = daily("23:00");
+        config.allowed_windows = vec![TimeWindow { start: "02:00".into(), end: "06:00".into() }];
+        let compiled = config.compile().unwrap();
+        assert_eq!(compiled.next_after(&madrid(2024, 1, 10, 12, 0)).unwrap(), madrid(2024, 1, 11, 2, 0));
+
+        // Dentro de la ventana no se mueve
+        let mut interval = ScheduleConfig::interval(600);
+        interval.allowed_windows = vec![TimeWindow { start: "22:00".into(), end: "06:00".into() }];
+        let compiled = interval.compile().unwrap();
+        assert_eq!(compiled.next_after(&madrid(2024, 1, 10, 23, 0)).unwrap(), madrid(2024, 1, 10, 23, 10));
+        assert_eq!(compiled.next_after(&madrid(2024, 1, 10, 12, 0)).unwrap(), madrid(2024, 1, 10, 22, 0));
+    }
+
+    struct ManualClock(Cell<DateTime<chrono_tz::Tz>>);
+
+    impl Clock for &ManualClock {
+        type Tz = chrono_tz::Tz;
+
+        fn now(&self) -> DateTime<chrono_tz::Tz> {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn test_pair_scheduler_with_manual_clock() {
+        let clock = ManualClock(Cell::new(madrid(2024, 1, 10, 12, 0)));
+        let mut scheduler = PairScheduler::new(&clock);
+
+        let interval_pair = BackupPair::new("C:\\a", "D:\\a");
+        let mut nightly_pair = BackupPair::new("C:\\b", "D:\\b");
+        nightly_pair.schedule = Some(daily("02:00"));
+        let pairs = vec![interval_pair.clone(), nightly_pair.clone()];
+        let global = ScheduleConfig::interval(3600);
+
+        // El intervalo corre al arrancar; el diario espera a su hora
+        assert_eq!(scheduler.due_pairs(&pairs, &global), vec![interval_pair.id.clone()]);
+        scheduler.mark_ran(&interval_pair.id, true);
+        assert!(scheduler.due_pairs(&pairs, &global).is_empty());
+
+        clock.0.set(madrid(2024, 1, 11, 2, 0));
+        let mut due = scheduler.due_pairs(&pairs, &global);
+        due.sort();
+        let mut expected = vec![interval_pair.id.clone(), nightly_pair.id.clone()];
+        expected.sort();
+        assert_eq!(due, expected);
+        assert_eq!(scheduler.next_run_at(&nightly_pair.id).unwrap(), madrid(2024, 1, 11, 2, 0));
+    }
+
+    #[test]
+    fn test_catch_up_of_missed_runs() {
+        let clock = ManualClock(Cell::new(madrid(2024, 1, 10, 8, 0)));
+        let mut scheduler = PairScheduler::new(&clock);
+        scheduler.set_catch_up(&CatchUpPolicy { enabled: true, grace_delay_seconds: 120 });
+
+        let hourly_pair = BackupPair::new("C:\\a", "D:\\a");
+        let mut nightly_pair = BackupPair::new("C:\\b", "D:\\b");
+        nightly_pair.schedule = Some(daily("02:00"));
+        let pairs = vec![hourly_pair.clone(), nightly_pair.clone()];
+        let global = ScheduleConfig::interval(3600);
+
+        // Horario: corrió hace 30 min → no se repite al arrancar; nocturno: se perdió el de hoy
+        scheduler.restore_last_runs(HashMap::from([
+            (hourly_pair.id.clone(), madrid(2024, 1, 10, 7, 30).timestamp()),
+            (nightly_pair.id.clone(), madrid(2024, 1, 9, 2, 0).timestamp()),
+        ]));
+        assert!(scheduler.due_pairs(&pairs, &global).is_empty());
+        assert_eq!(scheduler.next_run_at(&hourly_pair.id).unwrap(), madrid(2024, 1, 10, 8, 30));
+        assert_eq!(scheduler.next_run_at(&nightly_pair.id).unwrap(), madrid(2024, 1, 10, 8, 2));
+
+        clock.0.set(madrid(2024, 1, 10, 8, 2));
+        assert_eq!(scheduler.due_pairs(&pairs, &global), vec![nightly_pair.id.clone()]);
+        scheduler.mark_ran(&nightly_pair.id, true);
+        scheduler.mark_ran(&hourly_pair.id, true);
+
+        // Suspensión de 8:02 a 15:00: el horario se recupera tras la espera de gracia;
+        // el nocturno ya corrió hoy y sigue esperando a las 2:00 de mañana
+        clock.0.set(madrid(2024, 1, 10, 15, 0));
+        scheduler.reschedule_after_clock_jump();
+        assert_eq!(scheduler.next_run_at(&hourly_pair.id).unwrap(), madrid(2024, 1, 10, 15, 2));
+        assert_eq!(scheduler.next_run_at(&nightly_pair.id).unwrap(), madrid(2024, 1, 11, 2, 0));
+
+        // El nocturno falla y el equipo se suspende: se recupera como si no hubiera corrido
+        clock.0.set(madrid(2024, 1, 11, 2, 0));
+        scheduler.mark_ran(&nightly_pair.id, false);
+        clock.0.set(madrid(2024, 1, 11, 9, 0));
+        scheduler.reschedule_after_clock_jump();
+        assert_eq!(scheduler.next_run_at(&nightly_pair.id).unwrap(), madrid(2024, 1, 11, 9, 2));
+    }
+}
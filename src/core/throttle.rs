@@ -0,0 +1,210 @@
+//! Módulo de throttling - limita el ancho de banda de los backups
+//! Robocopy usa `/IPG` (pausa entre bloques) y el motor nativo un token bucket
+//! El bucket vuelve a mirar la ventana horaria mientras copia; `/IPG` se fija al lanzar robocopy
+//! y solo cambia entre reintentos
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Timelike;
+
+use crate::core::config::BandwidthLimit;
+
+/// Tamaño de bloque que robocopy usa al aplicar `/IPG` (64 KB)
+const ROBOCOPY_BLOCK_BYTES: f64 = 65536.0;
+
+/// Token bucket clásico: acumula `rate` bytes por segundo hasta `capacity`
+#[derive(Debug)]
+pub struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+    /// Límite con ventana horaria: el ritmo se recalcula en cada reposición (None = ritmo fijo)
+    window: Option<BandwidthLimit>,
+}
+
+impl TokenBucket {
+    /// Crear bucket para `bytes_per_sec`, con ráfaga máxima de un segundo
+    pub fn new(bytes_per_sec: f64) -> Self {
+        let rate = bytes_per_sec.max(1.0);
+        Self {
+            rate,
+            capacity: rate.max(ROBOCOPY_BLOCK_BYTES),
+            tokens: 0.0,
+            last_refill: Instant::now(),
+            window: None,
+        }
+    }
+
+    /// Bucket que sigue la ventana horaria de `limit` (None si el límite está desactivado)
+    /// Una copia que empieza antes de la ventana queda limitada en cuanto esta se abre
+    pub fn scheduled(limit: &BandwidthLimit) -> Option<Self> {
+        let mb = limit.max_mb_per_sec.filter(|mb| *mb > 0.0)?;
+        Some(Self {
+            window: Some(limit.clone()),
+            ..Self::new(mb_to_bytes(mb))
+        })
+    }
+
+    /// Consumir `bytes` y devolver cuánto hay que esperar para respetar el límite
+    pub fn reserve(&mut self, bytes: u64, now: Instant) -> Duration {
+        self.reserve_at(bytes, now, current_hour())
+    }
+
+    fn reserve_at(&mut self, bytes: u64, now: Instant, hour: u32) -> Duration {
+        if let Some(window) = &self.window {
+            let Some(mb) = window.limit_at_hour(hour) else {
+                // Fuera de la ventana no se limita ni se acumula crédito para después
+                self.tokens = 0.0;
+                self.last_refill = now;
+                return Duration::ZERO;
+            };
+            self.rate = mb_to_bytes(mb).max(1.0);
+            self.capacity = self.rate.max(ROBOCOPY_BLOCK_BYTES);
+        }
+
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+
+        // Se permite deuda: el tiempo de espera compensa el déficit
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// Conjunto de límites (por pair y global) aplicados a una copia nativa
+/// El bucket global se comparte entre todos los pairs que corren en paralelo
+#[derive(Debug, Clone, Default)]
+pub struct Throttle {
+    buckets: Vec<Arc<Mutex<TokenBucket>>>,
+}
+
+impl Throttle {
+    /// Throttle sin límites
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Agregar un límite con su ventana horaria (None = sin límite)
+    pub fn with_limit(mut self, limit: Option<&BandwidthLimit>) -> Self {
+        if let Some(bucket) = limit.and_then(TokenBucket::scheduled) {
+            self.buckets.push(Arc::new(Mutex::new(bucket)));
+        }
+        self
+    }
+
+    /// Agregar un bucket compartido (límite global)
+    pub fn with_shared(mut self, bucket: Option<Arc<Mutex<TokenBucket>>>) -> Self {
+        if let Some(bucket) = bucket {
+            self.buckets.push(bucket);
+        }
+        self
+    }
+
+    pub fn is_limited(&self) -> bool {
+        !self.buckets.is_empty()
+    }
+
+    /// Bloquear el thread actual el tiempo necesario tras transferir `bytes`
+    pub fn consume(&self, bytes: u64) {
+        for bucket in &self.buckets {
+            let wait = match bucket.lock() {
+                Ok(mut bucket) => bucket.reserve(bytes, Instant::now()),
+                Err(_) => Duration::ZERO,
+            };
+            if !wait.is_zero() {
+                std::thread::sleep(wait);
+            }
+        }
+    }
+}
+
+/// Crear bucket compartido para el límite global (None = límite desactivado)
+/// Se crea aunque la ventana aún no esté abierta: la ejecución puede entrar en ella
+pub fn shared_bucket(limit: &BandwidthLimit) -> Option<Arc<Mutex<TokenBucket>>> {
+    TokenBucket::scheduled(limit).map(|bucket| Arc::new(Mutex::new(bucket)))
+}
+
+/// Calcular el valor de `/IPG:n` (ms entre bloques de 64 KB) para un límite en MB/s
+/// Aproximación: ignora el tiempo de transferencia del bloque, así que nunca supera el límite
+pub fn robocopy_inter_packet_gap_ms(mb_per_sec: f64) -> u32 {
+    (ROBOCOPY_BLOCK_BYTES * 1000.0 / mb_to_bytes(mb_per_sec)).ceil() as u32
+}
+
+/// Hora local actual (0-23) para evaluar ventanas horarias de límites
+pub fn current_hour() -> u32 {
+    chrono::Local::now().hour()
+}
+
+fn mb_to_bytes(mb_per_sec: f64) -> f64 {
+    mb_per_sec * 1024.0 * 1024.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket_waits_when_over_rate() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(1000.0);
+        bucket.last_refill = start;
+
+        // Sin tokens acumulados: 500 bytes a 1000 B/s requieren medio segundo
+        assert_eq!(bucket.reserve(500, start), Duration::from_millis(500));
+
+        // Dos segundos después la deuda está pagada y sobra crédito
+        let later = start + Duration::from_millis(2000);
+        assert_eq!(bucket.reserve(1000, later), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_scheduled_bucket_follows_window() {
+        let limit = BandwidthLimit { max_mb_per_sec: Some(1.0), active_from_hour: Some(9), active_until_hour: Some(18) };
+        let mut bucket = TokenBucket::scheduled(&limit).unwrap();
+        let start = Instant::now();
+
+        // 8:59 sin límite; en cuanto son las 9 la misma copia pasa a 1 MB/s
+        assert_eq!(bucket.reserve_at(10 * 1024 * 1024, start, 8), Duration::ZERO);
+        assert_eq!(bucket.reserve_at(512 * 1024, start, 9), Duration::from_millis(500));
+        assert!(TokenBucket::scheduled(&BandwidthLimit::default()).is_none());
+    }
+
+    #[test]
+    fn test_bandwidth_limit_windows() {
+        let window = |from, until| BandwidthLimit {
+            max_mb_per_sec: Some(5.0),
+            active_from_hour: from,
+            active_until_hour: until,
+        };
+
+        // 22h → 6h cruza medianoche
+        assert_eq!(window(Some(22), Some(6)).limit_at_hour(23), Some(5.0));
+        assert_eq!(window(Some(22), Some(6)).limit_at_hour(12), None);
+        assert_eq!(window(Some(22), Some(6)).describe(), "5 MB/s de 22h a 6h");
+
+        // Misma hora de inicio y fin: todo el día
+        assert_eq!(window(Some(8), Some(8)).limit_at_hour(3), Some(5.0));
+        assert_eq!(window(Some(8), Some(8)).limit_at_hour(8), Some(5.0));
+        assert_eq!(window(Some(8), Some(8)).describe(), "5 MB/s");
+
+        // Ventanas abiertas por un lado
+        assert_eq!(window(Some(22), None).limit_at_hour(21), None);
+        assert_eq!(window(Some(22), None).describe(), "5 MB/s desde 22h");
+        assert_eq!(window(None, Some(6)).limit_at_hour(5), Some(5.0));
+        assert_eq!(window(None, Some(6)).describe(), "5 MB/s hasta 6h");
+    }
+
+    #[test]
+    fn test_robocopy_ipg() {
+        // 1 MB/s → 64 KB cada 62.5 ms
+        assert_eq!(robocopy_inter_packet_gap_ms(1.0), 63);
+        assert_eq!(robocopy_inter_packet_gap_ms(10.0), 7);
+    }
+}
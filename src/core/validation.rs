@@ -0,0 +1,316 @@
+//! Módulo de validación de configuración - un reporte con todos los problemas, no solo el primero
+//! Rutas de cada pair (con `PathValidator`), schedules, intervalo, motor y opciones incompatibles.
+//! Se usa al cargar config.json, al importar, al recargar el archivo editado y con `--validate`
+
+use std::collections::HashSet;
+use tracing::{info, warn};
+
+use crate::core::config::{AppConfig, BackupEngine, BackupPair, BandwidthLimit};
+use crate::core::migration::CURRENT_CONFIG_VERSION;
+use crate::core::path_validation::PathValidator;
+
+/// Intervalo por debajo del cual el daemon revisa casi sin pausa
+const MIN_SENSIBLE_INTERVAL_SECONDS: u64 = 60;
+/// Intervalo por encima del cual los backups son tan espaciados que parece un error de tipeo
+const MAX_SENSIBLE_INTERVAL_SECONDS: u64 = 30 * 24 * 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Qué se validó; los problemas de la máquina (rutas, motor) no bloquean la configuración
+/// porque se resuelven sin tocar el archivo (la carpeta puede no existir todavía)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    Paths,
+    Engine,
+    Schedule,
+    Options,
+}
+
+/// Un problema encontrado en la configuración
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// Pair afectado (None = ajuste global)
+    pub pair_id: Option<String>,
+    /// Qué se validó ("Pair #2: origen → destino", "Schedule global"...)
+    pub subject: String,
+    pub message: String,
+    pub severity: Severity,
+    pub kind: IssueKind,
+}
+
+impl ValidationIssue {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Error que impide usar la configuración (schedules u opciones inválidas)
+    pub fn is_blocking(&self) -> bool {
+        self.is_error() && matches!(self.kind, IssueKind::Schedule | IssueKind::Options)
+    }
+
+    /// Línea para logs y consola
+    pub fn describe(&self) -> String {
+        let icon = if self.is_error() { "❌" } else { "⚠️" };
+        format!("{} {}: {}", icon, self.subject, self.message)
+    }
+}
+
+/// Resultado de validar una configuración completa
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.is_error()).count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.issues.len() - self.error_count()
+    }
+
+    /// Errores que impiden usar la configuración
+    pub fn blocking(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.is_blocking())
+    }
+
+    /// Problemas de los ajustes globales
+    pub fn global(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.pair_id.is_none())
+    }
+
+    /// Problemas de un pair
+    pub fn for_pair<'a>(&'a self, pair_id: &'a str) -> impl Iterator<Item = &'a ValidationIssue> {
+        self.issues.iter().filter(move |issue| issue.pair_id.as_deref() == Some(pair_id))
+    }
+
+    /// El pair tiene algún error (se importa deshabilitado)
+    pub fn pair_has_errors(&self, pair_id: &str) -> bool {
+        self.for_pair(pair_id).any(ValidationIssue::is_error)
+    }
+
+    /// "2 errores, 1 advertencia"
+    pub fn summary(&self) -> String {
+        let (errors, warnings) = (self.error_count(), self.warning_count());
+        format!(
+            "{} {}, {} {}",
+            errors, if errors == 1 { "error" } else { "errores" },
+            warnings, if warnings == 1 { "advertencia" } else { "advertencias" },
+        )
+    }
+
+    /// Escribir el reporte en el log
+    pub fn log(&self, context: &str) {
+        if self.is_clean() {
+            info!("✅ {}: configuración válida", context);
+            return;
+        }
+        warn!("⚠️ {}: {}", context, self.summary());
+        for issue in &self.issues {
+            warn!("   {}", issue.describe());
+        }
+    }
+
+    fn push(&mut self, pair: Option<&BackupPair>, subject: &str, message: impl Into<String>, severity: Severity, kind: IssueKind) {
+        self.issues.push(ValidationIssue {
+            pair_id: pair.map(|pair| pair.id.clone()),
+            subject: subject.to_string(),
+            message: message.into(),
+            severity,
+            kind,
+        });
+    }
+}
+
+/// Validar la configuración completa
+pub fn validate(config: &AppConfig) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    validate_global(config, &mut report);
+    validate_engine(config, robocopy_available(), &mut report);
+
+    let mut ids = HashSet::new();
+    for (index, pair) in config.backup_pairs.iter().enumerate() {
+        validate_pair(config, index, pair, &mut report);
+        if !ids.insert(pair.id.as_str()) {
+            report.push(Some(pair), &pair_subject(index, pair), "Id repetido: otro pair usa el mismo id (historial y estado se mezclarían)", Severity::Error, IssueKind::Options);
+        }
+    }
+    report
+}
+
+fn validate_global(config: &AppConfig, report: &mut ValidationReport) {
+    // Cargada sin migrar: no se guarda (`AppConfig::ensure_writable`) y no debe usarse como si nada
+    if config.config_version > CURRENT_CONFIG_VERSION {
+        report.push(None, "Versión", format!(
+            "config.json es de una versión más nueva de RustyVault (v{} > v{}): queda en solo lectura para no perder sus ajustes",
+            config.config_version, CURRENT_CONFIG_VERSION,
+        ), Severity::Error, IssueKind::Options);
+    }
+    if config.backup_pairs.is_empty() {
+        report.push(None, "Backup pairs", "No hay backup pairs configurados", Severity::Warning, IssueKind::Options);
+    } else if config.enabled_pairs().is_empty() {
+        report.push(None, "Backup pairs", "Todos los backup pairs están deshabilitados", Severity::Warning, IssueKind::Options);
+    }
+
+    // El intervalo solo rige sin schedule global
+    if config.schedule.is_none() {
+        let seconds = config.check_interval_seconds;
+        if seconds == 0 {
+            report.push(None, "Intervalo", "El intervalo debe ser de al menos 1 segundo", Severity::Error, IssueKind::Schedule);
+        } else if seconds < MIN_SENSIBLE_INTERVAL_SECONDS {
+            report.push(None, "Intervalo", format!("{}s es muy poco: el daemon revisaría casi sin pausa", seconds), Severity::Warning, IssueKind::Schedule);
+        } else if seconds > MAX_SENSIBLE_INTERVAL_SECONDS {
+            report.push(None, "Intervalo", format!("{}s son más de 30 días entre backups", seconds), Severity::Warning, IssueKind::Schedule);
+        }
+    }
+    if let Err(e) = config.effective_schedule().compile() {
+        report.push(None, "Schedule global", e.to_string(), Severity::Error, IssueKind::Schedule);
+    }
+
+    let threads = config.robocopy.multithreading;
+    if !(1..=128).contains(&threads) {
+        report.push(None, "Robocopy", format!("Threads (/MT) debe estar entre 1 y 128 (es {})", threads), Severity::Error, IssueKind::Options);
+    }
+    if config.max_parallel_backups == 0 {
+        report.push(None, "Pairs en paralelo", "Debe ser al menos 1", Severity::Error, IssueKind::Options);
+    }
+    validate_bandwidth(&config.bandwidth_limit, None, "Límite de ancho de banda", report);
+
+    let retry = &config.pair_retry;
+    if retry.max_attempts == 0 {
+        report.push(None, "Reintentos", "max_attempts debe ser al menos 1 (1 = sin reintentos)", Severity::Error, IssueKind::Options);
+    }
+    if retry.initial_delay_seconds > retry.max_delay_seconds {
+        report.push(None, "Reintentos", format!(
+            "La espera inicial ({}s) supera la máxima ({}s): todas las esperas serán de {}s",
+            retry.initial_delay_seconds, retry.max_delay_seconds, retry.max_delay_seconds,
+        ), Severity::Warning, IssueKind::Options);
+    }
+}
+
+/// Robocopy configurado pero ausente: fuera de Windows se usa el motor nativo, en Windows el backup falla
+fn validate_engine(config: &AppConfig, robocopy_available: bool, report: &mut ValidationReport) {
+    if config.engine != BackupEngine::Robocopy {
+        return;
+    }
+    if !robocopy_available {
+        if cfg!(windows) {
+            report.push(None, "Motor", "robocopy no está disponible en esta máquina - usa \"engine\": \"native\"", Severity::Error, IssueKind::Engine);
+        } else {
+            report.push(None, "Motor", "robocopy solo existe en Windows - se usará el motor nativo", Severity::Warning, IssueKind::Engine);
+        }
+        return;
+    }
+
+    // Con límite de ancho de banda robocopy usa /IPG, que copia en un solo hilo
+    let limited = config.bandwidth_limit.max_mb_per_sec.is_some_and(|mb| mb > 0.0)
+        || config.backup_pairs.iter().any(|pair| pair.bandwidth_limit.as_ref().is_some_and(|limit| limit.max_mb_per_sec.is_some_and(|mb| mb > 0.0)));
+    if limited && config.robocopy.multithreading > 1 {
+        report.push(None, "Robocopy", format!(
+            "Con límite de ancho de banda se copia en un solo hilo: los {} threads (/MT) no se usan mientras rige el límite",
+            config.robocopy.multithreading,
+        ), Severity::Warning, IssueKind::Options);
+    }
+}
+
+fn robocopy_available() -> bool {
+    cfg!(windows) && crate::system::process::is_robocopy_available()
+}
+
+fn validate_pair(config: &AppConfig, index: usize, pair: &BackupPair, report: &mut ValidationReport) {
+    let subject = pair_subject(index, pair);
+    let validation = PathValidator::validate_backup_pair(
+        &pair.source.to_string_lossy(),
+        &pair.destination.to_string_lossy(),
+        &config.backup_pairs,
+        Some(index),
+    );
+    // Un pair deshabilitado no se ejecuta: sus rutas inválidas son solo advertencias
+    let path_severity = if pair.enabled { Severity::Error } else { Severity::Warning };
+    for message in validation.get_error_messages() {
+        report.push(Some(pair), &subject, message, path_severity, IssueKind::Paths);
+    }
+    for message in validation.get_warning_messages() {
+        report.push(Some(pair), &subject, message, Severity::Warning, IssueKind::Paths);
+    }
+
+    if let Some(schedule) = &pair.schedule {
+        if let Err(e) = schedule.compile() {
+            report.push(Some(pair), &format!("Schedule del pair #{}", index + 1), e.to_string(), Severity::Error, IssueKind::Schedule);
+        } else if pair.continuous.is_some() {
+            report.push(Some(pair), &subject, "El modo continuo ignora el schedule del pair mientras la vigilancia funciona", Severity::Warning, IssueKind::Options);
+        }
+    }
+    if let Some(limit) = &pair.bandwidth_limit {
+        validate_bandwidth(limit, Some(pair), &subject, report);
+    }
+}
+
+fn validate_bandwidth(limit: &BandwidthLimit, pair: Option<&BackupPair>, subject: &str, report: &mut ValidationReport) {
+    if limit.max_mb_per_sec.is_some_and(|mb| mb.is_nan() || mb < 0.0) {
+        report.push(pair, subject, "El límite en MB/s no puede ser negativo", Severity::Error, IssueKind::Options);
+    }
+    for hour in [limit.active_from_hour, limit.active_until_hour].into_iter().flatten() {
+        if hour > 23 {
+            report.push(pair, subject, format!("Hora {} fuera de rango (0-23)", hour), Severity::Error, IssueKind::Options);
+        }
+    }
+}
+
+fn pair_subject(index: usize, pair: &BackupPair) -> String {
+    format!("Pair #{}: {} → {}", index + 1, pair.source.display(), pair.destination.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ContinuousMode;
+    use crate::core::schedule::{Schedule, ScheduleConfig};
+
+    #[test]
+    fn test_report_collects_every_problem_by_pair() {
+        let base = std::env::temp_dir().join(format!("rustyvault_validation_{}", std::process::id()));
+        let source = base.join("src");
+        std::fs::create_dir_all(&source).unwrap();
+
+        let good = BackupPair::with_id("good".to_string(), source.clone(), base.join("dst"));
+        let missing = BackupPair::with_id("missing".to_string(), base.join("nope"), base.join("dst2"));
+        let mut bad_schedule = BackupPair::with_id("sched".to_string(), source.clone(), base.join("dst3"));
+        bad_schedule.schedule = Some(ScheduleConfig { schedule: Schedule::Daily { time: "25:99".to_string() }, allowed_windows: Vec::new() });
+        let mut continuous = BackupPair::with_id("cont".to_string(), source.clone(), base.join("dst4"));
+        continuous.continuous = Some(ContinuousMode::default());
+        continuous.schedule = Some(ScheduleConfig::interval(600));
+
+        let config = AppConfig {
+            backup_pairs: vec![good, missing, bad_schedule, continuous],
+            check_interval_seconds: 0,
+            max_parallel_backups: 0,
+            engine: BackupEngine::Native,
+            ..AppConfig::default()
+        };
+        let report = validate(&config);
+        let _ = std::fs::remove_dir_all(&base);
+
+        // Todos los problemas, no solo el primero
+        assert!(report.pair_has_errors("missing"));
+        assert!(report.pair_has_errors("sched"));
+        assert!(!report.pair_has_errors("good"));
+        assert!(!report.pair_has_errors("cont"));
+        assert!(report.for_pair("cont").any(|issue| issue.kind == IssueKind::Options));
+        assert!(report.global().any(|issue| issue.subject == "Intervalo" && issue.is_error()));
+        assert!(report.global().any(|issue| issue.subject == "Pairs en paralelo"));
+
+        // Rutas inexistentes no bloquean; schedules y opciones sí
+        assert!(report.for_pair("missing").all(|issue| !issue.is_blocking()));
+        assert_eq!(report.blocking().count(), 3);
+    }
+}
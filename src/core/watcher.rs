@@ -0,0 +1,300 @@
+//! Módulo de vigilancia - backups disparados por cambios en el origen (modo continuo)
+//! Usa notificaciones del sistema (ReadDirectoryChangesW en Windows, inotify en Linux)
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, error, info, warn};
+
+use crate::core::config::BackupPair;
+
+/// Margen tras el pre-hook de un pair: sus últimos eventos pueden llegar un poco después de que termine
+const PRE_HOOK_EVENT_GRACE: Duration = Duration::from_millis(500);
+
+/// Señales que el callback de notify envía al daemon
+#[derive(Debug)]
+enum WatchSignal {
+    /// Hubo cambios en el origen del pair (con el momento en que llegó el evento)
+    Changed(String, Instant),
+    /// Se perdieron eventos (overflow o error): el watch ya no es confiable
+    Degraded(String),
+}
+
+/// Watch activo sobre el origen de un pair
+struct WatchedPair {
+    _watcher: RecommendedWatcher,
+    source: PathBuf,
+    quiet_period: Duration,
+    /// Primer cambio pendiente de copiar (para no esperar indefinidamente)
+    first_change: Option<Instant>,
+    /// Último cambio recibido (para el quiet period)
+    last_change: Option<Instant>,
+    /// Watch no confiable: el pair vuelve al intervalo hasta re-armarse
+    degraded: bool,
+}
+
+/// Vigila el origen de los pairs en modo continuo y agrupa ráfagas de cambios
+pub struct SourceWatcher {
+    watched: HashMap<String, WatchedPair>,
+    /// Pairs cuyo watch no pudo crearse; se reintenta solo al re-armar
+    unavailable: HashSet<String>,
+    /// Cambios de cada pair anteriores a este momento ya los recogió una copia (los de su propio pre-hook incluidos)
+    ignore_until: HashMap<String, Instant>,
+    sender: Sender<WatchSignal>,
+    receiver: Receiver<WatchSignal>,
+}
+
+impl Default for SourceWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourceWatcher {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            watched: HashMap::new(),
+            unavailable: HashSet::new(),
+            ignore_until: HashMap::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Sincronizar los watches con la configuración actual
+    /// - Agrega watches para pairs continuos nuevos y quita los que ya no aplican
+    /// - Con `rearm_degraded`, vuelve a crear los watches degradados (tras una ejecución por intervalo)
+    /// - Se vigila la ruta expandida (`path_vars`); si no se puede expandir, el pair usa el intervalo
+    pub fn sync(&mut self, pairs: &[BackupPair], rearm_degraded: bool) {
+        if rearm_degraded {
+            self.unavailable.clear();
+        }
+
+        let resolved: Vec<BackupPair> = pairs
+            .iter()
+            .filter(|pair| pair.enabled && pair.continuous.is_some())
+            .filter_map(|pair| match pair.resolved() {
+                Ok(resolved) => Some(resolved),
+                Err(e) => {
+                    if self.unavailable.insert(pair.id.clone()) {
+                        warn!("⚠️ No se puede vigilar el pair {} - se usará el intervalo: {}", pair.display_name(), e);
+                    }
+                    None
+                }
+            })
+            .collect();
+        let wanted: HashMap<&str, &BackupPair> = resolved
+            .iter()
+            .map(|pair| (pair.id.as_str(), pair))
+            .collect();
+
+        self.watched.retain(|id, watched| {
+            let keep = wanted
+                .get(id.as_str())
+                .is_some_and(|pair| {
+                    pair.source == watched.source
+                        && quiet_period_of(pair) == watched.quiet_period
+                        && !(rearm_degraded && watched.degraded)
+                });
+            if !keep {
+                debug!("👁️ Quitando watch del pair {}", id);
+            }
+            keep
+        });
+        let watched = &self.watched;
+        self.ignore_until.retain(|id, _| watched.contains_key(id));
+
+        for (id, pair) in wanted {
+            if self.watched.contains_key(id) || self.unavailable.contains(id) {
+                continue;
+            }
+            match self.watch_pair(pair) {
+                Some(watched) => {
+                    self.watched.insert(id.to_string(), watched);
+                }
+                None => {
+                    self.unavailable.insert(id.to_string());
+                }
+            }
+        }
+    }
+
+    /// Hay al menos un pair en modo continuo vigilado (hay que revisar sus quiet periods seguido)
+    pub fn has_watches(&self) -> bool {
+        !self.watched.is_empty()
+    }
+
+    /// El pair tiene un watch confiable (no necesita la ejecución por intervalo)
+    pub fn is_watched(&self, pair_id: &str) -> bool {
+        self.watched.get(pair_id).is_some_and(|watched| !watched.degraded)
+    }
+
+    /// El pre-hook del pair terminó y la copia empieza: se descartan los cambios recibidos hasta ahora
+    /// (el volcado que escribe el hook en el origen no debe disparar otra ejecución)
+    pub fn pre_hook_finished(&mut self, pair_id: &str, at: Instant) {
+        self.ignore_until.insert(pair_id.to_string(), at + PRE_HOOK_EVENT_GRACE);
+    }
+
+    /// Procesar eventos y devolver los pairs cuyo quiet period terminó
+    /// `max_wait` evita que cambios constantes posterguen el backup indefinidamente
+    pub fn due_pairs(&mut self, now: Instant, max_wait: Duration) -> Vec<String> {
+        while let Ok(signal) = self.receiver.try_recv() {
+            match signal {
+                WatchSignal::Changed(id, at) => {
+                    if self.ignore_until.get(&id).is_some_and(|until| at <= *until) {
+                        continue;
+                    }
+                    if let Some(watched) = self.watched.get_mut(&id) {
+                        watched.first_change.get_or_insert(at);
+                        watched.last_change = Some(watched.last_change.map_or(at, |last| last.max(at)));
+                    }
+                }
+                WatchSignal::Degraded(id) => {
+                    if let Some(watched) = self.watched.get_mut(&id) {
+                        if !watched.degraded {
+                            warn!("⚠️ Watch del pair {} perdió eventos - volviendo al intervalo", id);
+                        }
+                        watched.degraded = true;
+                        watched.first_change = None;
+                        watched.last_change = None;
+                    }
+                }
+            }
+        }
+
+        let mut due = Vec::new();
+        for (id, watched) in &mut self.watched {
+            let (Some(first), Some(last)) = (watched.first_change, watched.last_change) else {
+                continue;
+            };
+
+            let quiet = now.saturating_duration_since(last) >= watched.quiet_period;
+            let overdue = now.saturating_duration_since(first) >= max_wait;
+            if quiet || overdue {
+                watched.first_change = None;
+                watched.last_change = None;
+                due.push(id.clone());
+            }
+        }
+
+        due
+    }
+
+    fn watch_pair(&self, pair: &BackupPair) -> Option<WatchedPair> {
+        let quiet_period = quiet_period_of(pair);
+        let sender = self.sender.clone();
+        let id = pair.id.clone();
+
+        let handler = move |event: notify::Result<Event>| {
+            let signal = match event {
+                // inotify/FSEvents avisan del overflow con un evento de rescan
+                Ok(event) if event.need_rescan() => WatchSignal::Degraded(id.clone()),
+                // Lecturas no cambian el contenido
+                Ok(event) if matches!(event.kind, EventKind::Access(_)) => return,
+                Ok(_) => WatchSignal::Changed(id.clone(), Instant::now()),
+                Err(e) => {
+                    error!("❌ Error del watcher del pair {}: {}", id, e);
+                    WatchSignal::Degraded(id.clone())
+                }
+            };
+            let _ = sender.send(signal);
+        };
+
+        let result = notify::recommended_watcher(handler).and_then(|mut watcher| {
+            watcher.watch(&pair.source, RecursiveMode::Recursive)?;
+            Ok(watcher)
+        });
+
+        match result {
+            Ok(watcher) => {
+                info!("👁️ Modo continuo activo para {} (quiet period {}s)",
+                     pair.source.display(), quiet_period.as_secs());
+                Some(WatchedPair {
+                    _watcher: watcher,
+                    source: pair.source.clone(),
+                    quiet_period,
+                    first_change: None,
+                    last_change: None,
+                    degraded: false,
+                })
+            }
+            Err(e) => {
+                warn!("⚠️ No se pudo vigilar {} - se usará el intervalo: {}", pair.source.display(), e);
+                None
+            }
+        }
+    }
+}
+
+fn quiet_period_of(pair: &BackupPair) -> Duration {
+    pair.continuous
+        .as_ref()
+        .map(|mode| Duration::from_secs(mode.quiet_period_seconds))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ContinuousMode;
+
+    fn watched_temp_dir(quiet_period_seconds: u64) -> (PathBuf, BackupPair, SourceWatcher) {
+        let dir = std::env::temp_dir().join(format!("rustyvault-watch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut pair = BackupPair::new(dir.clone(), dir.join("..").join("dest"));
+        pair.continuous = Some(ContinuousMode { quiet_period_seconds });
+
+        let mut watcher = SourceWatcher::new();
+        watcher.sync(std::slice::from_ref(&pair), false);
+        assert!(watcher.is_watched(&pair.id));
+        (dir, pair, watcher)
+    }
+
+    #[test]
+    fn test_changes_trigger_after_quiet_period() {
+        let (dir, mut pair, mut watcher) = watched_temp_dir(60);
+        let max_wait = Duration::from_secs(3600);
+
+        std::fs::write(dir.join("doc.txt"), b"v1").unwrap();
+
+        // Dentro del quiet period todavía no toca (se espera a que llegue el evento, sin tiempos fijos)
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while watcher.watched[&pair.id].last_change.is_none() && Instant::now() < deadline {
+            assert!(watcher.due_pairs(Instant::now(), max_wait).is_empty());
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(watcher.due_pairs(Instant::now(), max_wait).is_empty());
+        assert_eq!(watcher.due_pairs(Instant::now() + Duration::from_secs(120), max_wait), vec![pair.id.clone()]);
+
+        // Pair sin modo continuo: el watch se quita
+        pair.continuous = None;
+        watcher.sync(std::slice::from_ref(&pair), false);
+        assert!(!watcher.is_watched(&pair.id));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pre_hook_changes_do_not_retrigger() {
+        let (dir, pair, mut watcher) = watched_temp_dir(1);
+        let max_wait = Duration::from_secs(3600);
+        let hook_started = Instant::now();
+
+        // El pre-hook vuelca la base de datos en el origen durante la ejecución
+        watcher.sender.send(WatchSignal::Changed(pair.id.clone(), hook_started)).unwrap();
+        watcher.pre_hook_finished(&pair.id, hook_started + Duration::from_millis(10));
+        assert!(watcher.due_pairs(hook_started + Duration::from_secs(60), max_wait).is_empty());
+
+        // Un cambio posterior del usuario sí dispara otra copia
+        let edited = hook_started + Duration::from_secs(5);
+        watcher.sender.send(WatchSignal::Changed(pair.id.clone(), edited)).unwrap();
+        assert_eq!(watcher.due_pairs(edited + Duration::from_secs(2), max_wait), vec![pair.id.clone()]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,14 +1,21 @@
 use anyhow::Result;
-use std::env;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use tracing_subscriber::{
     fmt,
     layer::SubscriberExt,
+    reload,
     util::SubscriberInitExt,
     EnvFilter,
     Layer,
+    Registry,
 };
 
+use crate::core::preferences::LogLevel;
+
+/// Filtro del archivo de log, ajustable en caliente desde las preferencias
+static FILE_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
 /// Setup del sistema de logging multi-target según PRD
 /// - Console: Todos los niveles (development)
 /// - File: Solo errores (production) 
@@ -25,13 +32,15 @@ pub fn setup_logging() -> Result<()> {
         .append(true)
         .open(&log_file)?;
     
-    // Layer para archivos - solo errores y warnings
+    // Layer para archivos - por defecto solo errores y warnings (ver `set_file_level`)
+    let (file_filter, file_filter_handle) = reload::Layer::new(EnvFilter::new(LogLevel::default().as_filter()));
+    let _ = FILE_FILTER.set(file_filter_handle);
     let file_layer = fmt::layer()
         .with_writer(file_appender)
         .with_ansi(false) // No ANSI codes en archivos
         .with_target(true)
         .with_thread_ids(true)
-        .with_filter(EnvFilter::new("warn")); // Solo warnings y errores
+        .with_filter(file_filter);
     
     // Layer para console - todos los niveles en desarrollo
     let console_layer = fmt::layer()
@@ -48,29 +57,29 @@ pub fn setup_logging() -> Result<()> {
     
     tracing::info!("📋 Sistema de logging configurado:");
     tracing::info!("  📁 Logs de errores: {}", log_dir.display());
+    tracing::info!("  ⚙️ Configuración: {} (modo {})", crate::core::paths::current().config_file.display(),
+                   crate::core::paths::current().mode.describe());
     tracing::info!("  🖥️ Console logging: {}", get_console_level());
     
     Ok(())
 }
 
+/// Cambiar el nivel del archivo de log (preferencia `log_level`, aplica de inmediato)
+pub fn set_file_level(level: LogLevel) {
+    let Some(handle) = FILE_FILTER.get() else {
+        return;
+    };
+    match handle.reload(EnvFilter::new(level.as_filter())) {
+        Ok(()) => tracing::info!("📋 Nivel del archivo de log: {}", level.as_filter()),
+        Err(e) => tracing::warn!("⚠️ No se pudo cambiar el nivel del log: {}", e),
+    }
+}
+
 /// Determina el directorio para logs
-/// Prioridad: carpeta del ejecutable > carpeta temp
+/// Portable: carpeta del ejecutable; instalado: carpeta local del usuario; con --config o
+/// RUSTYVAULT_CONFIG: junto al config.json elegido
 fn get_log_directory() -> Result<PathBuf> {
-    // Intentar usar carpeta del ejecutable primero
-    if let Ok(exe_path) = env::current_exe() {
-        if let Some(exe_dir) = exe_path.parent() {
-            return Ok(exe_dir.to_path_buf());
-        }
-    }
-    
-    // Fallback a carpeta temporal del usuario
-    if let Some(temp_dir) = dirs::cache_dir() {
-        let log_dir = temp_dir.join("RobocopyBackupTool");
-        return Ok(log_dir);
-    }
-    
-    // Último fallback a directorio actual
-    Ok(PathBuf::from("."))
+    Ok(crate::core::paths::current().log_dir.clone())
 }
 
 /// Filtro para console según environment
@@ -15,6 +15,12 @@ use logging::setup_logging;
 
 /// Entry point principal de la aplicación RustyVault
 fn main() -> Result<()> {
+    // Parse command line arguments
+    let args: Vec<String> = env::args().collect();
+    
+    // Ubicación de config.json y logs: debe resolverse antes de abrir el log
+    crate::core::paths::init(config_path_arg(&args)?);
+    
     // Setup logging system
     setup_logging()?;
     
@@ -22,16 +28,26 @@ fn main() -> Result<()> {
     // Esto es necesario para que funcionen los eventos del system tray en Windows
     info!("🔧 Inicializando sistema de eventos del tray...");
     
-    // Parse command line arguments
-    let args: Vec<String> = env::args().collect();
     let start_daemon = args.contains(&"--start-daemon".to_string());
     let show_help = args.contains(&"--help".to_string());
+    let backup_now = args.contains(&"--backup-now".to_string());
+    let validate = args.get(1).is_some_and(|arg| arg == "validate") || args.contains(&"--validate".to_string());
     
     if show_help {
         show_help_message();
         return Ok(());
     }
     
+    let profile = option_arg(&args, "--profile")?;
+    
+    if validate {
+        return run_cli_validate(profile.as_deref());
+    }
+    
+    if backup_now {
+        return run_cli_backup(profile.as_deref());
+    }
+    
     info!("🚀 Iniciando RustyVault v2.0");
     info!("👤 Desarrollado por Damian Naone");
     
@@ -56,7 +72,7 @@ fn main() -> Result<()> {
             setup_custom_style(&cc.egui_ctx);
             
             // Crear app con flag de auto-start daemon
-            Ok(Box::new(BackupApp::new(cc, start_daemon)))
+            Ok(Box::new(BackupApp::new(cc, start_daemon, profile)))
         }),
     ) {
         error!("❌ Error al iniciar la aplicación GUI: {}", e);
@@ -67,6 +83,135 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Ejecuta un backup de todos los pairs habilitados sin abrir la GUI
+/// Termina con código 1 si algún pair falló (útil para el Programador de tareas)
+/// Con `--profile` se ejecuta ese perfil sin cambiar el activo
+fn run_cli_backup(profile: Option<&str>) -> Result<()> {
+    use crate::core::events::EventBus;
+    use crate::core::runner::{BackupRunner, RunTrigger};
+    
+    let config = crate::core::AppConfig::load().map_err(|e| match crate::core::AppConfig::latest_valid_backup() {
+        Some(backup) => e.context(format!("Hay una copia válida en {} - abre RustyVault para restaurarla", backup.display())),
+        None => e,
+    })?;
+    let config = match profile {
+        Some(name) => crate::core::profiles::ProfileStore::open().resolve(&config, name)?,
+        None => config,
+    };
+    info!("👤 Perfil: {}", config.profile);
+    // Igual que al recargar o importar: schedules u opciones inválidas no se ejecutan
+    let report = config.validate();
+    if report.blocking().next().is_some() {
+        for issue in report.blocking() {
+            error!("{}", issue.describe());
+        }
+        error!("❌ config.json tiene errores - corrígelos (ver `rusty-vault.exe validate`)");
+        std::process::exit(1);
+    }
+    crate::logging::set_file_level(config.ui_preferences.log_level);
+    crate::system::notifications::configure(&config.ui_preferences);
+    let pairs = config.enabled_pairs();
+    if pairs.is_empty() {
+        warn!("⚠️ No hay backup pairs habilitados - nada que hacer");
+        return Ok(());
+    }
+    
+    let events = EventBus::new();
+    crate::system::notifications::subscribe(&events);
+    crate::core::history::HistoryStore::subscribe(&events);
+    
+    let summary = BackupRunner::new(&config, RunTrigger::Cli).with_events(&events).run(&pairs);
+    if summary.aborted.is_some() || summary.failures > 0 {
+        error!("❌ Backup CLI terminó con errores");
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Valida config.json (o el perfil indicado) y muestra todos los problemas encontrados
+/// No modifica nada en disco: sin crear config.json por defecto ni migrar archivos antiguos
+/// Termina con código 1 si hay errores o si el archivo no existe o no se puede leer
+fn run_cli_validate(profile: Option<&str>) -> Result<()> {
+    let config_path = &crate::core::paths::current().config_file;
+    let parsed = if config_path.exists() {
+        std::fs::read_to_string(config_path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| crate::core::AppConfig::from_json(&content))
+    } else {
+        Err(anyhow::anyhow!("No existe el archivo"))
+    };
+    let (config, migrated_from) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            println!("❌ {}: {:#}", config_path.display(), e);
+            std::process::exit(1);
+        }
+    };
+    let config = match profile {
+        Some(name) => crate::core::profiles::ProfileStore::open().resolve(&config, name)?,
+        None => config,
+    };
+    let report = config.validate();
+    
+    println!("📋 Validación de {} (perfil \"{}\")", config_path.display(), config.profile);
+    if let Some(version) = migrated_from {
+        println!("ℹ️ Archivo en formato v{} - se migrará al abrir RustyVault", version);
+    }
+    let global: Vec<_> = report.global().collect();
+    if !global.is_empty() {
+        println!();
+        println!("GLOBAL:");
+        for issue in global {
+            println!("  {}", issue.describe());
+        }
+    }
+    for (index, pair) in config.backup_pairs.iter().enumerate() {
+        let issues: Vec<_> = report.for_pair(&pair.id).collect();
+        if issues.is_empty() {
+            continue;
+        }
+        println!();
+        println!("PAIR #{} [{}] {} → {}", index + 1, pair.id, pair.source.display(), pair.destination.display());
+        for issue in issues {
+            println!("  {} {}", if issue.is_error() { "❌" } else { "⚠️" }, issue.message);
+        }
+    }
+    println!();
+    if report.is_clean() {
+        println!("✅ Configuración válida ({} pairs)", config.backup_pairs.len());
+    } else {
+        println!("Resultado: {}", report.summary());
+    }
+    
+    if report.error_count() > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Ruta pasada con `--config <ruta>` o `--config=<ruta>`
+fn config_path_arg(args: &[String]) -> Result<Option<std::path::PathBuf>> {
+    Ok(option_arg(args, "--config")?.map(Into::into))
+}
+
+/// Valor de una opción `--nombre <valor>` o `--nombre=<valor>`
+fn option_arg(args: &[String], name: &str) -> Result<Option<String>> {
+    let prefix = format!("{}=", name);
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            return Ok(Some(value.to_string()));
+        }
+        if arg == name {
+            return match iter.next() {
+                Some(value) => Ok(Some(value.clone())),
+                None => Err(anyhow::anyhow!("{} requiere un valor", name)),
+            };
+        }
+    }
+    Ok(None)
+}
+
 /// Muestra mensaje de ayuda CLI
 fn show_help_message() {
     println!("🔧 RustyVault v2.0 - Modern Backup Automation");
@@ -77,15 +222,28 @@ fn show_help_message() {
     println!();
     println!("OPCIONES:");
     println!("  --start-daemon    Auto-inicia el daemon de backup al abrir");
+    println!("  --backup-now      Ejecuta un backup de los pairs habilitados y sale (sin GUI)");
+    println!("  --config <ruta>   Usa este config.json (o la carpeta que lo contiene)");
+    println!("  --profile <nombre> Activa ese perfil al abrir (con --backup-now/--validate: solo ese perfil)");
+    println!("  --validate        Valida config.json (rutas, schedules, motor, opciones) y sale; también `validate`");
     println!("  --help           Muestra este mensaje de ayuda");
     println!();
     println!("CONFIGURACIÓN:");
-    println!("  La configuración se guarda en config.json junto al ejecutable");
+    println!("  config.json se busca en este orden:");
+    println!("    1. --config <ruta>");
+    println!("    2. Variable de entorno {}", crate::core::paths::CONFIG_ENV_VAR);
+    println!("    3. Junto al ejecutable (modo portable, si ya existe ahí)");
+    println!("    4. %APPDATA%\\RustyVault (Linux: $XDG_CONFIG_HOME/RustyVault) - modo instalado");
+    println!("  Historial y demás datos se guardan junto a config.json");
     println!("  Edita manualmente el archivo para configuraciones avanzadas");
     println!();
     println!("EJEMPLOS:");
     println!("  rusty-vault.exe                  # Abrir GUI normal");
     println!("  rusty-vault.exe --start-daemon   # Auto-start daemon");
+    println!("  rusty-vault.exe --backup-now     # Backup inmediato sin GUI");
+    println!("  rusty-vault.exe --config D:\\rv\\config.json --backup-now");
+    println!("  rusty-vault.exe --profile \"Full archive weekend\" --backup-now");
+    println!("  rusty-vault.exe validate         # Revisar la configuración sin abrir la GUI");
 }
 
 /// Carga el icono desde archivo ico.ico
@@ -144,6 +302,9 @@ fn load_icon() -> egui::IconData {
 
 /// Configurar estilo custom para egui - Dark Mode elegante
 fn setup_custom_style(ctx: &egui::Context) {
+    // Los temas se aplican al estilo oscuro; el claro es el de egui (ver preferencia `theme`)
+    ctx.set_theme(egui::Theme::Dark);
+    
     // 🎨 CAMBIO DE TEMA: Cambia esta línea para probar diferentes temas
     setup_theme_elegant_dark(ctx);
     
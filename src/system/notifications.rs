@@ -3,65 +3,170 @@
 
 use notify_rust::{Notification, Timeout};
 use anyhow::Result;
-use tracing::{info, error};
+use std::sync::Mutex;
+use tracing::{debug, info, error, warn};
+
+use crate::core::events::{AppEvent, EventBus};
+use crate::core::preferences::{Language, NotificationKind, NotificationPreferences, UiPreferences};
+use crate::core::runner::{RunSummary, RunTrigger};
+use crate::ui::components::format_duration;
+
+/// Preferencias de notificaciones vigentes (None = valores por defecto)
+static PREFERENCES: Mutex<Option<(NotificationPreferences, Language)>> = Mutex::new(None);
+
+/// Notificar el resultado de cada ejecución publicada en el bus
+pub fn subscribe(events: &EventBus) {
+    events.subscribe(|event| {
+        if let AppEvent::RunFinished { trigger, summary, .. } = event {
+            if let Err(e) = show_run_summary(*trigger, summary) {
+                warn!("⚠️ Error mostrando notificación: {}", e);
+            }
+        }
+    });
+}
+
+/// Notificación consolidada de una ejecución
+fn show_run_summary(trigger: RunTrigger, summary: &RunSummary) -> Result<()> {
+    let label = trigger.label();
+    let elapsed = format_duration(summary.duration.as_secs());
+    let language = current_preferences().1;
+
+    if let Some(reason) = &summary.aborted {
+        show_backup_failed(reason)
+    } else if summary.failures > 0 {
+        let msg = match language {
+            Language::Spanish => format!("{}: {} exitosos, {} advertencias, {} fallidos (en {})",
+                                         label, summary.success, summary.warnings, summary.failures, elapsed),
+            Language::English => format!("{}: {} succeeded, {} warnings, {} failed (in {})",
+                                         label, summary.success, summary.warnings, summary.failures, elapsed),
+        };
+        show_backup_failed(&msg)
+    } else if summary.warnings > 0 {
+        let msg = match language {
+            Language::Spanish => format!("{}: {} exitosos, {} advertencias (en {})", label, summary.success, summary.warnings, elapsed),
+            Language::English => format!("{}: {} succeeded, {} warnings (in {})", label, summary.success, summary.warnings, elapsed),
+        };
+        show_backup_warning(&msg)
+    } else if summary.success == 0 && summary.skipped > 0 {
+        // Nada cambió: no molestar con notificaciones
+        info!("💤 {}: {} pair(s) sin cambios - nada que copiar", label, summary.skipped);
+        Ok(())
+    } else {
+        info!("🎉 {}: {} backups completados exitosamente", label, summary.success);
+        let timing = match summary.throughput_mb_s() {
+            Some(mb_s) => format!("{} ({:.1} MB/s)", elapsed, mb_s),
+            None => elapsed,
+        };
+        match trigger {
+            // El modo continuo puede ejecutarse muy seguido: solo se notifican problemas
+            RunTrigger::Watch => Ok(()),
+            _ => show_backup_success(Some(summary.files_copied.min(u32::MAX as u64) as u32), Some(&timing)),
+        }
+    }
+}
+
+/// Aplicar las preferencias de notificaciones e idioma (al arrancar y al cambiarlas en Settings)
+pub fn configure(preferences: &UiPreferences) {
+    if let Ok(mut current) = PREFERENCES.lock() {
+        *current = Some((preferences.notifications.clone(), preferences.language));
+    }
+}
+
+fn current_preferences() -> (NotificationPreferences, Language) {
+    PREFERENCES
+        .lock()
+        .ok()
+        .and_then(|current| current.clone())
+        .unwrap_or_default()
+}
 
 /// Mostrar notificación de backup completado exitosamente
 pub fn show_backup_success(files_copied: Option<u32>, duration: Option<&str>) -> Result<()> {
-    let title = "✅ Backup Completado";
+    let language = current_preferences().1;
+    let title = language.pick("✅ Backup Completado", "✅ Backup Completed");
     
-    let message = match (files_copied, duration) {
-        (Some(count), Some(time)) => format!("✨ {} archivos copiados en {}", count, time),
-        (Some(count), None) => format!("✨ {} archivos copiados", count),
-        (None, Some(time)) => format!("✨ Sin cambios detectados ({})", time),
-        (None, None) => "✨ Sin cambios detectados".to_string(),
+    let message = match (language, files_copied, duration) {
+        (Language::Spanish, Some(count), Some(time)) => format!("✨ {} archivos copiados en {}", count, time),
+        (Language::Spanish, Some(count), None) => format!("✨ {} archivos copiados", count),
+        (Language::Spanish, None, Some(time)) => format!("✨ Sin cambios detectados ({})", time),
+        (Language::Spanish, None, None) => "✨ Sin cambios detectados".to_string(),
+        (Language::English, Some(count), Some(time)) => format!("✨ {} files copied in {}", count, time),
+        (Language::English, Some(count), None) => format!("✨ {} files copied", count),
+        (Language::English, None, Some(time)) => format!("✨ No changes detected ({})", time),
+        (Language::English, None, None) => "✨ No changes detected".to_string(),
     };
 
-    show_notification(title, &message, NotificationType::Success)
+    show_notification(NotificationKind::BackupSuccess, title, &message, NotificationType::Success)
 }
 
 /// Mostrar notificación de backup con advertencias
 pub fn show_backup_warning(warning_msg: &str) -> Result<()> {
-    let title = "⚠️ Backup con Advertencias";
+    let title = current_preferences().1.pick("⚠️ Backup con Advertencias", "⚠️ Backup Completed with Warnings");
     let message = format!("🔶 {}", warning_msg);
     
-    show_notification(title, &message, NotificationType::Warning)
+    show_notification(NotificationKind::BackupWarning, title, &message, NotificationType::Warning)
 }
 
 /// Mostrar notificación de backup fallido
 pub fn show_backup_failed(error_msg: &str) -> Result<()> {
-    let title = "❌ Backup Falló";
+    let title = current_preferences().1.pick("❌ Backup Falló", "❌ Backup Failed");
     let message = format!("💥 {}", error_msg);
     
-    show_notification(title, &message, NotificationType::Error)
+    show_notification(NotificationKind::BackupFailed, title, &message, NotificationType::Error)
 }
 
 /// Mostrar notificación de daemon iniciado
 pub fn show_daemon_started(interval: u64) -> Result<()> {
-    let title = "🤖 Daemon Iniciado";
+    let language = current_preferences().1;
+    let title = language.pick("🤖 Daemon Iniciado", "🤖 Daemon Started");
     let hours = interval / 3600;
-    let message = if hours >= 1 {
-        format!("⏰ Backup automático cada {} horas", hours)
-    } else {
-        format!("⏰ Backup automático cada {} segundos", interval)
+    let message = match (language, hours >= 1) {
+        (Language::Spanish, true) => format!("⏰ Backup automático cada {} horas", hours),
+        (Language::Spanish, false) => format!("⏰ Backup automático cada {} segundos", interval),
+        (Language::English, true) => format!("⏰ Automatic backup every {} hours", hours),
+        (Language::English, false) => format!("⏰ Automatic backup every {} seconds", interval),
     };
     
-    show_notification(title, &message, NotificationType::Info)
+    show_notification(NotificationKind::Daemon, title, &message, NotificationType::Info)
 }
 
 /// Mostrar notificación de daemon detenido
 pub fn show_daemon_stopped() -> Result<()> {
-    let title = "⏹️ Daemon Detenido";
-    let message = "🔕 Backup automático deshabilitado";
+    let language = current_preferences().1;
+    let title = language.pick("⏹️ Daemon Detenido", "⏹️ Daemon Stopped");
+    let message = language.pick("🔕 Backup automático deshabilitado", "🔕 Automatic backup disabled");
+    
+    show_notification(NotificationKind::Daemon, title, message, NotificationType::Info)
+}
+
+/// Mostrar notificación de config.json editado con errores (se mantiene la configuración anterior)
+pub fn show_config_reload_failed(reason: &str) -> Result<()> {
+    let language = current_preferences().1;
+    let title = language.pick("⚠️ config.json no se recargó", "⚠️ config.json was not reloaded");
+    let message = format!("📝 {}\n{}", reason,
+                          language.pick("Se mantiene la configuración anterior", "The previous configuration is kept"));
+    
+    show_notification(NotificationKind::Config, title, &message, NotificationType::Warning)
+}
+
+/// Mostrar notificación de cambio de perfil
+pub fn show_profile_switched(profile: &str) -> Result<()> {
+    let title = current_preferences().1.pick("👤 Perfil Activo", "👤 Active Profile");
+    let message = format!("📋 {}", profile);
     
-    show_notification(title, message, NotificationType::Info)
+    show_notification(NotificationKind::Config, title, &message, NotificationType::Info)
 }
 
 /// Mostrar notificación cuando se minimiza al tray
 pub fn show_tray_minimized() -> Result<()> {
-    let title = "📦 Minimizado al Tray";
-    let message = "🖥️ La aplicación sigue funcionando en segundo plano\n💡 Click derecho en el icono del tray para abrir menú";
+    let language = current_preferences().1;
+    let title = language.pick("📦 Minimizado al Tray", "📦 Minimized to Tray");
+    let message = language.pick(
+        "🖥️ La aplicación sigue funcionando en segundo plano\n💡 Click derecho en el icono del tray para abrir menú",
+        "🖥️ The application keeps running in the background\n💡 Right-click the tray icon to open the menu",
+    );
     
-    show_notification(title, message, NotificationType::Info)
+    show_notification(NotificationKind::Tray, title, message, NotificationType::Info)
 }
 
 /// Tipos de notificación para diferentes estilos
@@ -72,8 +177,13 @@ enum NotificationType {
     Info,
 }
 
-/// Función central para mostrar notificaciones
-fn show_notification(title: &str, message: &str, notification_type: NotificationType) -> Result<()> {
+/// Función central para mostrar notificaciones (las desactivadas en las preferencias se omiten)
+fn show_notification(kind: NotificationKind, title: &str, message: &str, notification_type: NotificationType) -> Result<()> {
+    if !current_preferences().0.allows(kind) {
+        debug!("🔕 Notificación desactivada ({:?}): {}", kind, title);
+        return Ok(());
+    }
+    
     info!("🔔 Showing notification: {} - {}", title, message);
     
     let mut notification = Notification::new();
@@ -37,17 +37,20 @@ pub fn set_startup_shortcut(enabled: bool, exe: &Path) -> Result<()> {
         let exe_str = exe_copy.display().to_string();
         let lnk_str = lnk.display().to_string();
         let cwd = exe_copy.parent().map(|p| p.display().to_string()).unwrap_or_else(|| "".to_string());
+        // La copia del exe no tiene config.json al lado: apuntarla al que usa esta instancia
+        let arguments = format!("--start-daemon --config \"{}\"", crate::core::paths::current().config_file.display());
 
         tracing::info!("🔗 Creating startup shortcut:");
         tracing::info!("   TargetPath: {}", exe_str);
-        tracing::info!("   Arguments: --start-daemon");
+        tracing::info!("   Arguments: {}", arguments);
         tracing::info!("   WorkingDirectory: {}", cwd);
         tracing::info!("   Shortcut: {}", lnk_str);
 
         let ps = format!(
-            "$shell = New-Object -ComObject WScript.Shell; $sc = $shell.CreateShortcut('{lnk}'); $sc.TargetPath = '{exe}'; $sc.Arguments = '--start-daemon'; $sc.WorkingDirectory = '{cwd}'; $sc.Save(); Write-Host 'Shortcut created successfully'",
+            "$shell = New-Object -ComObject WScript.Shell; $sc = $shell.CreateShortcut('{lnk}'); $sc.TargetPath = '{exe}'; $sc.Arguments = '{args}'; $sc.WorkingDirectory = '{cwd}'; $sc.Save(); Write-Host 'Shortcut created successfully'",
             lnk = lnk_str,
             exe = exe_str,
+            args = arguments,
             cwd = cwd
         );
 
@@ -4,7 +4,10 @@
 use anyhow::Result;
 
 use tracing::{info, debug, warn};
-use tray_icon::{TrayIcon, TrayIconBuilder, menu::{Menu, MenuItem, MenuEvent}, Icon, TrayIconEvent};
+use tray_icon::{TrayIcon, TrayIconBuilder, menu::{CheckMenuItem, Menu, MenuItem, MenuEvent, Submenu}, Icon, TrayIconEvent};
+
+/// Prefijo de los ids de menú de cada perfil (`profile:<nombre>`)
+const PROFILE_MENU_PREFIX: &str = "profile:";
 
 /// Acciones que puede enviar el system tray (mantenidas para compatibilidad)
 #[derive(Debug, Clone)]
@@ -17,7 +20,9 @@ pub enum TrayAction {
 
 /// Manejador del system tray - COMUNICACIÓN CON BACKGROUND
 pub struct SystemTray {
-    _tray_icon: TrayIcon,
+    tray_icon: TrayIcon,
+    /// Submenú con un item por perfil (se reconstruye al cambiar la lista)
+    profiles_menu: Submenu,
 }
 
 impl SystemTray {
@@ -31,13 +36,17 @@ impl SystemTray {
         // Crear menu
         let tray_menu = Menu::new();
         let show_item = MenuItem::with_id("show_app", "Mostrar Aplicacion", true, None);
+        let run_now_item = MenuItem::with_id("run_now", "Ejecutar Ahora", true, None);
         let start_daemon_item = MenuItem::with_id("start_daemon", "Iniciar Daemon", true, None);
         let stop_daemon_item = MenuItem::with_id("stop_daemon", "Detener Daemon", true, None);
+        let profiles_menu = Submenu::with_id("profiles", "Perfil", true);
         let exit_item = MenuItem::with_id("exit_app", "Salir", true, None);
         
         tray_menu.append(&show_item)?;
+        tray_menu.append(&run_now_item)?;
         tray_menu.append(&start_daemon_item)?;
         tray_menu.append(&stop_daemon_item)?;
+        tray_menu.append(&profiles_menu)?;
         tray_menu.append(&exit_item)?;
         
         // Crear tray icon
@@ -59,6 +68,9 @@ impl SystemTray {
                             let _ = crate::system::window::try_restore_main_window_by_title("RustyVault v2.0");
                             crate::app::send_background_command(crate::app::BackgroundCommand::ShowWindow);
                         }
+                        "run_now" => {
+                            crate::app::send_background_command(crate::app::BackgroundCommand::RunScheduledNow);
+                        }
                         "start_daemon" => {
                             crate::app::send_background_command(crate::app::BackgroundCommand::StartDaemon);
                         }
@@ -68,6 +80,10 @@ impl SystemTray {
                         "exit_app" => {
                             crate::app::send_background_command(crate::app::BackgroundCommand::Exit);
                         }
+                        id if id.starts_with(PROFILE_MENU_PREFIX) => {
+                            let profile = id[PROFILE_MENU_PREFIX.len()..].to_string();
+                            crate::app::send_background_command(crate::app::BackgroundCommand::SwitchProfile(profile));
+                        }
                         _ => {
                             warn!("Unknown menu ID: '{}'", menu_event.id.0);
                         }
@@ -98,7 +114,8 @@ impl SystemTray {
         info!("System tray inicializado");
         
         Ok(Self {
-            _tray_icon: tray_icon,
+            tray_icon,
+            profiles_menu,
         })
     }
     
@@ -139,6 +156,30 @@ impl SystemTray {
         }
     }
     
+    /// Actualizar el tooltip (estado del daemon y cuenta regresiva)
+    pub fn set_tooltip(&self, text: &str) {
+        if let Err(e) = self.tray_icon.set_tooltip(Some(text)) {
+            debug!("Error actualizando tooltip del tray: {}", e);
+        }
+    }
+    
+    /// Reconstruir el submenú de perfiles marcando el activo
+    pub fn set_profiles(&self, profiles: &[String], active: &str) {
+        while self.profiles_menu.remove_at(0).is_some() {}
+        for profile in profiles {
+            let item = CheckMenuItem::with_id(
+                format!("{}{}", PROFILE_MENU_PREFIX, profile),
+                profile,
+                true,
+                profile == active,
+                None,
+            );
+            if let Err(e) = self.profiles_menu.append(&item) {
+                debug!("Error agregando perfil al menú del tray: {}", e);
+            }
+        }
+    }
+    
     /// Minimizar al tray
     pub fn minimize_to_tray(&self) -> Result<()> {
         
@@ -32,4 +32,31 @@ pub fn backup_progress_bar(ui: &mut egui::Ui, progress: f32, current_file: Optio
     if let Some(file) = current_file {
         ui.small(format!("Processing: {}", file));
     }
-} 
\ No newline at end of file
+}
+
+/// Tamaño legible (B, KB, MB, GB, TB)
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Duración legible ("45s", "3m 20s", "1h 05m")
+pub fn format_duration(seconds: u64) -> String {
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 3600 {
+        format!("{}m {:02}s", seconds / 60, seconds % 60)
+    } else {
+        format!("{}h {:02}m", seconds / 3600, (seconds % 3600) / 60)
+    }
+}
@@ -0,0 +1,48 @@
+use eframe::egui;
+use crate::core::AppConfig;
+use crate::core::config_exchange;
+
+/// Conflict dialog - config.json was edited on disk while the UI had unsaved changes
+/// Returns Some(true) to load the file from disk, Some(false) to keep the UI edits
+pub fn render(ctx: &egui::Context, current: &AppConfig, disk: &AppConfig) -> Option<bool> {
+    let mut choice = None;
+
+    egui::Window::new("📝 config.json changed on disk")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(520.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("config.json fue editado fuera de RustyVault mientras tenías cambios sin guardar.");
+            ui.add_space(4.0);
+
+            ui.strong("Cambios en disco");
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                let changes = config_exchange::diff(current, disk);
+                if changes.is_empty() {
+                    ui.label("Sin cambios");
+                }
+                for change in changes {
+                    ui.label(change.describe());
+                }
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("📂 Load from disk")
+                    .on_hover_text("Usa el archivo editado y descarta los cambios sin guardar de la UI")
+                    .clicked()
+                {
+                    choice = Some(true);
+                }
+                if ui.button("✏ Keep my edits")
+                    .on_hover_text("Ignora el archivo editado; el próximo guardado lo reemplaza")
+                    .clicked()
+                {
+                    choice = Some(false);
+                }
+            });
+        });
+
+    choice
+}
@@ -0,0 +1,113 @@
+use eframe::egui;
+use std::sync::{Arc, Mutex};
+use crate::core::AppConfig;
+use crate::core::config_exchange::{self, ConfigChange, ImportMode, ImportPreview};
+
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(229, 57, 53);
+const WARNING_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 167, 38);
+
+/// Import dialog - shows what an imported config would change before applying it
+pub struct ImportDialog {
+    preview: ImportPreview,
+    mode: ImportMode,
+}
+
+impl ImportDialog {
+    pub fn new(preview: ImportPreview) -> Self {
+        Self { preview, mode: ImportMode::Merge }
+    }
+
+    /// Render the dialog; returns (keep_open, config to apply once confirmed)
+    pub fn render(&mut self, ctx: &egui::Context, config: &Arc<Mutex<AppConfig>>) -> (bool, Option<AppConfig>) {
+        let Ok(current) = config.lock().map(|config| config.clone()) else {
+            return (true, None);
+        };
+        let result = self.preview.apply(&current, self.mode);
+        let changes = config_exchange::diff(&current, &result);
+
+        let mut open = true;
+        let mut cancelled = false;
+        let mut confirmed = false;
+
+        egui::Window::new("📥 Import Config")
+            .open(&mut open)
+            .default_size([560.0, 480.0])
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(format!("📄 {}", self.preview.path.display()));
+                if let Some(version) = self.preview.migrated_from {
+                    ui.small(format!("Archivo de la versión {} - migrado al formato actual", version));
+                }
+                ui.add_space(4.0);
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.mode, ImportMode::Merge, "➕ Merge")
+                        .on_hover_text("Agrega los pairs importados y mantiene los ajustes actuales; los ids repetidos se regeneran");
+                    ui.radio_value(&mut self.mode, ImportMode::Replace, "♻ Replace")
+                        .on_hover_text("La configuración importada reemplaza a la actual por completo");
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height((ui.available_height() - 40.0).max(120.0))
+                    .show(ui, |ui| {
+                        render_issues(ui, &self.preview);
+                        render_changes(ui, &changes);
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let blocked = self.preview.is_blocked();
+                    let disabled_reason = if blocked {
+                        "Corrige los schedules u opciones inválidas del archivo antes de importarlo"
+                    } else {
+                        "El archivo no cambia nada en la configuración actual"
+                    };
+                    if ui.add_enabled(!blocked && !changes.is_empty(), egui::Button::new("✅ Import"))
+                        .on_disabled_hover_text(disabled_reason)
+                        .clicked()
+                    {
+                        confirmed = true;
+                    }
+                    if ui.button("❌ Cancel").clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+
+        let applied = confirmed.then_some(result);
+        (open && !cancelled && applied.is_none(), applied)
+    }
+}
+
+/// Validation problems found in the imported file
+fn render_issues(ui: &mut egui::Ui, preview: &ImportPreview) {
+    if preview.report.is_clean() {
+        ui.label("✅ Todas las rutas, schedules y opciones son válidos en esta máquina");
+        ui.add_space(6.0);
+        return;
+    }
+
+    ui.strong(format!("Validation ({})", preview.report.summary()));
+    for issue in &preview.report.issues {
+        let (icon, color) = if issue.is_error() { ("❌", ERROR_COLOR) } else { ("⚠", WARNING_COLOR) };
+        ui.colored_label(color, format!("{} {}: {}", icon, issue.subject, issue.message));
+    }
+    let disabled = preview.disabled_pairs();
+    if disabled > 0 {
+        ui.small(format!("{} pair(s) con errores se importarán deshabilitados", disabled));
+    }
+    ui.add_space(6.0);
+}
+
+/// Diff between the current config and the result of the import
+fn render_changes(ui: &mut egui::Ui, changes: &[ConfigChange]) {
+    ui.strong(format!("Changes ({})", changes.len()));
+    if changes.is_empty() {
+        ui.label("Sin cambios");
+    }
+    for change in changes {
+        ui.label(change.describe());
+    }
+}
@@ -15,7 +15,10 @@ pub enum UIAction {
     Exit,
     ConfigChanged,
     OpenSettings,
+    OpenStatistics,
     UpdateAutoStart(bool),
+    SwitchProfile(String),
+    CreateProfile(String),
     
     // === NEW CARDS ACTIONS ===
     AddBackupPair { source: String, destination: String },
@@ -27,10 +30,14 @@ pub enum UIAction {
 
     // === ADVANCED MANAGEMENT ACTIONS ===
     ToggleBackupPairEnabled(usize, bool),
+    ToggleBackupPairContinuous(usize, bool),
 }
 use crate::core::{AppConfig, RobocopyConfig};
 use crate::ui::tooltips::*;
 
+/// Campo de texto del perfil nuevo (recibe el foco al abrirse)
+const NEW_PROFILE_INPUT: &str = "new_profile_name";
+
 /// Ventana principal con interfaz minimalista según PRD
 /// Layout: Daemon Control + Backup Cards + Robocopy Settings + Window Actions
 pub struct MainWindow {
@@ -82,6 +89,10 @@ pub struct MainWindow {
     pub temp_start_with_windows: bool,
     /// Mostrar preview del comando robocopy
     show_command_preview: bool,
+    
+    // === PROFILES ===
+    /// Nombre del perfil nuevo mientras se escribe (None = selector normal)
+    new_profile_buffer: Option<String>,
 }
 
 impl MainWindow {
@@ -118,6 +129,7 @@ impl MainWindow {
             temp_robocopy_config: RobocopyConfig::default(),
             temp_start_with_windows: false,
             show_command_preview: false,
+            new_profile_buffer: None,
         }
     }
     
@@ -134,7 +146,12 @@ impl MainWindow {
         self.sync_buffers_with_config(config);
         
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("🔧 RustyVault v2.0");
+            ui.horizontal(|ui| {
+                ui.heading("🔧 RustyVault v2.0");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    self.render_profile_selector(ui, background_state, action_callback);
+                });
+            });
             ui.separator();
             
             // Section 1: Daemon Control - Simple y claro
@@ -182,6 +199,58 @@ impl MainWindow {
         });
     }
     
+    /// Selector de perfil (cada perfil tiene sus pairs, motor y schedule)
+    fn render_profile_selector(
+        &mut self,
+        ui: &mut egui::Ui,
+        background_state: &Arc<Mutex<crate::app::AppState>>,
+        action_callback: &mut dyn FnMut(UIAction),
+    ) {
+        let (profiles, active) = match background_state.lock() {
+            Ok(state) => (state.profiles.clone(), state.active_profile.clone()),
+            Err(_) => return,
+        };
+        
+        // Right-to-left: los widgets se agregan de derecha a izquierda
+        if let Some(name) = &mut self.new_profile_buffer {
+            let mut done = false;
+            if ui.button("❌").on_hover_text("Cancelar").clicked() {
+                done = true;
+            }
+            let create = ui.add_enabled(!name.trim().is_empty(), egui::Button::new("✅"))
+                .on_hover_text("Crear el perfil copiando el actual y activarlo");
+            let edit = ui.add(egui::TextEdit::singleline(name)
+                .id(egui::Id::new(NEW_PROFILE_INPUT))
+                .hint_text("Profile name")
+                .desired_width(140.0));
+            let submitted = edit.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            if (create.clicked() || submitted) && !name.trim().is_empty() {
+                action_callback(UIAction::CreateProfile(name.trim().to_string()));
+                done = true;
+            }
+            if done {
+                self.new_profile_buffer = None;
+            }
+            return;
+        }
+        
+        if ui.button("➕").on_hover_text("Nuevo perfil (copia del actual)").clicked() {
+            self.new_profile_buffer = Some(String::new());
+            ui.memory_mut(|memory| memory.request_focus(egui::Id::new(NEW_PROFILE_INPUT)));
+        }
+        egui::ComboBox::from_id_salt("profile_selector")
+            .selected_text(format!("👤 {}", active))
+            .show_ui(ui, |ui| {
+                for profile in &profiles {
+                    if ui.selectable_label(*profile == active, profile).clicked() && *profile != active {
+                        action_callback(UIAction::SwitchProfile(profile.clone()));
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Perfil activo: cada perfil tiene sus propios backup pairs, motor y schedule");
+    }
+    
     /// Section 1: Control básico del daemon
     fn render_daemon_control_section(
         &mut self,
@@ -397,22 +466,18 @@ impl MainWindow {
                 action_callback(UIAction::MinimizeToTray);
             }
 
-            // 🚧 SETTINGS TEMPORALMENTE DESHABILITADO - UNCOMMENT PARA HABILITAR:
-            /*
-            if ui.button("⚙ Settings")
-                .on_hover_text("Abrir ventana de configuración avanzada")
+            if ui.button("📊 Statistics")
+                .on_hover_text("Historial de ejecuciones: duración, datos copiados y tasa de éxito")
                 .clicked()
             {
-                action_callback(UIAction::OpenSettings);
+                action_callback(UIAction::OpenStatistics);
             }
-            */
-            
-            // 🔧 DUMMY SETTINGS BUTTON (remove when enabling real settings above)
-            if ui.add_enabled(false, egui::Button::new("⚙ Settings (WIP)"))
-                .on_hover_text("Settings en desarrollo - temporalmente deshabilitado")
+
+            if ui.button("⚙ Settings")
+                .on_hover_text("Abrir ventana de configuración avanzada")
                 .clicked()
             {
-                // No action - dummy button
+                action_callback(UIAction::OpenSettings);
             }
 
             if ui.button("❌ Exit")
@@ -437,6 +502,25 @@ impl MainWindow {
         });
     }
     
+    /// La configuración se reemplazó entera: releer los buffers en el próximo frame
+    pub fn reload_from_config(&mut self) {
+        self.initialized_from_config = false;
+    }
+    
+    /// Formulario de pair abierto (cambios sin guardar)
+    pub fn has_unsaved_edits(&self) -> bool {
+        self.show_add_modal
+    }
+    
+    /// Cerrar el formulario de pair descartando lo escrito
+    pub fn discard_edits(&mut self) {
+        self.show_add_modal = false;
+        self.editing_pair_index = None;
+        self.temp_source_buffer.clear();
+        self.temp_destination_buffer.clear();
+        self.current_validation = None;
+    }
+    
     /// Sincronizar buffers de UI con configuración actual
     fn sync_buffers_with_config(&mut self, config: &Arc<Mutex<AppConfig>>) {
         if let Ok(cfg) = config.lock() {
@@ -507,9 +591,22 @@ impl MainWindow {
             return;
         }
 
+        // Varios pairs pueden estar en ejecución a la vez (volúmenes distintos)
+        let running_pairs = active_pairs.iter()
+            .filter(|pair| self.get_backup_pair_status_text_real(pair, background_state).starts_with("En ejecución"))
+            .count();
+
         ui.horizontal(|ui| {
             ui.label("Overall Progress:");
-            ui.colored_label(egui::Color32::GRAY, format!("({} active)", total_active_pairs));
+            let run_progress = background_state.lock().ok().and_then(|state| state.run_progress());
+            if let (true, Some(progress)) = (running_pairs > 0, run_progress) {
+                ui.colored_label(egui::Color32::GRAY, format!("({} active, {} running • {}% • {})",
+                    total_active_pairs, running_pairs, progress.percent(), crate::app::format_remaining(progress.eta())));
+            } else if running_pairs > 0 {
+                ui.colored_label(egui::Color32::GRAY, format!("({} active, {} running)", total_active_pairs, running_pairs));
+            } else {
+                ui.colored_label(egui::Color32::GRAY, format!("({} active)", total_active_pairs));
+            }
 
             // Calcular ancho disponible para la barra
             let available_width = ui.available_width() - 20.0; // Margin
@@ -525,7 +622,16 @@ impl MainWindow {
                     egui::Sense::hover()
                 );
 
-                ui.painter().rect_filled(rect, 2.0, color);
+                // Pair copiando: el segmento se llena según su avance
+                let transfer = self.get_backup_pair_transfer(pair, background_state);
+                if let Some(transfer) = &transfer {
+                    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(70));
+                    let mut filled = rect;
+                    filled.set_width(rect.width() * transfer.fraction());
+                    ui.painter().rect_filled(filled, 2.0, color);
+                } else {
+                    ui.painter().rect_filled(rect, 2.0, color);
+                }
 
                 // Texto del estado en el centro del segmento
                 let text_color = if color == egui::Color32::WHITE {
@@ -533,11 +639,14 @@ impl MainWindow {
                 } else {
                     egui::Color32::WHITE
                 };
+                let segment_text = transfer
+                    .map(|transfer| format!("{}%", transfer.percent()))
+                    .unwrap_or_else(|| status_char.to_string());
 
                 ui.painter().text(
                     rect.center(),
                     egui::Align2::CENTER_CENTER,
-                    status_char,
+                    segment_text,
                     egui::FontId::default(),
                     text_color,
                 );
@@ -546,9 +655,12 @@ impl MainWindow {
                 if response.hovered() {
                     response.on_hover_ui(|ui| {
                         ui.label(format!("Active Backup Pair #{}", active_index + 1));
-                        ui.label(format!("Source: {}", pair.source.display()));
-                        ui.label(format!("Destination: {}", pair.destination.display()));
+                        ui.label(format!("Source: {}", path_with_resolved(&pair.source)));
+                        ui.label(format!("Destination: {}", path_with_resolved(&pair.destination)));
                         ui.label(format!("Status: {}", self.get_backup_pair_status_text_real(pair, background_state)));
+                        if let Some(transfer) = &transfer {
+                            ui.label(format!("Progress: {}% • {}", transfer.percent(), crate::app::format_remaining(transfer.eta())));
+                        }
                     });
                 }
             }
@@ -561,14 +673,24 @@ impl MainWindow {
         let mut warning_count = 0;
         let mut error_count = 0;
         let mut pending_count = 0;
+        let mut running_count = 0;
+        let mut skipped_count = 0;
 
         // Contar estados usando datos reales - solo backup pairs activos
         for pair in backup_pairs.iter().filter(|pair| pair.enabled) {
-            match self.get_backup_pair_status_text_real(pair, background_state).as_str() {
-                "Exitoso" => success_count += 1,
-                "Advertencia" => warning_count += 1,
-                "Error" => error_count += 1,
-                _ => pending_count += 1,
+            let status_text = self.get_backup_pair_status_text_real(pair, background_state);
+            if status_text == "Exitoso" {
+                success_count += 1;
+            } else if status_text.starts_with("Advertencia") {
+                warning_count += 1;
+            } else if status_text.starts_with("Error") {
+                error_count += 1;
+            } else if status_text.starts_with("En ejecución") {
+                running_count += 1;
+            } else if status_text.starts_with("Omitido") {
+                skipped_count += 1;
+            } else {
+                pending_count += 1;
             }
         }
         
@@ -590,6 +712,16 @@ impl MainWindow {
                 ui.label(format!("Error ({})", error_count));
             }
             
+            if running_count > 0 {
+                ui.colored_label(egui::Color32::from_rgb(33, 150, 243), "■");
+                ui.label(format!("En ejecución ({})", running_count));
+            }
+            
+            if skipped_count > 0 {
+                ui.colored_label(egui::Color32::from_rgb(120, 144, 156), "■");
+                ui.label(format!("Sin cambios ({})", skipped_count));
+            }
+            
             if pending_count > 0 {
                 ui.colored_label(egui::Color32::from_rgb(158, 158, 158), "■");
                 ui.label(format!("Pendiente ({})", pending_count));
@@ -638,6 +770,8 @@ impl MainWindow {
                     crate::app::BackupStatus::Warning(_) => (egui::Color32::from_rgb(255, 152, 0), "⚠"), // Warning - naranja  
                     crate::app::BackupStatus::Error(_) => (egui::Color32::from_rgb(244, 67, 54), "❌"),   // Error - rojo
                     crate::app::BackupStatus::Running => (egui::Color32::from_rgb(33, 150, 243), "●"),   // Running - azul
+                    crate::app::BackupStatus::Retrying { .. } => (egui::Color32::from_rgb(33, 150, 243), "↻"), // Reintentando - azul
+                    crate::app::BackupStatus::Skipped(_) => (egui::Color32::from_rgb(120, 144, 156), "⏭"), // Sin cambios - gris azulado
                     crate::app::BackupStatus::Pending => (egui::Color32::from_rgb(158, 158, 158), "○"),  // Pending - gris
                 }
             } else {
@@ -679,6 +813,14 @@ impl MainWindow {
                     crate::app::BackupStatus::Warning(msg) => format!("Advertencia: {}", msg),
                     crate::app::BackupStatus::Error(msg) => format!("Error: {}", msg),
                     crate::app::BackupStatus::Running => "En ejecución".to_string(),
+                    crate::app::BackupStatus::Retrying { attempt, max_attempts, next_retry_at } => {
+                        let now = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs();
+                        format!("En ejecución: reintento {}/{} en {}s", attempt, max_attempts, next_retry_at.saturating_sub(now))
+                    }
+                    crate::app::BackupStatus::Skipped(reason) => format!("Omitido: {}", reason.describe()),
                     crate::app::BackupStatus::Pending => "Pendiente".to_string(),
                 }
             } else {
@@ -811,6 +953,42 @@ impl MainWindow {
         (0, 0, "nunca".to_string(), "0".to_string())
     }
 
+    /// Duración de la última ejecución y su velocidad (solo si terminó exitosamente)
+    fn get_backup_pair_duration(&self, pair: &crate::core::config::BackupPair, background_state: &Arc<Mutex<crate::app::AppState>>) -> Option<(std::time::Duration, Option<f64>)> {
+        let state = background_state.lock().ok()?;
+        let status = state.backup_statuses.get(&pair.id)?;
+        let throughput = match &status.status {
+            crate::app::BackupStatus::Success(metrics) => metrics.throughput_mb_s(),
+            _ => None,
+        };
+        status.last_duration.map(|duration| (duration, throughput))
+    }
+
+    /// Avance en vivo del pair (solo mientras está copiando)
+    fn get_backup_pair_transfer(&self, pair: &crate::core::config::BackupPair, background_state: &Arc<Mutex<crate::app::AppState>>) -> Option<crate::core::progress::TransferProgress> {
+        let state = background_state.lock().ok()?;
+        let status = state.backup_statuses.get(&pair.id)?;
+        if !matches!(status.status, crate::app::BackupStatus::Running) {
+            return None;
+        }
+        state.transfers.get(&pair.id).copied()
+    }
+
+    /// Obtener los intentos de la última ejecución de un backup pair
+    fn get_backup_pair_attempts(&self, pair: &crate::core::config::BackupPair, background_state: &Arc<Mutex<crate::app::AppState>>) -> Vec<crate::core::retry::AttemptRecord> {
+        background_state.lock()
+            .ok()
+            .and_then(|state| state.backup_statuses.get(&pair.id).map(|status| status.attempts.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Obtener la próxima ejecución programada de un backup pair (si el daemon corre)
+    fn get_backup_pair_next_run(&self, pair: &crate::core::config::BackupPair, background_state: &Arc<Mutex<crate::app::AppState>>) -> Option<chrono::DateTime<chrono::Local>> {
+        background_state.lock()
+            .ok()
+            .and_then(|state| state.next_run_at.get(&pair.id).copied())
+    }
+
     // === NEW CARDS UI FUNCTIONS ===
     
     /// Renderizar cards de backup pairs 
@@ -961,6 +1139,7 @@ impl MainWindow {
                             }
                         }
                     });
+                    render_resolved_path(ui, &self.temp_source_buffer);
                     
                     ui.add_space(10.0);
                     
@@ -989,6 +1168,7 @@ impl MainWindow {
                             }
                         }
                     });
+                    render_resolved_path(ui, &self.temp_destination_buffer);
                     
                     ui.add_space(15.0);
 
@@ -1114,12 +1294,23 @@ impl MainWindow {
                 // Status indicator para activos
                 ui.colored_label(egui::Color32::GREEN, SafeIcons::SUCCESS);
 
+                // Modo continuo: backup al detectar cambios en el origen
+                let continuous = pair.continuous.is_some();
+                let hover = match &pair.continuous {
+                    Some(mode) => format!("Modo continuo activo: backup {}s después del último cambio\nClick para volver al intervalo", mode.quiet_period_seconds),
+                    None => "Modo continuo: hacer backup al detectar cambios en el origen".to_string(),
+                };
+                if ui.selectable_label(continuous, "👁").on_hover_text(hover).clicked() {
+                    info!("👁️ UI: Modo continuo {} para backup pair #{}", if continuous { "desactivado" } else { "activado" }, original_index + 1);
+                    action_callback(UIAction::ToggleBackupPairContinuous(original_index, !continuous));
+                }
+
                 // ICONO DE DIRECTORIOS con tooltip hover (reemplaza la línea de rutas completas)
                 ui.colored_label(egui::Color32::from_rgb(120, 120, 120), "📂")
                     .on_hover_text(format!(
                         "Rutas completas:\n📁 Origen: {}\n📁 Destino: {}",
-                        pair.source.display(),
-                        pair.destination.display()
+                        path_with_resolved(&pair.source),
+                        path_with_resolved(&pair.destination)
                     ));
 
                 // BOTONES DE ACCIÓN - Funcionalidad completa para backup pairs activos
@@ -1178,7 +1369,60 @@ impl MainWindow {
                         files_copied
                     )
                 );
+                
+                // Duración (y velocidad si copió) de la última ejecución
+                if let Some((duration, throughput)) = self.get_backup_pair_duration(pair, background_state) {
+                    let speed = throughput.map(|mb_s| format!(" • {:.1} MB/s", mb_s)).unwrap_or_default();
+                    ui.colored_label(
+                        egui::Color32::from_rgb(120, 120, 120),
+                        format!("• ⌛ {}{}", crate::ui::components::format_duration(duration.as_secs()), speed)
+                    ).on_hover_text("Duración de la última ejecución");
+                }
+                
+                // Próxima ejecución programada por el daemon
+                if let Some(next) = self.get_backup_pair_next_run(pair, background_state) {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(120, 120, 120),
+                        format!("• ⏰ {}", crate::app::format_countdown(next))
+                    ).on_hover_text(format!("Próxima ejecución: {}", next.format("%Y-%m-%d %H:%M")));
+                }
+                
+                // Intentos de la última ejecución (solo si hubo reintentos)
+                let attempts = self.get_backup_pair_attempts(pair, background_state);
+                if attempts.iter().any(|attempt| attempt.next_retry_in.is_some()) {
+                    let details = attempts.iter()
+                        .map(|attempt| {
+                            let time = chrono::DateTime::from_timestamp(attempt.started_at as i64, 0)
+                                .map(|utc| utc.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                                .unwrap_or_default();
+                            format!("#{}/{} ({}): {}", attempt.attempt, attempt.max_attempts, time, attempt.outcome)
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 152, 0),
+                        format!("🔁 {} intentos", attempts.len())
+                    ).on_hover_text(format!("Intentos de la última ejecución:\n{}", details));
+                }
             });
+            
+            // LÍNEA 3: Avance de la copia en curso
+            if let Some(transfer) = self.get_backup_pair_transfer(pair, background_state) {
+                let speed = transfer.throughput_mb_s()
+                    .map(|mb_s| format!(" • {:.1} MB/s", mb_s))
+                    .unwrap_or_default();
+                ui.add(
+                    egui::ProgressBar::new(transfer.fraction())
+                        .text(format!(
+                            "{}% • {} de {}{} • {}",
+                            transfer.percent(),
+                            crate::ui::components::format_bytes(transfer.bytes_done),
+                            crate::ui::components::format_bytes(transfer.bytes_total),
+                            speed,
+                            crate::app::format_remaining(transfer.eta())
+                        ))
+                ).on_hover_text(format!("{} de {} archivos copiados", transfer.files_done, transfer.files_total));
+            }
         });
         ui.add_space(5.0);
     }
@@ -1228,8 +1472,8 @@ impl MainWindow {
                 ui.colored_label(egui::Color32::GRAY, "📂")
                     .on_hover_text(format!(
                         "Rutas completas:\n📁 Origen: {}\n📁 Destino: {}",
-                        pair.source.display(),
-                        pair.destination.display()
+                        path_with_resolved(&pair.source),
+                        path_with_resolved(&pair.destination)
                     ));
 
                 // BOTONES DE ACCIÓN - Solo delete para backup pairs deshabilitados
@@ -1579,4 +1823,21 @@ impl BulkOperationType {
             BulkOperationType::Delete => SafeIcons::DELETE,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Path as written, plus the resolved path when it uses variables (`{Documents}`, `%USERPROFILE%`...)
+fn path_with_resolved(path: &std::path::Path) -> String {
+    match crate::core::path_vars::preview(path) {
+        None => path.display().to_string(),
+        Some(Ok(resolved)) => format!("{} → {}", path.display(), resolved.display()),
+        Some(Err(e)) => format!("{} ({})", path.display(), e),
+    }
+}
+
+/// Resolved path preview under a path input (expansion errors show in the validation icon)
+fn render_resolved_path(ui: &mut egui::Ui, input: &str) {
+    if let Some(Ok(resolved)) = crate::core::path_vars::preview(std::path::Path::new(input.trim())) {
+        ui.small(format!("→ {}", resolved.display()))
+            .on_hover_text("Ruta expandida en esta máquina; se guarda tal como la escribiste");
+    }
+}
@@ -2,4 +2,7 @@ pub mod main_window;
 pub mod components;
 pub mod tooltips;
 pub mod icons;
-pub mod settings_window;
\ No newline at end of file
+pub mod settings_window;
+pub mod statistics_window;
+pub mod import_dialog;
+pub mod config_conflict;
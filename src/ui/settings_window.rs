@@ -1,9 +1,15 @@
 #![allow(dead_code)]
 use eframe::egui;
-use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tracing::info;
-use crate::core::{AppConfig, RobocopyConfig};
+use crate::core::{AppConfig, BandwidthLimit, Schedule, ScheduleConfig, TimeWindow};
+use crate::core::preferences::{AppTheme, Language, LogLevel, UiPreferences};
+
+const ERROR_COLOR: egui::Color32 = egui::Color32::from_rgb(229, 57, 53);
 
 /// Actions that the settings window can trigger
 #[derive(Debug, Clone)]
@@ -11,55 +17,81 @@ pub enum SettingsAction {
     // Daemon Control
     StartDaemon,
     StopDaemon,
-    
+
     // Configuration Changes
-    UpdateInterval(u64),
-    UpdateRobocopyConfig(RobocopyConfig),
-    UpdateAutoStart(bool),
-    UpdateNotificationEnabled(bool),
-    UpdateTheme(AppTheme),
-    
+    /// Show interface preferences while editing (saved only with Apply)
+    PreviewPreferences(UiPreferences),
+    /// Commit the draft: the settings edited here replace those of the live config
+    ApplyAndSave(Box<AppConfig>),
+
     // Import/Export
     ExportConfig,
     ImportConfig(String),
-    
+
     // Window Control
     CloseSettings,
-    ApplyAndSave,
 }
 
-/// Available UI themes
-#[derive(Debug, Clone, PartialEq)]
-pub enum AppTheme {
-    Auto,
-    Light,
-    Dark,
+/// Schedule modes offered in the daemon tab
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScheduleMode {
+    Interval,
+    Daily,
+    Weekly,
+    Monthly,
+    Cron,
 }
 
-impl Default for AppTheme {
-    fn default() -> Self {
-        AppTheme::Auto
+impl ScheduleMode {
+    fn label(&self) -> &'static str {
+        match self {
+            ScheduleMode::Interval => "Every N seconds",
+            ScheduleMode::Daily => "Daily",
+            ScheduleMode::Weekly => "Weekly",
+            ScheduleMode::Monthly => "Monthly",
+            ScheduleMode::Cron => "Cron expression",
+        }
     }
 }
 
+const WEEKDAYS: [(&str, &str); 7] = [
+    ("mon", "Mon"), ("tue", "Tue"), ("wed", "Wed"), ("thu", "Thu"),
+    ("fri", "Fri"), ("sat", "Sat"), ("sun", "Sun"),
+];
+
 /// Dedicated settings window with tabbed interface
+/// Edits go into `draft`; nothing is saved until Apply (Cancel goes back to `original_config`)
 pub struct SettingsWindow {
     /// Current active tab
     active_tab: SettingsTab,
-    
-    /// Temporary buffers for editing
+
+    /// Draft copy of the configuration being edited
+    draft: AppConfig,
+
+    /// Text buffers for the numeric fields (parsed into the draft every frame)
     temp_interval_buffer: String,
     temp_robocopy_threads: String,
     temp_robocopy_retries: String,
     temp_robocopy_wait: String,
-    
-    /// UI state
-    show_advanced_robocopy: bool,
-    
+    temp_parallel_buffer: String,
+    temp_bandwidth_mb: String,
+    temp_bandwidth_from: String,
+    temp_bandwidth_until: String,
+    temp_schedule_mode: ScheduleMode,
+    temp_schedule_time: String,
+    temp_schedule_days: [bool; 7],
+    temp_schedule_month_day: String,
+    temp_schedule_cron: String,
+    temp_window_start: String,
+    temp_window_end: String,
+
+    /// Validation errors shown next to each field (field → message)
+    errors: HashMap<&'static str, String>,
+
     /// Configuration backup (for Cancel functionality)
     original_config: Option<AppConfig>,
-    
-    /// Whether changes have been made
+
+    /// Whether the draft differs from the saved configuration (or has invalid values)
     has_unsaved_changes: bool,
 }
 
@@ -76,11 +108,23 @@ impl Default for SettingsWindow {
     fn default() -> Self {
         Self {
             active_tab: SettingsTab::Daemon,
+            draft: AppConfig::default(),
             temp_interval_buffer: String::new(),
             temp_robocopy_threads: "8".to_string(),
             temp_robocopy_retries: "3".to_string(),
             temp_robocopy_wait: "2".to_string(),
-            show_advanced_robocopy: false,
+            temp_parallel_buffer: "2".to_string(),
+            temp_bandwidth_mb: String::new(),
+            temp_bandwidth_from: String::new(),
+            temp_bandwidth_until: String::new(),
+            temp_schedule_mode: ScheduleMode::Interval,
+            temp_schedule_time: "02:00".to_string(),
+            temp_schedule_days: [true, true, true, true, true, false, false],
+            temp_schedule_month_day: "1".to_string(),
+            temp_schedule_cron: "0 2 * * *".to_string(),
+            temp_window_start: String::new(),
+            temp_window_end: String::new(),
+            errors: HashMap::new(),
             original_config: None,
             has_unsaved_changes: false,
         }
@@ -91,29 +135,43 @@ impl SettingsWindow {
     pub fn new() -> Self {
         Self::default()
     }
-    
+
+    /// Edits not applied yet
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.has_unsaved_changes
+    }
+
     /// Initialize settings window with current configuration
     pub fn initialize_from_config(&mut self, config: &AppConfig) {
+        self.draft = config.clone();
         self.temp_interval_buffer = config.check_interval_seconds.to_string();
-        
+
         self.temp_robocopy_threads = config.robocopy.multithreading.to_string();
         self.temp_robocopy_retries = config.robocopy.retry_count.to_string();
         self.temp_robocopy_wait = config.robocopy.retry_wait.to_string();
-        
+        self.temp_parallel_buffer = config.max_parallel_backups.to_string();
+
+        let limit = &config.bandwidth_limit;
+        self.temp_bandwidth_mb = limit.max_mb_per_sec.map(|mb| mb.to_string()).unwrap_or_default();
+        self.temp_bandwidth_from = limit.active_from_hour.map(|h| h.to_string()).unwrap_or_default();
+        self.temp_bandwidth_until = limit.active_until_hour.map(|h| h.to_string()).unwrap_or_default();
+
+        self.load_schedule_buffers(config.schedule.as_ref());
+
+        self.errors.clear();
         self.original_config = Some(config.clone());
         self.has_unsaved_changes = false;
     }
-    
+
     /// Main render function for settings window
     pub fn render(
         &mut self,
         ctx: &egui::Context,
-        config: &Arc<Mutex<AppConfig>>,
         daemon_running: &Arc<AtomicBool>,
     ) -> (bool, Vec<SettingsAction>) {
         let mut keep_open = true;
         let mut actions = Vec::new();
-        
+
         egui::Window::new("⚙ Settings")
             .default_size([600.0, 500.0])
             .min_size([500.0, 400.0])
@@ -123,78 +181,186 @@ impl SettingsWindow {
                 // Header with unsaved changes indicator
                 if self.has_unsaved_changes {
                     ui.horizontal(|ui| {
-                        ui.label("⚠️ You have unsaved changes");
+                        if self.errors.is_empty() {
+                            ui.label("⚠️ You have unsaved changes");
+                        } else {
+                            ui.colored_label(ERROR_COLOR, format!("❌ {} invalid value(s) - fix them to save", self.errors.len()));
+                        }
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if ui.button("💾 Apply & Save").clicked() {
-                                actions.push(SettingsAction::ApplyAndSave);
-                                self.has_unsaved_changes = false;
+                            if ui.add_enabled(self.errors.is_empty(), egui::Button::new("💾 Apply & Save"))
+                                .on_disabled_hover_text("Corrige los valores marcados en rojo")
+                                .clicked()
+                            {
+                                self.apply(&mut actions);
                             }
                         });
                     });
                     ui.separator();
                 }
-                
+
                 // Tab navigation
                 ui.horizontal(|ui| {
-                    ui.selectable_value(&mut self.active_tab, SettingsTab::Daemon, "🔥 Daemon");
-                    ui.selectable_value(&mut self.active_tab, SettingsTab::Robocopy, "🔧 Robocopy");
-                    ui.selectable_value(&mut self.active_tab, SettingsTab::Interface, "🎨 Interface");
-                    ui.selectable_value(&mut self.active_tab, SettingsTab::General, "⚙ General");
+                    for tab in [SettingsTab::Daemon, SettingsTab::Robocopy, SettingsTab::Interface, SettingsTab::General] {
+                        let label = self.tab_label(tab.clone());
+                        ui.selectable_value(&mut self.active_tab, tab, label);
+                    }
                 });
-                
+
                 ui.separator();
-                
+
                 // Tab content
-                match self.active_tab {
-                    SettingsTab::Daemon => self.render_daemon_tab(ui, config, daemon_running, &mut actions),
-                    SettingsTab::Robocopy => self.render_robocopy_tab(ui, config, &mut actions),
-                    SettingsTab::Interface => self.render_interface_tab(ui, config, &mut actions),
-                    SettingsTab::General => self.render_general_tab(ui, config, &mut actions),
-                }
-                
+                egui::ScrollArea::vertical()
+                    .max_height((ui.available_height() - 40.0).max(200.0))
+                    .show(ui, |ui| match self.active_tab {
+                        SettingsTab::Daemon => self.render_daemon_tab(ui, daemon_running, &mut actions),
+                        SettingsTab::Robocopy => self.render_robocopy_tab(ui),
+                        SettingsTab::Interface => self.render_interface_tab(ui, &mut actions),
+                        SettingsTab::General => self.render_general_tab(ui),
+                    });
+
                 ui.separator();
-                
+
                 // Footer buttons
                 ui.horizontal(|ui| {
-                    if ui.button("❌ Cancel").clicked() {
-                        // TODO: Restore original config
+                    if ui.button("❌ Cancel")
+                        .on_hover_text("Descarta los cambios sin guardar")
+                        .clicked()
+                    {
+                        self.cancel(&mut actions);
                         keep_open = false;
                     }
-                    
+
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if ui.button("✅ OK").clicked() {
-                            if self.has_unsaved_changes {
-                                actions.push(SettingsAction::ApplyAndSave);
+                            // With invalid values the window stays open showing the errors
+                            if !self.has_unsaved_changes || self.apply(&mut actions) {
+                                keep_open = false;
                             }
-                            keep_open = false;
                         }
-                        
+
+                        if ui.add_enabled(self.has_unsaved_changes && self.errors.is_empty(), egui::Button::new("💾 Apply"))
+                            .clicked()
+                        {
+                            self.apply(&mut actions);
+                        }
+
                         if ui.button("📤 Export Config").clicked() {
                             actions.push(SettingsAction::ExportConfig);
                         }
-                        
+
                         if ui.button("📥 Import Config").clicked() {
-                            // TODO: File dialog for import
-                            info!("Import config clicked - TODO: implement file dialog");
+                            if let Some(path) = rfd::FileDialog::new()
+                                .set_title("Import configuration")
+                                .add_filter("JSON", &["json"])
+                                .pick_file()
+                            {
+                                actions.push(SettingsAction::ImportConfig(path.to_string_lossy().into_owned()));
+                            }
                         }
                     });
                 });
             });
-        
+
+        self.refresh_draft();
         (keep_open, actions)
     }
-    
+
+    /// Tab title, marked when one of its fields is invalid
+    fn tab_label(&self, tab: SettingsTab) -> String {
+        let (label, fields): (&str, &[&str]) = match tab {
+            SettingsTab::Daemon => ("🔥 Daemon", &["interval", "schedule"]),
+            SettingsTab::Robocopy => ("🔧 Robocopy", &["threads", "retries", "wait", "bandwidth_mb", "bandwidth_hours"]),
+            SettingsTab::Interface => ("🎨 Interface", &[]),
+            SettingsTab::General => ("⚙ General", &["parallel"]),
+        };
+        if fields.iter().any(|field| self.errors.contains_key(field)) {
+            format!("{} ❌", label)
+        } else {
+            label.to_string()
+        }
+    }
+
+    /// Commit the draft if it is valid; returns false (and keeps the errors visible) otherwise
+    fn apply(&mut self, actions: &mut Vec<SettingsAction>) -> bool {
+        self.refresh_draft();
+        if !self.errors.is_empty() {
+            return false;
+        }
+        info!("💾 Settings applied");
+        actions.push(SettingsAction::ApplyAndSave(Box::new(self.draft.clone())));
+        self.original_config = Some(self.draft.clone());
+        self.has_unsaved_changes = false;
+        true
+    }
+
+    /// Go back to `original_config`, undoing previewed preferences
+    fn cancel(&mut self, actions: &mut Vec<SettingsAction>) {
+        let Some(original) = self.original_config.clone() else {
+            return;
+        };
+        if original.ui_preferences != self.draft.ui_preferences {
+            actions.push(SettingsAction::PreviewPreferences(original.ui_preferences.clone()));
+        }
+        self.initialize_from_config(&original);
+        info!("❌ Settings changes discarded");
+    }
+
+    /// Parse the text buffers into the draft and collect the validation errors
+    fn refresh_draft(&mut self) {
+        let mut errors = HashMap::new();
+
+        match parse_in_range(&self.temp_interval_buffer, 1u64, u64::MAX) {
+            Ok(seconds) => self.draft.check_interval_seconds = seconds,
+            Err(e) => { errors.insert("interval", e); }
+        }
+        match parse_in_range(&self.temp_robocopy_threads, 1u8, 128) {
+            Ok(threads) => self.draft.robocopy.multithreading = threads,
+            Err(e) => { errors.insert("threads", e); }
+        }
+        match parse_in_range(&self.temp_robocopy_retries, 0u8, u8::MAX) {
+            Ok(retries) => self.draft.robocopy.retry_count = retries,
+            Err(e) => { errors.insert("retries", e); }
+        }
+        match parse_in_range(&self.temp_robocopy_wait, 0u8, u8::MAX) {
+            Ok(wait) => self.draft.robocopy.retry_wait = wait,
+            Err(e) => { errors.insert("wait", e); }
+        }
+        match parse_in_range(&self.temp_parallel_buffer, 1usize, 16) {
+            Ok(parallel) => self.draft.max_parallel_backups = parallel,
+            Err(e) => { errors.insert("parallel", e); }
+        }
+        if let Some(limit) = self.parse_bandwidth_limit(&mut errors) {
+            self.draft.bandwidth_limit = limit;
+        }
+
+        let schedule = self.build_schedule();
+        match schedule.as_ref().map(|schedule| schedule.compile()) {
+            Some(Err(e)) => { errors.insert("schedule", format!("{:#}", e)); }
+            _ => self.draft.schedule = schedule,
+        }
+
+        self.errors = errors;
+        self.has_unsaved_changes = !self.errors.is_empty()
+            || self.original_config.as_ref().is_some_and(|original| !same_config(original, &self.draft));
+    }
+
+    /// Inline validation message under a field
+    fn field_error(&self, ui: &mut egui::Ui, field: &str) {
+        if let Some(error) = self.errors.get(field) {
+            ui.colored_label(ERROR_COLOR, format!("❌ {}", error));
+        }
+    }
+
     /// Render daemon control tab
     fn render_daemon_tab(
         &mut self,
         ui: &mut egui::Ui,
-        _config: &Arc<Mutex<AppConfig>>,
         daemon_running: &Arc<AtomicBool>,
         actions: &mut Vec<SettingsAction>,
     ) {
         ui.heading("⚙ Daemon Control");
         ui.add_space(10.0);
-        
+
         // Current daemon status
         let is_running = daemon_running.load(Ordering::Relaxed);
         ui.horizontal(|ui| {
@@ -205,9 +371,9 @@ impl SettingsWindow {
                 ui.colored_label(egui::Color32::GRAY, "⏸ Stopped");
             }
         });
-        
+
         ui.add_space(10.0);
-        
+
         // Daemon controls
         ui.horizontal(|ui| {
             if is_running {
@@ -220,202 +386,371 @@ impl SettingsWindow {
                 }
             }
         });
-        
+
         ui.add_space(20.0);
-        
+
         // Interval configuration
         ui.horizontal(|ui| {
             ui.label("Check Interval:");
-            if ui.text_edit_singleline(&mut self.temp_interval_buffer).changed() {
-                self.has_unsaved_changes = true;
-            }
+            ui.text_edit_singleline(&mut self.temp_interval_buffer);
             ui.label("seconds");
         });
-        
+        self.field_error(ui, "interval");
+
         // Quick interval presets
         ui.horizontal(|ui| {
             ui.label("Quick set:");
-            if ui.small_button("1 min").clicked() {
-                self.temp_interval_buffer = "60".to_string();
-                self.has_unsaved_changes = true;
+            for (label, seconds) in [("1 min", "60"), ("5 min", "300"), ("1 hour", "3600"), ("6 hours", "21600")] {
+                if ui.small_button(label).clicked() {
+                    self.temp_interval_buffer = seconds.to_string();
+                }
             }
-            if ui.small_button("5 min").clicked() {
-                self.temp_interval_buffer = "300".to_string();
-                self.has_unsaved_changes = true;
+        });
+
+        ui.add_space(20.0);
+
+        self.render_schedule_editor(ui);
+
+        ui.add_space(20.0);
+
+        ui.checkbox(&mut self.draft.start_with_windows, "Start with Windows")
+            .on_hover_text("Add RustyVault to Windows startup programs");
+    }
+
+    /// Global schedule editor (calendar rules and allowed time windows)
+    fn render_schedule_editor(&mut self, ui: &mut egui::Ui) {
+        ui.label("Schedule:");
+        ui.horizontal(|ui| {
+            ui.label("Run:");
+            egui::ComboBox::from_id_salt("schedule_mode")
+                .selected_text(self.temp_schedule_mode.label())
+                .show_ui(ui, |ui| {
+                    for mode in [ScheduleMode::Interval, ScheduleMode::Daily, ScheduleMode::Weekly, ScheduleMode::Monthly, ScheduleMode::Cron] {
+                        ui.selectable_value(&mut self.temp_schedule_mode, mode, mode.label());
+                    }
+                });
+        });
+
+        match self.temp_schedule_mode {
+            ScheduleMode::Interval => {
+                ui.label("Uses the check interval above");
             }
-            if ui.small_button("1 hour").clicked() {
-                self.temp_interval_buffer = "3600".to_string();
-                self.has_unsaved_changes = true;
+            ScheduleMode::Cron => {
+                ui.horizontal(|ui| {
+                    ui.label("Expression:");
+                    ui.text_edit_singleline(&mut self.temp_schedule_cron);
+                }).response.on_hover_text("minute hour day-of-month month day-of-week, e.g. */15 9-17 * * mon-fri");
             }
-            if ui.small_button("6 hours").clicked() {
-                self.temp_interval_buffer = "21600".to_string();
-                self.has_unsaved_changes = true;
+            mode => {
+                if mode == ScheduleMode::Weekly {
+                    ui.horizontal(|ui| {
+                        for (selected, (_, label)) in self.temp_schedule_days.iter_mut().zip(WEEKDAYS) {
+                            ui.checkbox(selected, label);
+                        }
+                    });
+                }
+                ui.horizontal(|ui| {
+                    if mode == ScheduleMode::Monthly {
+                        ui.label("Day:");
+                        ui.add(egui::TextEdit::singleline(&mut self.temp_schedule_month_day).desired_width(30.0));
+                    }
+                    ui.label("At:");
+                    ui.add(egui::TextEdit::singleline(&mut self.temp_schedule_time).desired_width(50.0));
+                    ui.label("(HH:MM, local time)");
+                });
             }
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Only between");
+            ui.add(egui::TextEdit::singleline(&mut self.temp_window_start).desired_width(50.0));
+            ui.label("and");
+            ui.add(egui::TextEdit::singleline(&mut self.temp_window_end).desired_width(50.0));
+            ui.label("(empty = any time)");
         });
-        
-        ui.add_space(20.0);
-        
-        // Auto-start options
-        ui.checkbox(&mut true, "Auto-start daemon when application starts")
-            .on_hover_text("Automatically start the backup daemon when RustyVault launches");
-            
-        ui.checkbox(&mut false, "Start with Windows")
-            .on_hover_text("Add RustyVault to Windows startup programs");
+
+        self.field_error(ui, "schedule");
+        if !self.errors.contains_key("schedule") {
+            if let Some(schedule) = &self.draft.schedule {
+                ui.small(format!("📅 {}", schedule.describe()));
+            }
+        }
     }
-    
+
+    /// Fill the schedule buffers from the current configuration
+    fn load_schedule_buffers(&mut self, schedule: Option<&ScheduleConfig>) {
+        self.temp_schedule_mode = ScheduleMode::Interval;
+        self.temp_window_start.clear();
+        self.temp_window_end.clear();
+
+        let Some(config) = schedule else {
+            return;
+        };
+
+        match &config.schedule {
+            Schedule::Interval { .. } => {}
+            Schedule::Daily { time } => {
+                self.temp_schedule_mode = ScheduleMode::Daily;
+                self.temp_schedule_time = time.clone();
+            }
+            Schedule::Weekly { days, time } => {
+                self.temp_schedule_mode = ScheduleMode::Weekly;
+                self.temp_schedule_time = time.clone();
+                for (selected, (key, _)) in self.temp_schedule_days.iter_mut().zip(WEEKDAYS) {
+                    *selected = days.iter().any(|day| day.eq_ignore_ascii_case(key));
+                }
+            }
+            Schedule::Monthly { day, time } => {
+                self.temp_schedule_mode = ScheduleMode::Monthly;
+                self.temp_schedule_month_day = day.to_string();
+                self.temp_schedule_time = time.clone();
+            }
+            Schedule::Cron { expression } => {
+                self.temp_schedule_mode = ScheduleMode::Cron;
+                self.temp_schedule_cron = expression.clone();
+            }
+        }
+
+        if let Some(window) = config.allowed_windows.first() {
+            self.temp_window_start = window.start.clone();
+            self.temp_window_end = window.end.clone();
+        }
+    }
+
+    /// Build the schedule from the buffers (None = plain check interval)
+    fn build_schedule(&self) -> Option<ScheduleConfig> {
+        let time = self.temp_schedule_time.trim().to_string();
+        let schedule = match self.temp_schedule_mode {
+            ScheduleMode::Interval => None,
+            ScheduleMode::Daily => Some(Schedule::Daily { time }),
+            ScheduleMode::Weekly => Some(Schedule::Weekly {
+                days: self.temp_schedule_days
+                    .iter()
+                    .zip(WEEKDAYS)
+                    .filter(|(selected, _)| **selected)
+                    .map(|(_, (key, _))| key.to_string())
+                    .collect(),
+                time,
+            }),
+            ScheduleMode::Monthly => Some(Schedule::Monthly {
+                day: self.temp_schedule_month_day.trim().parse().unwrap_or(0),
+                time,
+            }),
+            ScheduleMode::Cron => Some(Schedule::Cron { expression: self.temp_schedule_cron.trim().to_string() }),
+        };
+
+        let start = self.temp_window_start.trim();
+        let end = self.temp_window_end.trim();
+        let allowed_windows = if start.is_empty() && end.is_empty() {
+            Vec::new()
+        } else {
+            vec![TimeWindow { start: start.to_string(), end: end.to_string() }]
+        };
+
+        match schedule {
+            Some(schedule) => Some(ScheduleConfig { schedule, allowed_windows }),
+            // Interval with a window still needs a schedule entry to carry the window
+            None if !allowed_windows.is_empty() => Some(ScheduleConfig {
+                schedule: Schedule::Interval {
+                    seconds: self.temp_interval_buffer.trim().parse().unwrap_or(3600),
+                },
+                allowed_windows,
+            }),
+            None => None,
+        }
+    }
+
     /// Render robocopy configuration tab
-    fn render_robocopy_tab(
-        &mut self,
-        ui: &mut egui::Ui,
-        _config: &Arc<Mutex<AppConfig>>,
-        _actions: &mut Vec<SettingsAction>,
-    ) {
+    fn render_robocopy_tab(&mut self, ui: &mut egui::Ui) {
         ui.heading("🔧 Robocopy Configuration");
         ui.add_space(10.0);
-        
+
         // Multi-threading
         ui.horizontal(|ui| {
             ui.label("Threads:");
-            if ui.text_edit_singleline(&mut self.temp_robocopy_threads).changed() {
-                self.has_unsaved_changes = true;
-            }
+            ui.text_edit_singleline(&mut self.temp_robocopy_threads);
             ui.label("(1-128, recommended: 8)")
                 .on_hover_text("Number of parallel threads for file copying. More threads = faster but more CPU usage.");
         });
-        
+        self.field_error(ui, "threads");
+
         // Retry settings
         ui.horizontal(|ui| {
             ui.label("Retries:");
-            if ui.text_edit_singleline(&mut self.temp_robocopy_retries).changed() {
-                self.has_unsaved_changes = true;
-            }
+            ui.text_edit_singleline(&mut self.temp_robocopy_retries);
             ui.label("attempts");
         });
-        
+        self.field_error(ui, "retries");
+
         ui.horizontal(|ui| {
             ui.label("Wait time:");
-            if ui.text_edit_singleline(&mut self.temp_robocopy_wait).changed() {
-                self.has_unsaved_changes = true;
-            }
+            ui.text_edit_singleline(&mut self.temp_robocopy_wait);
             ui.label("seconds between retries");
         });
-        
+        self.field_error(ui, "wait");
+
         ui.add_space(10.0);
-        
+
         // Standard options
         ui.label("Standard Options:");
-        ui.checkbox(&mut true, "Mirror mode (/MIR)")
+        ui.checkbox(&mut self.draft.robocopy.mirror_mode, "Mirror mode (/MIR)")
             .on_hover_text("Mirrors source to destination (deletes extra files in destination)");
-            
-        ui.checkbox(&mut true, "Use file timestamps (/FFT)")
+
+        ui.checkbox(&mut self.draft.robocopy.fat_file_timing, "Use file timestamps (/FFT)")
             .on_hover_text("Assume FAT file times (2-second granularity)");
-            
-        ui.checkbox(&mut false, "Copy subdirectories including empty ones (/E)")
-            .on_hover_text("Copies subdirectories, including empty ones");
-        
+
+        ui.add_space(10.0);
+
+        // Bandwidth limit (global, shared by all pairs running in parallel)
+        ui.label("Bandwidth Limit:");
+        ui.horizontal(|ui| {
+            ui.label("Max:");
+            ui.add(egui::TextEdit::singleline(&mut self.temp_bandwidth_mb).desired_width(60.0));
+            ui.label("MB/s (empty = unlimited)")
+                .on_hover_text("Robocopy uses /IPG (single-threaded while limited); the native engine uses a token bucket");
+        });
+        self.field_error(ui, "bandwidth_mb");
+        ui.horizontal(|ui| {
+            ui.label("Only from");
+            ui.add(egui::TextEdit::singleline(&mut self.temp_bandwidth_from).desired_width(30.0));
+            ui.label("h to");
+            ui.add(egui::TextEdit::singleline(&mut self.temp_bandwidth_until).desired_width(30.0));
+            ui.label("h (empty = all day)");
+        });
+        self.field_error(ui, "bandwidth_hours");
+
         ui.add_space(10.0);
-        
-        // Advanced options toggle
-        ui.checkbox(&mut self.show_advanced_robocopy, "Show advanced options");
-        
-        if self.show_advanced_robocopy {
-            ui.separator();
-            ui.label("Advanced Options:");
-            
-            ui.checkbox(&mut false, "Verbose output (/V)")
-                .on_hover_text("Show detailed information about copied files");
-                
-            ui.checkbox(&mut true, "No progress indicator (/NP)")
-                .on_hover_text("Don't display percentage progress for individual files");
-                
-            ui.checkbox(&mut false, "Log output (/LOG)")
-                .on_hover_text("Write status output to log file");
+        ui.small(format!("robocopy {}", self.draft.robocopy.build_args().join(" ")));
+    }
+
+    /// Build a bandwidth limit from the text buffers (empty = no limit; invalid values are reported)
+    fn parse_bandwidth_limit(&self, errors: &mut HashMap<&'static str, String>) -> Option<BandwidthLimit> {
+        let max_mb_per_sec = match self.temp_bandwidth_mb.trim() {
+            "" => None,
+            text => match text.parse::<f64>() {
+                Ok(mb) if mb > 0.0 => Some(mb),
+                _ => {
+                    errors.insert("bandwidth_mb", "Debe ser un número mayor que 0 (vacío = sin límite)".to_string());
+                    return None;
+                }
+            },
+        };
+        let hour = |text: &str| match text.trim() {
+            "" => Ok(None),
+            text => parse_in_range(text, 0u8, 23).map(Some),
+        };
+        match (hour(&self.temp_bandwidth_from), hour(&self.temp_bandwidth_until)) {
+            (Ok(active_from_hour), Ok(active_until_hour)) => Some(BandwidthLimit { max_mb_per_sec, active_from_hour, active_until_hour }),
+            (Err(e), _) | (_, Err(e)) => {
+                errors.insert("bandwidth_hours", format!("Horas: {}", e));
+                None
+            }
         }
     }
-    
-    /// Render interface/UI tab
-    fn render_interface_tab(
-        &mut self,
-        ui: &mut egui::Ui,
-        _config: &Arc<Mutex<AppConfig>>,
-        _actions: &mut Vec<SettingsAction>,
-    ) {
+
+    /// Render interface/UI tab (changes are previewed right away and saved with Apply)
+    fn render_interface_tab(&mut self, ui: &mut egui::Ui, actions: &mut Vec<SettingsAction>) {
         ui.heading("🎨 Interface Settings");
         ui.add_space(10.0);
-        
+
+        let before = self.draft.ui_preferences.clone();
+        let prefs = &mut self.draft.ui_preferences;
+
         // Theme selection
         ui.horizontal(|ui| {
             ui.label("Theme:");
-            egui::ComboBox::from_label("")
-                .selected_text("Auto (System)")
+            egui::ComboBox::from_id_salt("settings_theme")
+                .selected_text(prefs.theme.label())
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut AppTheme::Auto, AppTheme::Auto, "Auto (System)");
-                    ui.selectable_value(&mut AppTheme::Light, AppTheme::Light, "Light");
-                    ui.selectable_value(&mut AppTheme::Dark, AppTheme::Dark, "Dark");
+                    for theme in AppTheme::ALL {
+                        ui.selectable_value(&mut prefs.theme, theme, theme.label());
+                    }
                 });
         });
-        
+
+        // Language
+        ui.horizontal(|ui| {
+            ui.label("Language:");
+            egui::ComboBox::from_id_salt("settings_language")
+                .selected_text(prefs.language.label())
+                .show_ui(ui, |ui| {
+                    for language in Language::ALL {
+                        ui.selectable_value(&mut prefs.language, language, language.label());
+                    }
+                });
+        })
+        .response
+        .on_hover_text("Idioma de las notificaciones del sistema");
+
         ui.add_space(10.0);
-        
+
         // Notification settings
         ui.label("Notifications:");
-        ui.checkbox(&mut true, "Show backup completion notifications");
-        ui.checkbox(&mut true, "Show error notifications");
-        ui.checkbox(&mut false, "Show daemon start/stop notifications");
-        
+        let notifications = &mut prefs.notifications;
+        ui.checkbox(&mut notifications.enabled, "Show notifications");
+        ui.add_enabled_ui(notifications.enabled, |ui| {
+            ui.indent("notification_kinds", |ui| {
+                ui.checkbox(&mut notifications.backup_success, "Backup completed");
+                ui.checkbox(&mut notifications.backup_warning, "Backup with warnings");
+                ui.checkbox(&mut notifications.backup_failed, "Backup failed");
+                ui.checkbox(&mut notifications.daemon, "Daemon started/stopped");
+                ui.checkbox(&mut notifications.config, "Profile switched / config.json reload rejected");
+                ui.checkbox(&mut notifications.tray, "Minimized to tray");
+            });
+        });
+
         ui.add_space(10.0);
-        
+
         // Window behavior
         ui.label("Window Behavior:");
-        ui.checkbox(&mut true, "Minimize to system tray");
-        ui.checkbox(&mut false, "Start minimized");
-        ui.checkbox(&mut true, "Close to tray (don't exit)");
+        let window = &mut prefs.window;
+        ui.checkbox(&mut window.minimize_to_tray, "Minimize to system tray")
+            .on_hover_text("Minimizar oculta la ventana en el tray en lugar de la barra de tareas");
+        ui.checkbox(&mut window.start_minimized, "Start minimized")
+            .on_hover_text("Arrancar oculto en el tray");
+        ui.checkbox(&mut window.close_to_tray, "Close to tray (don't exit)")
+            .on_hover_text("Cerrar la ventana la oculta en el tray; para salir usa Exit en el menú del tray");
+
+        if *prefs != before {
+            actions.push(SettingsAction::PreviewPreferences(prefs.clone()));
+        }
     }
-    
+
     /// Render general/misc settings tab
-    fn render_general_tab(
-        &mut self,
-        ui: &mut egui::Ui,
-        _config: &Arc<Mutex<AppConfig>>,
-        _actions: &mut Vec<SettingsAction>,
-    ) {
+    fn render_general_tab(&mut self, ui: &mut egui::Ui) {
         ui.heading("⚙ General Settings");
         ui.add_space(10.0);
-        
+
         // Logging
         ui.label("Logging:");
         ui.horizontal(|ui| {
-            ui.label("Log level:");
-            egui::ComboBox::from_label("")
-                .selected_text("Info")
+            ui.label("Log file level:");
+            let level = &mut self.draft.ui_preferences.log_level;
+            egui::ComboBox::from_id_salt("settings_log_level")
+                .selected_text(level.label())
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut "debug", "debug", "Debug");
-                    ui.selectable_value(&mut "info", "info", "Info");
-                    ui.selectable_value(&mut "warn", "warn", "Warning");
-                    ui.selectable_value(&mut "error", "error", "Error");
+                    for option in LogLevel::ALL {
+                        ui.selectable_value(level, option, option.label());
+                    }
                 });
-        });
-        
-        ui.checkbox(&mut true, "Enable file logging");
-        ui.checkbox(&mut false, "Enable console logging");
-        
+        })
+        .response
+        .on_hover_text("Nivel mínimo escrito en daemon_backup_ui.log; la consola usa RUST_LOG");
+
         ui.add_space(10.0);
-        
+
         // Performance
         ui.label("Performance:");
-        ui.checkbox(&mut true, "Enable multi-threaded backup processing");
-        ui.checkbox(&mut false, "Priority boost for backup operations");
-        
-        ui.add_space(10.0);
-        
-        // Safety
-        ui.label("Safety & Confirmations:");
-        ui.checkbox(&mut true, "Confirm before deleting backup pairs");
-        ui.checkbox(&mut true, "Warn about potentially dangerous paths");
-        ui.checkbox(&mut false, "Enable backup verification");
-        
+        ui.horizontal(|ui| {
+            ui.label("Pairs in parallel:");
+            ui.add(egui::TextEdit::singleline(&mut self.temp_parallel_buffer).desired_width(40.0));
+            ui.label("(1-16)")
+                .on_hover_text("Pairs que comparten volumen destino siempre se ejecutan en serie");
+        });
+        self.field_error(ui, "parallel");
+
         ui.add_space(20.0);
-        
+
         // About section
         ui.separator();
         ui.label("About RustyVault:");
@@ -424,3 +759,72 @@ impl SettingsWindow {
         ui.label("Built with: Rust + egui + robocopy");
     }
 }
+
+/// Live config with the settings this window edits taken from `draft`
+/// (backup pairs, profile and the rest may have changed while the window was open)
+pub fn apply_draft(draft: &AppConfig, live: &AppConfig) -> AppConfig {
+    AppConfig {
+        check_interval_seconds: draft.check_interval_seconds,
+        schedule: draft.schedule.clone(),
+        start_with_windows: draft.start_with_windows,
+        robocopy: draft.robocopy.clone(),
+        bandwidth_limit: draft.bandwidth_limit.clone(),
+        max_parallel_backups: draft.max_parallel_backups,
+        ui_preferences: draft.ui_preferences.clone(),
+        ..live.clone()
+    }
+}
+
+/// Whole number within `min..=max`
+fn parse_in_range<T: FromStr + PartialOrd + Display + Copy>(text: &str, min: T, max: T) -> Result<T, String> {
+    match text.trim().parse::<T>() {
+        Ok(value) if value >= min && value <= max => Ok(value),
+        _ => Err(format!("Debe ser un número entero entre {} y {}", min, max)),
+    }
+}
+
+/// Same saved content (AppConfig has no PartialEq; compare what would be written to config.json)
+fn same_config(a: &AppConfig, b: &AppConfig) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::BackupPair;
+
+    #[test]
+    fn test_draft_tracks_changes_and_validation() {
+        let live = AppConfig::default();
+        let mut window = SettingsWindow::new();
+        window.initialize_from_config(&live);
+        window.refresh_draft();
+        assert!(!window.has_unsaved_changes());
+
+        window.temp_robocopy_threads = "500".to_string();
+        window.temp_bandwidth_from = "25".to_string();
+        window.refresh_draft();
+        assert!(window.has_unsaved_changes());
+        assert!(window.errors.contains_key("threads"));
+        assert!(window.errors.contains_key("bandwidth_hours"));
+
+        window.temp_robocopy_threads = "16".to_string();
+        window.temp_bandwidth_from = String::new();
+        window.temp_interval_buffer = "600".to_string();
+        window.refresh_draft();
+        assert!(window.errors.is_empty());
+
+        // Pairs added while the window was open are kept when applying
+        let mut changed_live = live.clone();
+        changed_live.backup_pairs.push(BackupPair::new("C:\\docs", "D:\\docs"));
+        let applied = apply_draft(&window.draft, &changed_live);
+        assert_eq!(applied.check_interval_seconds, 600);
+        assert_eq!(applied.robocopy.multithreading, 16);
+        assert_eq!(applied.backup_pairs.len(), 1);
+
+        let mut actions = Vec::new();
+        window.cancel(&mut actions);
+        assert!(!window.has_unsaved_changes());
+        assert_eq!(window.temp_interval_buffer, live.check_interval_seconds.to_string());
+    }
+}
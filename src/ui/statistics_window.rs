@@ -0,0 +1,425 @@
+use eframe::egui;
+use egui_plot::{Bar, BarChart, HLine, Line, LineStyle, Plot, PlotPoints};
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use crate::core::AppConfig;
+use crate::core::fingerprint::FingerprintStore;
+use crate::core::history::{HistoryStats, HistoryStore, RunOutcome, RunRecord};
+use crate::ui::components::{format_bytes, format_duration};
+
+/// Weeks shown in the calendar heatmap
+const HEATMAP_WEEKS: i64 = 12;
+
+/// Height of each trend chart
+const CHART_HEIGHT: f32 = 120.0;
+
+const SUCCESS_COLOR: egui::Color32 = egui::Color32::from_rgb(76, 175, 80);
+const FAILURE_COLOR: egui::Color32 = egui::Color32::from_rgb(229, 57, 53);
+const DURATION_COLOR: egui::Color32 = egui::Color32::from_rgb(66, 165, 245);
+const BYTES_COLOR: egui::Color32 = egui::Color32::from_rgb(171, 71, 188);
+const FILES_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 167, 38);
+const THROUGHPUT_COLOR: egui::Color32 = egui::Color32::from_rgb(38, 166, 154);
+
+/// History and protected sizes read by the worker thread
+struct LoadedHistory {
+    records: Vec<RunRecord>,
+    source_sizes: HashMap<String, u64>,
+}
+
+/// Statistics window - trends built from the persistent run history
+pub struct StatisticsWindow {
+    /// Every recorded run, oldest first
+    records: Vec<RunRecord>,
+    /// Source size of each pair at its last successful backup
+    source_sizes: HashMap<String, u64>,
+    /// Pair being inspected (None = all pairs of the active profile)
+    selected_pair: Option<String>,
+    /// History being read in the background (the file can be large)
+    loading: Option<Receiver<LoadedHistory>>,
+}
+
+impl StatisticsWindow {
+    pub fn new() -> Self {
+        let mut window = Self {
+            records: Vec::new(),
+            source_sizes: HashMap::new(),
+            selected_pair: None,
+            loading: None,
+        };
+        window.reload();
+        window
+    }
+
+    /// Re-read history.jsonl on a worker thread; `render` picks up the result
+    pub fn reload(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(LoadedHistory {
+                records: HistoryStore::open().load(),
+                source_sizes: FingerprintStore::load().source_sizes(),
+            });
+        });
+        self.loading = Some(receiver);
+    }
+
+    fn poll_loading(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.loading else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(loaded) => {
+                self.records = loaded.records;
+                self.source_sizes = loaded.source_sizes;
+                self.loading = None;
+            }
+            Err(TryRecvError::Empty) => ctx.request_repaint_after(std::time::Duration::from_millis(100)),
+            Err(TryRecvError::Disconnected) => self.loading = None,
+        }
+    }
+
+    /// Render the window; returns false once it was closed
+    pub fn render(&mut self, ctx: &egui::Context, config: &Arc<Mutex<AppConfig>>) -> bool {
+        self.poll_loading(ctx);
+        let mut open = true;
+        let pair_names: Vec<(String, String)> = config
+            .lock()
+            .map(|config| config.backup_pairs.iter().map(|pair| (pair.id.clone(), pair.display_name())).collect())
+            .unwrap_or_default();
+
+        egui::Window::new("📊 Statistics")
+            .open(&mut open)
+            .default_size([640.0, 560.0])
+            .min_size([480.0, 360.0])
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.render_toolbar(ui, &pair_names);
+                ui.separator();
+
+                // "All pairs" = the pairs of the active profile (other profiles and removed pairs stay out)
+                let in_view = |pair_id: &String| match &self.selected_pair {
+                    Some(id) => pair_id == id,
+                    None => pair_names.iter().any(|(id, _)| id == pair_id),
+                };
+                let records: Vec<&RunRecord> = self.records
+                    .iter()
+                    .filter(|record| in_view(&record.pair_id))
+                    .collect();
+                let protected_bytes = self.source_sizes
+                    .iter()
+                    .filter(|(pair_id, _)| in_view(pair_id))
+                    .map(|(_, size)| *size)
+                    .sum::<u64>();
+
+                if records.is_empty() && self.loading.is_some() {
+                    ui.add_space(20.0);
+                    ui.vertical_centered(|ui| {
+                        ui.spinner();
+                        ui.label("Loading history...");
+                    });
+                    return;
+                }
+                if records.is_empty() {
+                    ui.add_space(20.0);
+                    ui.vertical_centered(|ui| {
+                        ui.label("No runs recorded yet");
+                        ui.small("Las estadísticas aparecen después del primer backup");
+                    });
+                    return;
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    render_summary(ui, &HistoryStats::from_records(records.iter().copied()), protected_bytes);
+                    ui.add_space(8.0);
+                    render_trends(ui, &records);
+                    ui.add_space(8.0);
+                    ui.label(egui::RichText::new(format!("Run calendar (last {} weeks)", HEATMAP_WEEKS)).strong());
+                    calendar_heatmap(ui, &records);
+                });
+            });
+
+        open
+    }
+
+    fn render_toolbar(&mut self, ui: &mut egui::Ui, pair_names: &[(String, String)]) {
+        ui.horizontal(|ui| {
+            let selected_text = match &self.selected_pair {
+                None => "All pairs".to_string(),
+                Some(id) => pair_names
+                    .iter()
+                    .find(|(pair_id, _)| pair_id == id)
+                    .map(|(_, name)| name.clone())
+                    .unwrap_or_else(|| "Removed pair".to_string()),
+            };
+
+            ui.label("Pair:");
+            egui::ComboBox::from_id_salt("statistics_pair")
+                .selected_text(selected_text)
+                .width(260.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.selected_pair, None, "All pairs");
+                    for (id, name) in pair_names {
+                        ui.selectable_value(&mut self.selected_pair, Some(id.clone()), name);
+                    }
+                });
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("🔄 Refresh").on_hover_text("Releer el historial de ejecuciones").clicked() {
+                    self.reload();
+                }
+            });
+        });
+    }
+}
+
+/// Totals and averages
+fn render_summary(ui: &mut egui::Ui, stats: &HistoryStats, protected_bytes: u64) {
+    egui::Grid::new("statistics_summary")
+        .num_columns(4)
+        .spacing([24.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("Runs:");
+            ui.strong(format!("{} ({} skipped)", stats.runs, stats.skipped));
+            ui.label("Success rate:");
+            ui.strong(stats.success_rate().map(|rate| format!("{:.0}%", rate)).unwrap_or_else(|| "-".to_string()));
+            ui.end_row();
+
+            ui.label("Avg duration:");
+            ui.strong(stats.avg_duration_seconds.map(|secs| format_duration(secs.round() as u64)).unwrap_or_else(|| "-".to_string()));
+            ui.label("Failures:");
+            ui.strong(stats.failures.to_string());
+            ui.end_row();
+
+            ui.label("Data transferred:");
+            ui.strong(format_bytes(stats.total_bytes))
+                .on_hover_text("Total copiado por todas las ejecuciones registradas");
+            ui.label("Files copied:");
+            ui.strong(stats.total_files.to_string());
+            ui.end_row();
+
+            ui.label("Data protected:");
+            ui.strong(format_bytes(protected_bytes))
+                .on_hover_text("Tamaño del origen en el último backup exitoso de cada pair");
+            ui.end_row();
+
+            ui.label("Avg throughput:");
+            ui.strong(stats.avg_throughput_mb_s.map(|mb_s| format!("{:.1} MB/s", mb_s)).unwrap_or_else(|| "-".to_string()));
+            ui.label("Last run:");
+            ui.strong(stats.last_run
+                .and_then(|ts| Local.timestamp_opt(ts, 0).single())
+                .map(|date| date.format("%d/%m/%Y %H:%M").to_string())
+                .unwrap_or_else(|| "-".to_string()));
+            ui.end_row();
+        });
+}
+
+/// Per-run trend charts over time (x axis = when each run finished)
+fn render_trends(ui: &mut egui::Ui, records: &[&RunRecord]) {
+    let executions: Vec<&RunRecord> = records.iter().copied().filter(|record| record.counts_as_execution()).collect();
+    if executions.is_empty() {
+        ui.small("Solo hay ejecuciones omitidas (sin cambios) - nada que graficar");
+        return;
+    }
+
+    let series = |value: fn(&RunRecord) -> f64| -> Vec<[f64; 2]> {
+        executions.iter().map(|record| [record.finished_at as f64, value(record)]).collect()
+    };
+    let durations = series(|record| record.duration().as_secs_f64());
+    let throughput = series(|record| record.throughput_mb_s().unwrap_or(0.0));
+    let bytes = series(|record| record.bytes_transferred as f64);
+    let files = series(|record| record.files_copied as f64);
+
+    // Tasa de éxito acumulada después de cada ejecución
+    let mut successes = 0;
+    let success_rate: Vec<[f64; 2]> = executions
+        .iter()
+        .enumerate()
+        .map(|(i, record)| {
+            if record.outcome != RunOutcome::Failed {
+                successes += 1;
+            }
+            [record.finished_at as f64, successes as f64 * 100.0 / (i + 1) as f64]
+        })
+        .collect();
+
+    let outcomes: Vec<RunOutcome> = executions.iter().map(|record| record.outcome).collect();
+
+    trend_chart(ui, "Duration", &durations, None, ChartKind::Line, DURATION_COLOR, |v| format_duration(v.round().max(0.0) as u64));
+    trend_chart(ui, "Data transferred", &bytes, Some(&outcomes), ChartKind::Bars, BYTES_COLOR, |v| format_bytes(v.max(0.0) as u64));
+    trend_chart(ui, "Throughput", &throughput, Some(&outcomes), ChartKind::Bars, THROUGHPUT_COLOR, |v| format!("{:.1} MB/s", v));
+    trend_chart(ui, "Files copied", &files, Some(&outcomes), ChartKind::Bars, FILES_COLOR, |v| format!("{:.0}", v));
+    trend_chart(ui, "Success rate", &success_rate, None, ChartKind::Line, SUCCESS_COLOR, |v| format!("{:.0}%", v));
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ChartKind {
+    Line,
+    Bars,
+}
+
+/// Values over time with egui_plot: dates on the x axis, dashed average and hover readout
+/// Bars for failed runs (when `outcomes` is given) are drawn in red
+fn trend_chart(
+    ui: &mut egui::Ui,
+    title: &str,
+    points: &[[f64; 2]],
+    outcomes: Option<&[RunOutcome]>,
+    kind: ChartKind,
+    color: egui::Color32,
+    format_value: fn(f64) -> String,
+) {
+    let average = points.iter().map(|[_, value]| value).sum::<f64>() / points.len() as f64;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(title).strong());
+        ui.small(format!("avg {}", format_value(average)));
+    });
+
+    // Ancho de barra proporcional a la separación media entre ejecuciones (mínimo un minuto)
+    let span = points.last().map(|[x, _]| *x).unwrap_or(0.0) - points.first().map(|[x, _]| *x).unwrap_or(0.0);
+    let bar_width = if points.len() > 1 {
+        (span / points.len() as f64 * 0.6).max(60.0)
+    } else {
+        3600.0
+    };
+
+    Plot::new(("statistics_trend", title))
+        .height(CHART_HEIGHT)
+        .include_y(0.0)
+        .allow_zoom(false)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .allow_boxed_zoom(false)
+        .allow_double_click_reset(false)
+        .x_axis_formatter(|mark, _range| format_date(mark.value, "%d/%m"))
+        .y_axis_formatter(move |mark, _range| format_value(mark.value))
+        .label_formatter(move |_name, point| format!("{}\n{}", format_date(point.x, "%d/%m/%Y %H:%M"), format_value(point.y)))
+        .show(ui, |plot_ui| {
+            match kind {
+                ChartKind::Bars => {
+                    let bars = points
+                        .iter()
+                        .enumerate()
+                        .map(|(i, [x, value])| {
+                            let failed = outcomes.is_some_and(|outcomes| outcomes[i] == RunOutcome::Failed);
+                            Bar::new(*x, *value)
+                                .width(bar_width)
+                                .fill(if failed { FAILURE_COLOR } else { color })
+                        })
+                        .collect();
+                    plot_ui.bar_chart(
+                        BarChart::new(bars)
+                            .color(color)
+                            .element_formatter(Box::new(move |bar, _chart| {
+                                format!("{}\n{}", format_date(bar.argument, "%d/%m/%Y %H:%M"), format_value(bar.value))
+                            })),
+                    );
+                }
+                ChartKind::Line => {
+                    plot_ui.line(Line::new(PlotPoints::from(points.to_vec())).color(color).width(2.0));
+                }
+            }
+
+            plot_ui.hline(
+                HLine::new(average)
+                    .color(color.gamma_multiply(0.6))
+                    .style(LineStyle::dashed_loose()),
+            );
+        });
+}
+
+/// Unix timestamp (as plotted on the x axis) in local time
+fn format_date(timestamp: f64, format: &str) -> String {
+    Local
+        .timestamp_opt(timestamp.round() as i64, 0)
+        .single()
+        .map(|date| date.format(format).to_string())
+        .unwrap_or_default()
+}
+
+/// Calendar heatmap: one column per week, one row per weekday
+/// Green intensity = runs that day, red = at least one failure
+fn calendar_heatmap(ui: &mut egui::Ui, records: &[&RunRecord]) {
+    let mut per_day: HashMap<NaiveDate, (usize, usize)> = HashMap::new();
+    for record in records {
+        if let Some(date) = Local.timestamp_opt(record.finished_at, 0).single() {
+            let entry = per_day.entry(date.date_naive()).or_default();
+            entry.0 += 1;
+            if record.outcome == RunOutcome::Failed {
+                entry.1 += 1;
+            }
+        }
+    }
+    let busiest = per_day.values().map(|(runs, _)| *runs).max().unwrap_or(1).max(1);
+
+    let today = Local::now().date_naive();
+    let first_monday = today
+        - Duration::days(today.weekday().num_days_from_monday() as i64)
+        - Duration::weeks(HEATMAP_WEEKS - 1);
+
+    let cell = 14.0;
+    let gap = 3.0;
+    let label_width = 28.0;
+    let size = egui::vec2(
+        label_width + HEATMAP_WEEKS as f32 * (cell + gap),
+        7.0 * (cell + gap) + 16.0,
+    );
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+    let origin = response.rect.left_top();
+    let visuals = ui.visuals();
+    let font = egui::FontId::proportional(10.0);
+
+    for (row, name) in ["Mon", "Wed", "Fri"].iter().enumerate() {
+        painter.text(
+            origin + egui::vec2(0.0, (row * 2) as f32 * (cell + gap) + cell / 2.0),
+            egui::Align2::LEFT_CENTER,
+            *name,
+            font.clone(),
+            visuals.weak_text_color(),
+        );
+    }
+
+    let mut hovered = None;
+    for week in 0..HEATMAP_WEEKS {
+        for weekday in 0..7 {
+            let date = first_monday + Duration::days(week * 7 + weekday);
+            if date > today {
+                continue;
+            }
+            let min = origin + egui::vec2(
+                label_width + week as f32 * (cell + gap),
+                weekday as f32 * (cell + gap),
+            );
+            let rect = egui::Rect::from_min_size(min, egui::vec2(cell, cell));
+            let (runs, failures) = per_day.get(&date).copied().unwrap_or_default();
+
+            let fill = if failures > 0 {
+                FAILURE_COLOR
+            } else if runs > 0 {
+                let intensity = 0.35 + 0.65 * runs as f32 / busiest as f32;
+                SUCCESS_COLOR.gamma_multiply(intensity)
+            } else {
+                visuals.faint_bg_color
+            };
+            painter.rect_filled(rect, 2.0, fill);
+
+            if response.hover_pos().is_some_and(|pos| rect.contains(pos)) {
+                painter.rect_stroke(rect, 2.0, egui::Stroke::new(1.0, visuals.text_color()));
+                hovered = Some((date, runs, failures));
+            }
+        }
+    }
+
+    let caption = match hovered {
+        Some((date, runs, failures)) => format!("{}: {} run(s), {} failed", date.format("%d/%m/%Y"), runs, failures),
+        None => "Hover a day to see its runs".to_string(),
+    };
+    painter.text(
+        origin + egui::vec2(label_width, 7.0 * (cell + gap) + 2.0),
+        egui::Align2::LEFT_TOP,
+        caption,
+        font,
+        visuals.weak_text_color(),
+    );
+}
@@ -57,13 +57,15 @@ Se registra en Windows Registry (HKCU\Run)
 pub const SOURCE_FOLDER_TOOLTIP: &str = r#"Carpeta de origen que se va a respaldar.
 📁 Debe existir y ser accesible
 Se copiarán todos los archivos y subcarpetas
-Ejemplo: C:\Users\TuUsuario\Documents"#;
+Admite variables: %USERPROFILE%, $VAR, ~, {Documents}, {Desktop}
+Ejemplo: C:\Users\TuUsuario\Documents o {Documents}"#;
 
 /// Tooltip para Destination Folder
 pub const DESTINATION_FOLDER_TOOLTIP: &str = r#"Carpeta de destino donde se guardará el backup.
 📦 Se creará automáticamente si no existe
 Recomendado: Usar un disco diferente al origen
-Ejemplo: D:\Backup\Documents"#;
+Admite variables: %USERNAME%, $VAR, ~, {Documents}, {Desktop}
+Ejemplo: D:\Backup\%USERNAME%\Documents"#;
 
 /// Helper function para mostrar tooltips con icono de ayuda
 pub fn show_tooltip_with_icon(ui: &mut egui::Ui, text: &str, tooltip: &str) -> egui::Response {